@@ -2,39 +2,864 @@
 //!
 //! This module generates the #[sea_orm(...)] attributes for entity fields.
 
+use crate::codegen::enum_gen::DbType;
+use crate::config::Backend;
 use crate::options::seaorm::FieldOptions;
-use crate::types::MappedType;
+use crate::types::{map_duration_type, map_temporal_type, wrapped_type, MappedType};
+use crate::GeneratorError;
+use heck::ToSnakeCase;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{DescriptorProto, FieldDescriptorProto, FileDescriptorProto};
 
-/// Generate SeaORM column attributes for a field
-pub struct ColumnAttributes {
-    /// The #[sea_orm(...)] attribute contents
-    pub attributes: Vec<String>,
-    /// The Rust type for this column
+/// Ensure a field marked `primary_key` can actually back a primary key column
+///
+/// Repeated fields and message fields stored as JSON have no usable equality in the
+/// database, so SeaORM would reject (or silently mis-key) them.
+pub fn validate_primary_key(
+    message_name: &str,
+    field: &FieldDescriptorProto,
+    field_options: Option<&FieldOptions>,
+    mapped_type: &MappedType,
+) -> Result<(), GeneratorError> {
+    let Some(opts) = field_options.filter(|o| o.primary_key) else {
+        return Ok(());
+    };
+
+    let field_name = field.name.as_deref().unwrap_or("");
+    let reason = if field.label() == Label::Repeated {
+        Some("repeated fields cannot be primary keys")
+    } else if opts.embed || mapped_type.column_type.as_deref() == Some("JsonBinary") {
+        Some("message fields stored as JSON cannot be primary keys")
+    } else {
+        None
+    };
+
+    match reason {
+        Some(reason) => Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} is marked primary_key, but {}; use a scalar field instead",
+            message_name, field_name, reason
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Convert a proto field name to a snake_case column name, keeping acronyms whole
+///
+/// `heck` splits runs of capitals letter by letter or not at all (`APIURL` becomes
+/// `apiurl`). Each configured acronym (e.g. `API`, `URL`, `ID`) found at a word
+/// boundary is treated as a single word instead, so `APIURL` becomes `api_url` and
+/// `UserID` becomes `user_id`. Without acronyms this is plain `to_snake_case`.
+pub fn to_column_name(name: &str, acronyms: &[String]) -> String {
+    if acronyms.is_empty() {
+        return name.to_snake_case();
+    }
+
+    let chars: Vec<char> = name.chars().collect();
+    let acronym_at = |i: usize| {
+        acronyms.iter().find(|acronym| {
+            let len = acronym.chars().count();
+            i + len <= chars.len() && chars[i..i + len].iter().copied().eq(acronym.chars())
+        })
+    };
+
+    let mut out = String::with_capacity(name.len());
+    let mut i = 0;
+    let mut at_word_start = true;
+    while i < chars.len() {
+        let starts_word = at_word_start || !chars[i - 1].is_ascii_uppercase();
+        if let Some(acronym) = acronym_at(i).filter(|_| starts_word) {
+            let end = i + acronym.chars().count();
+            // The acronym must be followed by a word boundary, not more of a capitalized run
+            let ends_word = match (chars.get(end), chars.get(end + 1)) {
+                (None, _) => true,
+                (Some(c), _) if !c.is_ascii_uppercase() => !c.is_ascii_lowercase(),
+                (Some(_), next) => {
+                    next.map(|n| n.is_ascii_lowercase()).unwrap_or(false)
+                        || acronym_at(end).is_some()
+                }
+            };
+            if ends_word {
+                // Capitalize it like a regular word so heck keeps it together
+                let mut word = acronym.chars();
+                out.extend(word.next());
+                out.extend(word.flat_map(char::to_lowercase));
+                i = end;
+                at_word_start = true;
+                continue;
+            }
+        }
+        out.push(chars[i]);
+        at_word_start = false;
+        i += 1;
+    }
+
+    out.to_snake_case()
+}
+
+/// Column name produced by the `column_name_template` parameter, if it renames the column
+///
+/// `{field}` in the template is replaced with the default column name, so `c_{field}`
+/// turns `email` into `c_email`. Returns `None` when there is no template or it leaves
+/// the name unchanged, since no `column_name` attribute is needed then.
+pub fn templated_column_name(template: Option<&str>, column: &str) -> Option<String> {
+    template
+        .map(|template| template.replace("{field}", column))
+        .filter(|name| name != column)
+}
+
+/// Shorten an identifier to fit the `max_identifier_length` parameter
+///
+/// Names within the limit are kept. Longer ones are cut and suffixed with `_` and an
+/// 8-digit hash of the full name, so they stay deterministic and distinct names sharing
+/// a long prefix don't collide once the database truncates them.
+pub fn shorten_identifier(name: &str, max_len: Option<usize>) -> String {
+    let Some(max_len) = max_len.filter(|max| name.len() > *max) else {
+        return name.to_string();
+    };
+
+    // 32-bit FNV-1a, stable across Rust versions unlike the std hashers
+    let hash = name.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    let suffix = format!("_{:08x}", hash);
+    let mut keep = max_len.saturating_sub(suffix.len());
+    while !name.is_char_boundary(keep) {
+        keep -= 1;
+    }
+    format!("{}{}", name[..keep].trim_end_matches('_'), suffix)
+}
+
+/// Ensure a column sets at most one insert default
+///
+/// `insert_default` is rendered as a `default_expr`, so combining it with `default_value`
+/// or `default_expr` would give the column two defaults.
+pub fn validate_insert_default(
+    message_name: &str,
+    field: &FieldDescriptorProto,
+    field_options: Option<&FieldOptions>,
+) -> Result<(), GeneratorError> {
+    match field_options {
+        Some(opts)
+            if !opts.insert_default.is_empty()
+                && (!opts.default_value.is_empty() || !opts.default_expr.is_empty()) =>
+        {
+            Err(GeneratorError::InvalidConfig(format!(
+                "{}.{} sets insert_default together with default_value or default_expr; keep one",
+                message_name,
+                field.name.as_deref().unwrap_or("")
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Ensure a generated column doesn't also take its value from the model
+///
+/// The database computes `generated` columns, so they can't be primary keys, carry
+/// defaults or be stamped, and `stored` only applies to a `generated` column.
+pub fn validate_generated(
+    message_name: &str,
+    field: &FieldDescriptorProto,
+    field_options: Option<&FieldOptions>,
+) -> Result<(), GeneratorError> {
+    let Some(opts) = field_options else {
+        return Ok(());
+    };
+    let field_name = field.name.as_deref().unwrap_or("");
+    if opts.generated.is_empty() {
+        if opts.stored {
+            return Err(GeneratorError::InvalidConfig(format!(
+                "{}.{} sets stored without a generated expression",
+                message_name, field_name
+            )));
+        }
+        return Ok(());
+    }
+    let conflict = [
+        ("primary_key", opts.primary_key),
+        ("default_value", !opts.default_value.is_empty()),
+        ("default_expr", !opts.default_expr.is_empty()),
+        ("insert_default", !opts.insert_default.is_empty()),
+        ("update_default", !opts.update_default.is_empty()),
+        ("auto_create_time", opts.auto_create_time),
+        ("auto_update_time", opts.auto_update_time),
+    ]
+    .into_iter()
+    .find(|(_, set)| *set);
+    match conflict {
+        Some((option, _)) => Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} is generated by the database and can't also set {}",
+            message_name, field_name, option
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Column definition suffix of a `generated` column, such as
+/// `GENERATED ALWAYS AS (a || b) STORED`
+///
+/// Postgres only supports `STORED` generated columns (before 18), so they are always
+/// stored there; MySQL and SQLite compute them on read unless `stored` is set.
+pub fn generated_column_sql(opts: &FieldOptions, backend: Backend) -> Option<String> {
+    if opts.generated.is_empty() {
+        return None;
+    }
+    let stored = opts.stored || backend == Backend::Postgres;
+    Some(format!(
+        "GENERATED ALWAYS AS ({}) {}",
+        opts.generated.trim(),
+        if stored { "STORED" } else { "VIRTUAL" }
+    ))
+}
+
+/// Whether an `update_default` expression means "the current time"
+///
+/// These can be stamped from Rust in `before_save` instead of only in bulk updates.
+pub fn is_current_timestamp_expr(expr: &str) -> bool {
+    matches!(
+        expr.trim().to_lowercase().as_str(),
+        "now()" | "current_timestamp" | "current_timestamp()" | "localtimestamp"
+    )
+}
+
+/// Index attribute for a column: `unique`, or `indexed` for a plain secondary index
+///
+/// A unique column is already backed by an index, so `indexed` is dropped when both
+/// are set.
+pub fn index_attribute(field_options: &FieldOptions) -> Option<&'static str> {
+    if field_options.unique {
+        Some("unique")
+    } else if field_options.indexed {
+        Some("indexed")
+    } else {
+        None
+    }
+}
+
+/// How a repeated field is stored, selected with `repeated_as`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepeatedStorage {
+    /// A JSON array in a `JsonBinary` column
+    Jsonb,
+    /// A length-prefixed little-endian blob in a binary column
+    Blob,
+}
+
+/// Element types that can be packed into a `repeated_as: "blob"` column
+const BLOB_ELEMENT_TYPES: &[&str] = &["i32", "i64", "u32", "u64", "f32", "f64"];
+
+/// Resolve the `repeated_as` storage for a field, if any
+///
+/// `element_type` is the Rust type of a single element. Blob storage packs fixed-width
+/// numbers, so other element types are rejected.
+pub fn repeated_storage(
+    message_name: &str,
+    field: &FieldDescriptorProto,
+    field_options: Option<&FieldOptions>,
+    element_type: &str,
+) -> Result<Option<RepeatedStorage>, GeneratorError> {
+    let Some(repeated_as) = field_options
+        .map(|o| o.repeated_as.as_str())
+        .filter(|r| !r.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let field_name = field.name.as_deref().unwrap_or("");
+    if field.label() != Label::Repeated {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} sets repeated_as, but is not a repeated field",
+            message_name, field_name
+        )));
+    }
+
+    match repeated_as {
+        "jsonb" => Ok(Some(RepeatedStorage::Jsonb)),
+        "blob" if BLOB_ELEMENT_TYPES.contains(&element_type) => Ok(Some(RepeatedStorage::Blob)),
+        "blob" => Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} uses repeated_as: \"blob\", which requires a numeric element type, not {}",
+            message_name, field_name, element_type
+        ))),
+        other => Err(GeneratorError::InvalidConfig(format!(
+            "Unknown repeated_as \"{}\" on {}.{}; expected \"jsonb\" or \"blob\"",
+            other, message_name, field_name
+        ))),
+    }
+}
+
+/// Resolve the `enum_as` storage for an enum field, if any
+///
+/// `"string"` stores the value's name and `"int"` its number, through an active enum
+/// generated for enums without `(seaorm.enum_opt)`.
+pub fn enum_storage(
+    message_name: &str,
+    field: &FieldDescriptorProto,
+    field_options: Option<&FieldOptions>,
+) -> Result<Option<DbType>, GeneratorError> {
+    let Some(enum_as) = field_options
+        .map(|o| o.enum_as.as_str())
+        .filter(|e| !e.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let field_name = field.name.as_deref().unwrap_or("");
+    if field.r#type() != Type::Enum {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} sets enum_as, but is not an enum field",
+            message_name, field_name
+        )));
+    }
+
+    match enum_as {
+        "string" => Ok(Some(DbType::String)),
+        "int" => Ok(Some(DbType::Integer)),
+        other => Err(GeneratorError::InvalidConfig(format!(
+            "Unknown enum_as \"{}\" on {}.{}; expected \"string\" or \"int\"",
+            other, message_name, field_name
+        ))),
+    }
+}
+
+/// Cast attributes for a string-backed enum column over an integer enum
+///
+/// Values are cast to `text` both when selected and when saved, so the column reads
+/// and writes variant names whatever textual SQL type backs it.
+pub fn text_cast_attribute() -> String {
+    "select_as = \"text\", save_as = \"text\"".to_string()
+}
+
+/// An integer column stored wider than its proto field, selected with `widen`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Widening {
+    /// Rust type of the proto field (e.g. `i32`)
+    pub proto_type: String,
+    /// Rust type of the column (e.g. `i64`)
+    pub rust_type: &'static str,
+    /// SeaORM column type backing the wider type
+    pub column_type: &'static str,
+}
+
+/// Supported widenings as (proto type, column type, SeaORM column type)
+///
+/// Each narrows back with `TryFrom`, which the generated conversions rely on.
+const WIDENINGS: &[(&str, &str, &str)] = &[
+    ("i32", "i64", "BigInteger"),
+    ("u32", "i64", "BigInteger"),
+    ("u32", "u64", "BigUnsigned"),
+];
+
+/// Resolve the widening for a field, if any
+///
+/// An explicit `widen` applies to any integer field; with `widen_pk` (the plugin
+/// parameter) 32-bit primary keys without one are widened to `i64`. Unsupported
+/// combinations are rejected rather than silently keeping the proto type.
+pub fn widening(
+    message_name: &str,
+    field: &FieldDescriptorProto,
+    field_options: Option<&FieldOptions>,
+    mapped_type: &MappedType,
+    widen_pk: bool,
+) -> Result<Option<Widening>, GeneratorError> {
+    let proto_type = mapped_type.rust_type.as_str();
+    let target = match field_options.map(|o| o.widen.as_str()) {
+        Some(widen) if !widen.is_empty() => widen,
+        _ if widen_pk
+            && field_options.map(|o| o.primary_key).unwrap_or(false)
+            && matches!(proto_type, "i32" | "u32") =>
+        {
+            "i64"
+        }
+        _ => return Ok(None),
+    };
+
+    let field_name = field.name.as_deref().unwrap_or("");
+    if field.label() == Label::Repeated {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} sets widen, but repeated fields cannot be widened",
+            message_name, field_name
+        )));
+    }
+
+    WIDENINGS
+        .iter()
+        .find(|(from, to, _)| *from == proto_type && *to == target)
+        .map(|&(_, rust_type, column_type)| {
+            Some(Widening {
+                proto_type: proto_type.to_string(),
+                rust_type,
+                column_type,
+            })
+        })
+        .ok_or_else(|| {
+            GeneratorError::InvalidConfig(format!(
+                "{}.{} cannot be widened from {} to \"{}\"",
+                message_name, field_name, proto_type, target
+            ))
+        })
+}
+
+/// Resolve the `temporal` override of a Timestamp field, if any
+///
+/// Only `google.protobuf.Timestamp` fields can pick their chrono type, and an explicit
+/// `column_type` can't be combined with one.
+pub fn temporal_override(
+    message_name: &str,
+    field: &FieldDescriptorProto,
+    field_options: Option<&FieldOptions>,
+) -> Result<Option<MappedType>, GeneratorError> {
+    let Some(options) = field_options.filter(|o| !o.temporal.is_empty()) else {
+        return Ok(None);
+    };
+
+    let field_name = field.name.as_deref().unwrap_or("");
+    if field.type_name.as_deref() != Some(".google.protobuf.Timestamp") {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} sets temporal, but only google.protobuf.Timestamp fields can",
+            message_name, field_name
+        )));
+    }
+    if !options.column_type.is_empty() {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} sets both temporal and column_type; use one of them",
+            message_name, field_name
+        )));
+    }
+
+    map_temporal_type(&options.temporal)
+        .map(Some)
+        .ok_or_else(|| {
+            GeneratorError::InvalidConfig(format!(
+                "{}.{} has temporal \"{}\"; expected naive, utc, local, date or time",
+                message_name, field_name, options.temporal
+            ))
+        })
+}
+
+/// Resolve the `duration_as` representation of a Duration field, if any
+///
+/// Only `google.protobuf.Duration` fields can pick their representation, and an
+/// explicit `column_type` can't be combined with one.
+pub fn duration_override(
+    message_name: &str,
+    field: &FieldDescriptorProto,
+    field_options: Option<&FieldOptions>,
+) -> Result<Option<MappedType>, GeneratorError> {
+    let Some(options) = field_options.filter(|o| !o.duration_as.is_empty()) else {
+        return Ok(None);
+    };
+
+    let field_name = field.name.as_deref().unwrap_or("");
+    if field.type_name.as_deref() != Some(".google.protobuf.Duration") {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} sets duration_as, but only google.protobuf.Duration fields can",
+            message_name, field_name
+        )));
+    }
+    if !options.column_type.is_empty() {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} sets both duration_as and column_type; use one of them",
+            message_name, field_name
+        )));
+    }
+
+    map_duration_type(&options.duration_as)
+        .map(Some)
+        .ok_or_else(|| {
+            GeneratorError::InvalidConfig(format!(
+                "{}.{} has duration_as \"{}\"; expected nanos or interval",
+                message_name, field_name, options.duration_as
+            ))
+        })
+}
+
+/// A column whose field uses a custom Rust type, selected with `rust_type`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomType {
+    /// Rust type of the model field (e.g. `UserId`)
     pub rust_type: String,
+    /// Inner type of a newtype to generate value adapters for (`newtype_inner`)
+    pub newtype_inner: Option<String>,
+}
+
+/// Resolve the `rust_type` override of a field, if any
+///
+/// `newtype_inner` needs a `rust_type` and must be the type the proto field maps to,
+/// since the adapters store the newtype as that type. Custom types can't be combined
+/// with `widen` or `repeated_as`, which pick their own types.
+pub fn custom_type(
+    message_name: &str,
+    field: &FieldDescriptorProto,
+    field_options: Option<&FieldOptions>,
+    mapped_type: &MappedType,
+) -> Result<Option<CustomType>, GeneratorError> {
+    let Some(options) = field_options else {
+        return Ok(None);
+    };
+    let field_name = field.name.as_deref().unwrap_or("");
+
+    if options.rust_type.is_empty() {
+        if !options.newtype_inner.is_empty() {
+            return Err(GeneratorError::InvalidConfig(format!(
+                "{}.{} sets newtype_inner without a rust_type",
+                message_name, field_name
+            )));
+        }
+        return Ok(None);
+    }
+    if syn::parse_str::<syn::Type>(&options.rust_type).is_err() {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} has rust_type \"{}\", which is not a Rust type",
+            message_name, field_name, options.rust_type
+        )));
+    }
+    if !options.widen.is_empty() || !options.repeated_as.is_empty() {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} sets rust_type together with widen or repeated_as",
+            message_name, field_name
+        )));
+    }
+
+    let newtype_inner = Some(options.newtype_inner.clone()).filter(|inner| !inner.is_empty());
+    if let Some(inner) = &newtype_inner {
+        let proto_type = mapped_type
+            .rust_type
+            .strip_prefix("Option<")
+            .and_then(|t| t.strip_suffix('>'))
+            .unwrap_or(&mapped_type.rust_type);
+        if inner != proto_type {
+            return Err(GeneratorError::InvalidConfig(format!(
+                "{}.{} has newtype_inner \"{}\", but the field maps to {}",
+                message_name, field_name, inner, proto_type
+            )));
+        }
+    }
+
+    Ok(Some(CustomType {
+        rust_type: options.rust_type.clone(),
+        newtype_inner,
+    }))
+}
+
+/// The `#[deprecated]` note of a field declared with `[deprecated = true]`
+///
+/// Returns None for fields that aren't deprecated, and `Some(None)` when no note is
+/// given. The note is taken from a `deprecated: <note>` line in the field's trailing
+/// proto comment, falling back to its leading comment.
+pub fn deprecation_note(
+    file: &FileDescriptorProto,
+    message: &DescriptorProto,
+    field_index: usize,
+) -> Option<Option<String>> {
+    let field = message.field.get(field_index)?;
+    if !field
+        .options
+        .as_ref()
+        .and_then(|o| o.deprecated)
+        .unwrap_or(false)
+    {
+        return None;
+    }
+
+    Some(comment_note(file, message, field_index))
+}
+
+/// The note of a `deprecated: <note>` line in a field's proto comments
+fn comment_note(
+    file: &FileDescriptorProto,
+    message: &DescriptorProto,
+    field_index: usize,
+) -> Option<String> {
+    let mut path = message_path(&file.message_type, message, vec![4])?;
+    path.extend([2, field_index as i32]);
+    let location = file
+        .source_code_info
+        .as_ref()?
+        .location
+        .iter()
+        .find(|location| location.path == path)?;
+
+    [&location.trailing_comments, &location.leading_comments]
+        .into_iter()
+        .flatten()
+        .flat_map(|comment| comment.lines())
+        .find_map(|line| line.trim().strip_prefix("deprecated:"))
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty())
+}
+
+/// SourceCodeInfo path of `message`, searching `messages` below `prefix`
+///
+/// Top-level messages live at `[4, i]`, nested ones append `[3, j]` per level.
+fn message_path(
+    messages: &[DescriptorProto],
+    message: &DescriptorProto,
+    prefix: Vec<i32>,
+) -> Option<Vec<i32>> {
+    messages.iter().enumerate().find_map(|(index, candidate)| {
+        let mut path = prefix.clone();
+        path.push(index as i32);
+        if std::ptr::eq(candidate, message) {
+            return Some(path);
+        }
+        path.push(3);
+        message_path(&candidate.nested_type, message, path)
+    })
+}
+
+/// Render a `column_type = "..."` attribute
+///
+/// The type is written as an escaped string literal, so custom SQL types carrying their
+/// own quoted name (`Custom("geography(Point,4326)")`) survive intact.
+pub fn column_type_attribute(column_type: &str) -> String {
+    format!("column_type = {:?}", column_type)
+}
+
+/// Whether a `column_type` names a custom SQL type (`Custom("...")`)
+///
+/// SeaORM has no Rust type for these, so the field keeps its proto-mapped type.
+pub fn is_custom_column_type(column_type: &str) -> bool {
+    column_type.starts_with("Custom(") || column_type.starts_with("custom(")
+}
+
+/// Column type for a length-limited string column
+///
+/// `max_length: 255` on a string field produces `String(StringLen::N(255))` (a
+/// `VARCHAR(255)`). Without it the column keeps SeaORM's default string type.
+/// Non-string fields ignore `max_length`.
+pub fn max_length_column_type(
+    field_options: &FieldOptions,
+    mapped_type: &MappedType,
+) -> Option<String> {
+    let is_string = matches!(mapped_type.rust_type.as_str(), "String" | "Option<String>");
+    if !is_string || field_options.max_length <= 0 {
+        return None;
+    }
+    Some(format!(
+        "String(StringLen::N({}))",
+        field_options.max_length
+    ))
+}
+
+/// Column type spelled out for a field without an explicit `column_type`
+///
+/// Bytes, Timestamps and Durations always name their column type (binary, time zone
+/// aware timestamp, nanoseconds or interval) instead of leaving it to SeaORM's default
+/// for the Rust type, as do the `BytesValue` wrappers of bytes. Repeated fields are
+/// stored whole and keep their own type.
+pub fn default_column_type<'a>(
+    field: &FieldDescriptorProto,
+    mapped_type: &'a MappedType,
+) -> Option<&'a str> {
+    let spelled_out = field.r#type() == Type::Bytes
+        || matches!(
+            field.type_name.as_deref(),
+            Some(".google.protobuf.Timestamp" | ".google.protobuf.Duration")
+        )
+        || field.type_name.as_deref().and_then(wrapped_type) == Some(Type::Bytes);
+    if !spelled_out || field.label() == Label::Repeated {
+        return None;
+    }
+    mapped_type.column_type.as_deref()
 }
 
 /// Generate column attributes from field options and mapped type
+///
+/// Attributes come in the order key, index, column name, column type and defaults.
+/// An explicit `column_type` wins over `embed` (stored as the backend's
+/// `json_column_type`) and `max_length`. `proto_default` is the column default from
+/// the proto2 descriptor, given only when no seaorm default is configured. Column
+/// types implied by the field type alone are left to `default_column_type`.
 pub fn generate_attributes(
-    _field_options: Option<&FieldOptions>,
+    field_options: Option<&FieldOptions>,
     mapped_type: &MappedType,
-    is_nullable: bool,
-) -> ColumnAttributes {
-    let attributes = Vec::new();
-    let mut rust_type = mapped_type.rust_type.clone();
+    proto_default: Option<&str>,
+    json_column_type: &str,
+) -> Vec<String> {
+    let mut attrs = Vec::new();
+
+    if let Some(opts) = field_options {
+        if opts.primary_key {
+            if opts.auto_increment {
+                attrs.push("primary_key".to_string());
+            } else {
+                attrs.push("primary_key, auto_increment = false".to_string());
+            }
+        }
+
+        if let Some(index) = index_attribute(opts) {
+            attrs.push(index.to_string());
+        }
+
+        if !opts.column_name.is_empty() {
+            attrs.push(format!("column_name = {:?}", opts.column_name));
+        }
+
+        // Handle column_type - embed implies the backend's JSON type if not explicitly set
+        if !opts.column_type.is_empty() {
+            // Map common type names to SeaORM 2.0 ColumnType variants
+            let column_type = match opts.column_type.as_str() {
+                "JsonB" | "Jsonb" | "jsonb" => "JsonBinary",
+                other => other,
+            };
+            attrs.push(column_type_attribute(column_type));
+        } else if opts.embed {
+            attrs.push(column_type_attribute(json_column_type));
+        } else if let Some(column_type) = max_length_column_type(opts, mapped_type) {
+            attrs.push(column_type_attribute(&column_type));
+        }
+
+        if !opts.default_value.is_empty() {
+            attrs.push(format!("default_value = {:?}", opts.default_value));
+        }
+
+        if !opts.default_expr.is_empty() {
+            attrs.push(format!("default_expr = {:?}", opts.default_expr));
+        }
+
+        // The database fills the column on insert; default_expr holds a Rust expression
+        if !opts.insert_default.is_empty() {
+            let expr = format!("Expr::cust({:?})", opts.insert_default);
+            attrs.push(format!("default_expr = {:?}", expr));
+        }
+    }
+
+    // Fall back to the proto-native default when no seaorm default is set
+    if let Some(default) = proto_default {
+        attrs.push(format!("default_value = {:?}", default));
+    }
+
+    attrs
+}
 
-    // TODO: Add attribute generation based on field_options
-    // - primary_key
-    // - auto_increment
-    // - unique
-    // - column_name
-    // - column_type
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::map_proto_type;
+    use prost_types::field_descriptor_proto::Type;
 
-    if is_nullable && !rust_type.starts_with("Option<") {
-        rust_type = format!("Option<{}>", rust_type);
+    #[test]
+    fn test_to_column_name_with_acronyms() {
+        let acronyms: Vec<String> = ["ID", "URL", "API"].map(String::from).to_vec();
+
+        assert_eq!(to_column_name("UserID", &acronyms), "user_id");
+        assert_eq!(to_column_name("APIKey", &acronyms), "api_key");
+        assert_eq!(to_column_name("APIURL", &acronyms), "api_url");
+        assert_eq!(to_column_name("avatarURL", &acronyms), "avatar_url");
+        assert_eq!(to_column_name("created_at", &acronyms), "created_at");
+
+        // Acronyms embedded in a longer capitalized run are left alone
+        assert_eq!(to_column_name("IDENTITY", &acronyms), "identity");
+
+        // Without acronyms runs of capitals follow heck
+        assert_eq!(to_column_name("APIURL", &[]), "apiurl");
+    }
+
+    #[test]
+    fn test_max_length_column_type() {
+        let string = map_proto_type(Type::String, None);
+        let opts = FieldOptions {
+            max_length: 255,
+            ..Default::default()
+        };
+        assert_eq!(
+            max_length_column_type(&opts, &string).as_deref(),
+            Some("String(StringLen::N(255))")
+        );
+
+        // No max_length keeps the default string column type
+        assert_eq!(
+            max_length_column_type(&FieldOptions::default(), &string),
+            None
+        );
+
+        // Only string fields are length-limited
+        let int = map_proto_type(Type::Int64, None);
+        assert_eq!(max_length_column_type(&opts, &int), None);
+    }
+
+    #[test]
+    fn test_generate_attributes_from_options() {
+        let int = map_proto_type(Type::Int64, None);
+        let opts = FieldOptions {
+            primary_key: true,
+            auto_increment: true,
+            unique: true,
+            column_name: "user_id".to_string(),
+            column_type: "BigInteger".to_string(),
+            default_value: "0".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            generate_attributes(Some(&opts), &int, None, "JsonBinary"),
+            [
+                "primary_key",
+                "unique",
+                "column_name = \"user_id\"",
+                "column_type = \"BigInteger\"",
+                "default_value = \"0\"",
+            ]
+        );
+
+        // Keys without auto_increment say so; no options means no attributes
+        let opts = FieldOptions {
+            primary_key: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_attributes(Some(&opts), &int, None, "JsonBinary"),
+            ["primary_key, auto_increment = false"]
+        );
+        assert!(generate_attributes(None, &int, None, "JsonBinary").is_empty());
+
+        // The proto default stands in for a missing seaorm default
+        assert_eq!(
+            generate_attributes(None, &int, Some("3"), "JsonBinary"),
+            ["default_value = \"3\""]
+        );
     }
 
-    ColumnAttributes {
-        attributes,
-        rust_type,
+    #[test]
+    fn test_generate_attributes_column_type_override() {
+        let message = map_proto_type(Type::Message, Some(".test.Address"));
+        let embed = FieldOptions {
+            embed: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_attributes(Some(&embed), &message, None, "Json"),
+            ["column_type = \"Json\""]
+        );
+
+        let opts = FieldOptions {
+            embed: true,
+            column_type: "jsonb".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_attributes(Some(&opts), &message, None, "Json"),
+            ["column_type = \"JsonBinary\""]
+        );
+    }
+
+    #[test]
+    fn test_shorten_identifier() {
+        assert_eq!(shorten_identifier("users", Some(30)), "users");
+        assert_eq!(
+            shorten_identifier("a_rather_long_table_name", None),
+            "a_rather_long_table_name"
+        );
+
+        let first = shorten_identifier("customer_subscription_invoice_line_items", Some(30));
+        let second = shorten_identifier("customer_subscription_invoice_line_totals", Some(30));
+        assert_eq!(first.len(), 30);
+        assert!(first.starts_with("customer_subscription_"));
+        assert_ne!(first, second, "names sharing a prefix should stay distinct");
+        assert_eq!(
+            first,
+            shorten_identifier("customer_subscription_invoice_line_items", Some(30)),
+            "shortening should be deterministic"
+        );
     }
 }
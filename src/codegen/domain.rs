@@ -4,6 +4,8 @@
 //! `seaorm.input` field options. The generated types include garde validation
 //! attributes and TryFrom implementations for converting from proto requests.
 
+use crate::codegen::relation::related_module_path;
+use crate::codegen::{non_exhaustive_attribute, specta_derive, Context};
 use crate::options::{
     get_cached_input_message_options, get_cached_input_options, parse_input_message_options,
     parse_input_options, seaorm,
@@ -17,6 +19,7 @@ use quote::{format_ident, quote};
 
 /// Generate a domain type from a protobuf message with input options
 pub fn generate(
+    ctx: &Context,
     file: &FileDescriptorProto,
     message: &DescriptorProto,
 ) -> Result<Option<File>, GeneratorError> {
@@ -59,34 +62,29 @@ pub fn generate(
     let module_name = domain_type_name.to_snake_case();
     let output_filename = format!(
         "{}/{}.rs",
-        file.package.as_deref().unwrap_or("").replace('.', "/"),
+        crate::generator::package_dir(&ctx.config, file.package.as_deref().unwrap_or("")),
         module_name
     );
 
-    // Generate domain struct fields and validation attributes
-    let (field_tokens, try_from_conversions) =
-        generate_domain_fields(file_name, message_name, &message.field)?;
-
-    // Build the struct
-    let struct_ident = format_ident!("{}", domain_type_name);
-    let proto_ident = format_ident!("{}", message_name);
-
-    // Build doc comments
-    let struct_doc = format!("Domain type for {}", message_name);
-
-    let code = if input_message_opts.generate_try_from {
+    // Errors go to a shared type when configured, otherwise to a local DomainError
+    let error_type: syn::Path = if input_message_opts.error_type.is_empty() {
+        syn::parse_quote!(DomainError)
+    } else {
+        syn::parse_str(&input_message_opts.error_type).map_err(|e| {
+            GeneratorError::InvalidConfig(format!(
+                "Invalid error_type \"{}\" on {}: {}",
+                input_message_opts.error_type, message_name, e
+            ))
+        })?
+    };
+    // The TryFrom impl and the builder both validate, reporting through the error type
+    let validated = input_message_opts.generate_try_from || input_message_opts.generate_builder;
+    let local_error = if validated && input_message_opts.error_type.is_empty() {
+        let non_exhaustive = non_exhaustive_attribute(ctx.config.non_exhaustive);
         quote! {
-            //! Domain type generated from protobuf message
-            //!
-            //! Generated by protoc-gen-seaorm with garde validation.
-            //! @generated
-
-            #[allow(unused_imports)]
-            use super::prelude::*;
-            use garde::Validate;
-
             /// Validation/conversion error for domain types
             #[derive(Debug, thiserror::Error)]
+            #non_exhaustive
             pub enum DomainError {
                 /// Validation failed
                 #[error("validation error: {0}")]
@@ -96,14 +94,73 @@ pub fn generate(
                 Conversion(String),
             }
 
-            #[doc = #struct_doc]
-            #[derive(Debug, Clone, garde::Validate)]
-            pub struct #struct_ident {
-                #(#field_tokens),*
+            impl DomainError {
+                /// Error messages keyed by the field they concern, e.g. for API responses
+                ///
+                /// Validation errors are keyed by their garde path (`email`,
+                /// `tags[0]`); errors not tied to a field have an empty key.
+                pub fn field_errors(&self) -> Vec<(String, String)> {
+                    match self {
+                        Self::Validation(report) => report
+                            .iter()
+                            .map(|(path, error)| (path.to_string(), error.to_string()))
+                            .collect(),
+                        Self::Conversion(message) => match message.split_once(": ") {
+                            Some((field, error)) => vec![(field.to_string(), error.to_string())],
+                            None => vec![(String::new(), message.clone())],
+                        },
+                    }
+                }
             }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate domain struct fields and validation attributes
+    let selected = select_fields(message_name, message, &input_message_opts.fields)?;
+    let domain_fields = generate_domain_fields(file_name, message_name, &selected, &error_type)?;
+    let field_tokens: Vec<&TokenStream> = domain_fields.iter().map(|f| &f.tokens).collect();
+    let try_from_conversions: Vec<&TokenStream> =
+        domain_fields.iter().map(|f| &f.conversion).collect();
+
+    // Build the struct
+    let struct_ident = format_ident!("{}", domain_type_name);
+
+    // Generate IntoActiveModel when the domain type targets an entity
+    let into_active_model = if input_message_opts.entity.is_empty() {
+        quote! {}
+    } else {
+        generate_into_active_model(
+            ctx,
+            file,
+            &input_message_opts.entity,
+            &struct_ident,
+            &domain_fields,
+        )?
+    };
+
+    let proto_ident = format_ident!("{}", message_name);
+
+    // Build doc comments
+    let struct_doc = format!("Domain type for {}", message_name);
+    let specta = specta_derive(ctx.config.derive_specta);
+    let arbitrary_impl = if ctx.config.derive_arbitrary {
+        generate_arbitrary_impl(&struct_ident, &domain_fields)
+    } else {
+        quote! {}
+    };
+
+    let validate_import = if validated {
+        quote! { use garde::Validate; }
+    } else {
+        quote! {}
+    };
 
+    let try_from_impl = if input_message_opts.generate_try_from {
+        quote! {
             impl TryFrom<#proto_ident> for #struct_ident {
-                type Error = DomainError;
+                type Error = #error_type;
 
                 fn try_from(proto: #proto_ident) -> Result<Self, Self::Error> {
                     let domain = Self {
@@ -115,21 +172,41 @@ pub fn generate(
             }
         }
     } else {
-        quote! {
-            //! Domain type generated from protobuf message
-            //!
-            //! Generated by protoc-gen-seaorm with garde validation.
-            //! @generated
-
-            #[allow(unused_imports)]
-            use super::prelude::*;
-
-            #[doc = #struct_doc]
-            #[derive(Debug, Clone, garde::Validate)]
-            pub struct #struct_ident {
-                #(#field_tokens),*
-            }
+        quote! {}
+    };
+
+    let builder = if input_message_opts.generate_builder {
+        generate_builder(&struct_ident, &domain_fields, &error_type)
+    } else {
+        quote! {}
+    };
+
+    let code = quote! {
+        //! Domain type generated from protobuf message
+        //!
+        //! Generated by protoc-gen-seaorm with garde validation.
+        //! @generated
+
+        #[allow(unused_imports)]
+        use super::prelude::*;
+        #validate_import
+
+        #local_error
+
+        #[doc = #struct_doc]
+        #[derive(Debug, Clone, garde::Validate)]
+        #specta
+        pub struct #struct_ident {
+            #(#field_tokens),*
         }
+
+        #try_from_impl
+
+        #builder
+
+        #into_active_model
+
+        #arbitrary_impl
     };
 
     // Format the generated code
@@ -148,14 +225,231 @@ pub fn generate(
     }))
 }
 
+/// A field emitted on a domain struct
+struct DomainField {
+    /// Field name on the domain struct
+    name: String,
+    /// Whether the field uses a custom (converted) type rather than the proto type
+    custom_type: bool,
+    /// Rust type of the field on the domain struct
+    rust_type: String,
+    /// Struct field definition with garde attributes
+    tokens: TokenStream,
+    /// Initializer used in the TryFrom implementation
+    conversion: TokenStream,
+    /// Expression drawing a valid value from `u` in the `Arbitrary` implementation
+    arbitrary: TokenStream,
+}
+
+/// Generate `<Domain>Builder`, collecting fields fluently and validating in `build()`
+///
+/// Every setter takes `impl Into<T>`. `build()` fails with a `Conversion` error naming
+/// the first required field that was never set (`Option` and `Vec` fields default to
+/// empty), then runs the garde rules like `TryFrom` does.
+fn generate_builder(
+    struct_ident: &proc_macro2::Ident,
+    fields: &[DomainField],
+    error_type: &syn::Path,
+) -> TokenStream {
+    let builder_ident = format_ident!("{}Builder", struct_ident);
+    let builder_doc = format!("Builder for [`{}`], validated on `build()`", struct_ident);
+
+    let mut builder_fields = Vec::new();
+    let mut setters = Vec::new();
+    let mut initializers = Vec::new();
+    for field in fields {
+        let ident = format_ident!("{}", field.name);
+        let ty: TokenStream = field.rust_type.parse().unwrap_or_else(|_| quote!(String));
+        let setter_doc = format!("Set `{}`", field.name);
+        let name = &field.name;
+
+        builder_fields.push(quote! { #ident: Option<#ty> });
+        setters.push(quote! {
+            #[doc = #setter_doc]
+            pub fn #ident(mut self, value: impl Into<#ty>) -> Self {
+                self.#ident = Some(value.into());
+                self
+            }
+        });
+        initializers.push(
+            if field.rust_type.starts_with("Option<") || field.rust_type.starts_with("Vec<") {
+                quote! { #ident: self.#ident.unwrap_or_default() }
+            } else {
+                quote! {
+                    #ident: self.#ident.ok_or_else(|| {
+                        #error_type::Conversion(format!("{}: is required", #name))
+                    })?
+                }
+            },
+        );
+    }
+
+    quote! {
+        #[doc = #builder_doc]
+        #[derive(Debug, Clone, Default)]
+        pub struct #builder_ident {
+            #(#builder_fields),*
+        }
+
+        impl #struct_ident {
+            /// Start building a validated value
+            pub fn builder() -> #builder_ident {
+                #builder_ident::default()
+            }
+        }
+
+        impl #builder_ident {
+            #(#setters)*
+
+            /// Build the domain type, failing on missing fields or validation errors
+            pub fn build(self) -> Result<#struct_ident, #error_type> {
+                let domain = #struct_ident {
+                    #(#initializers),*
+                };
+                domain.validate()?;
+                Ok(domain)
+            }
+        }
+    }
+}
+
+/// Generate `impl IntoActiveModel` populating the target entity's matching columns
+///
+/// Columns carried by the domain type are `Set`, which requires the domain field to
+/// have the column's type (or be converted through an input `type`). Of the rest,
+/// primary keys are explicitly `NotSet`, required timestamps without a default are
+/// set to now, and everything else is left `NotSet` through `Default`.
+fn generate_into_active_model(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    entity: &str,
+    struct_ident: &proc_macro2::Ident,
+    domain_fields: &[DomainField],
+) -> Result<TokenStream, GeneratorError> {
+    let target = ctx
+        .resolver
+        .resolve_message(entity, file.package.as_deref())
+        .filter(|m| crate::codegen::entity::entity_table_name(m.file, m.message).is_some())
+        .ok_or_else(|| {
+            GeneratorError::InvalidConfig(format!(
+                "input_message entity \"{}\" is not an entity message",
+                entity
+            ))
+        })?;
+
+    let columns = crate::codegen::entity::entity_columns(ctx, target.file, target.message)?;
+    let entity_name = target.message.name.as_deref().unwrap_or("");
+    let entity_module: syn::Path =
+        syn::parse_str(&related_module_path(ctx, file.package.as_deref(), entity)).map_err(
+            |e| GeneratorError::CodeGenError(format!("Invalid module path for {}: {}", entity, e)),
+        )?;
+
+    let mut assignments = Vec::new();
+    for column in &columns {
+        let ident = format_ident!("{}", column.name);
+        let assignment = match domain_fields.iter().find(|f| f.name == column.name) {
+            // Mapped from the domain type
+            Some(f) if f.custom_type => {
+                quote! { #ident: sea_orm::ActiveValue::Set(self.#ident.into()) }
+            }
+            // Widened columns take the proto-typed value losslessly
+            Some(_) if column.widened && column.rust_type.starts_with("Option<") => {
+                quote! { #ident: sea_orm::ActiveValue::Set(self.#ident.map(Into::into)) }
+            }
+            Some(_) if column.widened => {
+                quote! { #ident: sea_orm::ActiveValue::Set(self.#ident.into()) }
+            }
+            Some(f) if f.rust_type == column.rust_type => {
+                quote! { #ident: sea_orm::ActiveValue::Set(self.#ident) }
+            }
+            // Nullable columns take a required domain value as `Some`
+            Some(f) if column.rust_type == format!("Option<{}>", f.rust_type) => {
+                quote! { #ident: sea_orm::ActiveValue::Set(Some(self.#ident)) }
+            }
+            Some(f) => {
+                return Err(GeneratorError::InvalidConfig(format!(
+                    "{}.{} is {} but column {}.{} is {}; give the field an input `type` \
+                     that converts into the column or `skip` it",
+                    struct_ident, f.name, f.rust_type, entity_name, column.name, column.rust_type
+                )));
+            }
+            // Left to the database so inserts get a generated key
+            None if column.primary_key => quote! { #ident: sea_orm::ActiveValue::NotSet },
+            // Required timestamps without a database default start at the current time
+            None if !column.has_default => match required_timestamp_now(&column.rust_type) {
+                Some(now) => quote! { #ident: sea_orm::ActiveValue::Set(#now) },
+                None => continue,
+            },
+            None => continue,
+        };
+        assignments.push(assignment);
+    }
+
+    Ok(quote! {
+        impl sea_orm::IntoActiveModel<#entity_module::ActiveModel> for #struct_ident {
+            fn into_active_model(self) -> #entity_module::ActiveModel {
+                #entity_module::ActiveModel {
+                    #(#assignments,)*
+                    ..Default::default()
+                }
+            }
+        }
+    })
+}
+
+/// The current time in a column type, if it is a non-nullable timestamp
+fn required_timestamp_now(rust_type: &str) -> Option<TokenStream> {
+    if rust_type.starts_with("Option<") {
+        return None;
+    }
+    crate::codegen::entity::current_time_value(rust_type)
+}
+
+/// The request fields a domain type is built from
+///
+/// With a `fields` whitelist only the named proto fields are kept, in declaration
+/// order; naming a field the message doesn't have is an error. Without one every
+/// field is kept (fields marked `skip` are dropped later).
+fn select_fields<'a>(
+    message_name: &str,
+    message: &'a DescriptorProto,
+    whitelist: &[String],
+) -> Result<Vec<&'a FieldDescriptorProto>, GeneratorError> {
+    if whitelist.is_empty() {
+        return Ok(message.field.iter().collect());
+    }
+
+    if let Some(unknown) = whitelist.iter().find(|name| {
+        !message
+            .field
+            .iter()
+            .any(|f| f.name.as_deref() == Some(name.as_str()))
+    }) {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "input_message fields on {} lists \"{}\", which is not a field of the message",
+            message_name, unknown
+        )));
+    }
+
+    Ok(message
+        .field
+        .iter()
+        .filter(|f| {
+            whitelist
+                .iter()
+                .any(|name| f.name.as_deref() == Some(name.as_str()))
+        })
+        .collect())
+}
+
 /// Generate domain struct fields with garde validation attributes
 fn generate_domain_fields(
     file_name: &str,
     message_name: &str,
-    fields: &[FieldDescriptorProto],
-) -> Result<(Vec<TokenStream>, Vec<TokenStream>), GeneratorError> {
-    let mut field_tokens = Vec::new();
-    let mut try_from_conversions = Vec::new();
+    fields: &[&FieldDescriptorProto],
+    error_type: &syn::Path,
+) -> Result<Vec<DomainField>, GeneratorError> {
+    let mut domain_fields = Vec::new();
 
     for field in fields {
         let field_number = field.number.unwrap_or(0);
@@ -200,22 +494,165 @@ fn generate_domain_fields(
                 pub #field_ident: #type_tokens
             }
         };
-        field_tokens.push(field_token);
 
         // Generate TryFrom conversion
         let conversion = if needs_conversion {
             quote! {
-                #field_ident: proto.#proto_field_ident.parse().map_err(|e| DomainError::Conversion(format!("{}: {}", stringify!(#field_ident), e)))?
+                #field_ident: proto.#proto_field_ident.parse().map_err(|e| #error_type::Conversion(format!("{}: {}", stringify!(#field_ident), e)))?
             }
         } else {
             quote! {
                 #field_ident: proto.#proto_field_ident
             }
         };
-        try_from_conversions.push(conversion);
+
+        let arbitrary = if needs_conversion {
+            quote! { u.arbitrary()? }
+        } else {
+            arbitrary_value(&input_opts, &rust_type)
+        };
+
+        domain_fields.push(DomainField {
+            name: domain_field_name,
+            custom_type: needs_conversion,
+            rust_type: rust_type.clone(),
+            tokens: field_token,
+            conversion,
+            arbitrary,
+        });
+    }
+
+    Ok(domain_fields)
+}
+
+/// Expression for an arbitrary value of a field that passes its validation rules
+///
+/// Strings under `email`, `url`, `length`, `required`, `ascii` or `alphanumeric` are
+/// built from alphanumeric characters within the length bounds, and integers under
+/// `range` are drawn from the range. `pattern` and `custom` rules can't be honored,
+/// so such fields (like unconstrained ones) take any value.
+fn arbitrary_value(input_opts: &Option<seaorm::InputOptions>, rust_type: &str) -> TokenStream {
+    let Some(validate) = input_opts.as_ref().and_then(|o| o.validate.as_ref()) else {
+        return quote! { u.arbitrary()? };
+    };
+    if validate.skip || !validate.pattern.is_empty() || !validate.custom.is_empty() {
+        return quote! { u.arbitrary()? };
+    }
+
+    if rust_type == "String" {
+        let length = validate.length.as_ref();
+        let min = length
+            .and_then(|l| l.min)
+            .unwrap_or(0)
+            .max(u32::from(validate.required)) as usize;
+        let max = length.and_then(|l| l.max).map(|max| max as usize);
+
+        // The fixed parts of emails and URLs count towards the length bounds
+        let (template, fixed) = if validate.email {
+            (Some("{}@example.com"), "@example.com".len())
+        } else if validate.url {
+            (Some("https://{}.example.com"), "https://.example.com".len())
+        } else {
+            (None, 0)
+        };
+
+        if template.is_none()
+            && length.is_none()
+            && !validate.required
+            && !validate.ascii
+            && !validate.alphanumeric
+        {
+            return quote! { u.arbitrary()? };
+        }
+
+        let min = min
+            .saturating_sub(fixed)
+            .max(usize::from(template.is_some()));
+        let max = max.map_or(min + 16, |max| max.saturating_sub(fixed).max(min));
+        let value = quote! { arbitrary_alphanumeric(u, #min, #max)? };
+        return match template {
+            Some(template) => quote! { format!(#template, #value) },
+            None => value,
+        };
     }
 
-    Ok((field_tokens, try_from_conversions))
+    let Some(range) = &validate.range else {
+        return quote! { u.arbitrary()? };
+    };
+    let (min, max) = inclusive_bounds(range);
+    let ty = format_ident!("{}", rust_type);
+    let bound = |value: Option<i64>, default: TokenStream| match (value, rust_type) {
+        (Some(v), "i32") => {
+            let v = v as i32;
+            quote! { #v }
+        }
+        (Some(v), "u32") => {
+            let v = v as u32;
+            quote! { #v }
+        }
+        (Some(v), "u64") => {
+            let v = v as u64;
+            quote! { #v }
+        }
+        (Some(v), _) => quote! { #v },
+        (None, _) => default,
+    };
+    match rust_type {
+        "i32" | "i64" | "u32" | "u64" => {
+            let min = bound(min, quote! { #ty::MIN });
+            let max = bound(max, quote! { #ty::MAX });
+            quote! { u.int_in_range(#min..=#max)? }
+        }
+        _ => quote! { u.arbitrary()? },
+    }
+}
+
+/// Generate the `Arbitrary` implementation for a domain type, behind an `arbitrary` feature
+///
+/// Values are drawn to pass validation where the rules allow (see `arbitrary_value`).
+fn generate_arbitrary_impl(
+    struct_ident: &proc_macro2::Ident,
+    domain_fields: &[DomainField],
+) -> TokenStream {
+    let initializers = domain_fields.iter().map(|f| {
+        let ident = format_ident!("{}", f.name);
+        let value = &f.arbitrary;
+        quote! { #ident: #value }
+    });
+
+    let uses_helper = domain_fields
+        .iter()
+        .any(|f| f.arbitrary.to_string().contains("arbitrary_alphanumeric"));
+    let helper = if uses_helper {
+        quote! {
+            /// Alphanumeric string with a length in `min..=max`
+            #[cfg(feature = "arbitrary")]
+            fn arbitrary_alphanumeric(
+                u: &mut arbitrary::Unstructured<'_>,
+                min: usize,
+                max: usize,
+            ) -> arbitrary::Result<String> {
+                const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+                let len = u.int_in_range(min..=max)?;
+                (0..len).map(|_| u.choose(CHARS).map(|&c| char::from(c))).collect()
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #[cfg(feature = "arbitrary")]
+        impl<'a> arbitrary::Arbitrary<'a> for #struct_ident {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                Ok(Self {
+                    #(#initializers),*
+                })
+            }
+        }
+
+        #helper
+    }
 }
 
 /// Determine the Rust type for a field
@@ -322,6 +759,13 @@ fn generate_garde_attributes(
 
     // Range validation - use correct type based on field type
     if let Some(range) = &validate.range {
+        let (min, max) = inclusive_bounds(range);
+        let range = seaorm::RangeRule {
+            min,
+            max,
+            ..Default::default()
+        };
+
         // Generate range with correct type suffix based on proto field type
         let range_attr = match field_type {
             Type::Int32 | Type::Sint32 | Type::Sfixed32 => {
@@ -357,6 +801,22 @@ fn generate_garde_attributes(
     attrs
 }
 
+/// Fold exclusive `gt`/`lt` bounds into garde's inclusive `min`/`max`
+///
+/// Range rules only apply to integer fields, so `gt: 0` is exactly `min: 1`. When both
+/// an inclusive and an exclusive bound are given on one side, the stricter one wins.
+fn inclusive_bounds(range: &seaorm::RangeRule) -> (Option<i64>, Option<i64>) {
+    let min = match (range.min, range.gt.map(|gt| gt.saturating_add(1))) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    };
+    let max = match (range.max, range.lt.map(|lt| lt.saturating_sub(1))) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+    (min, max)
+}
+
 fn generate_range_attr_i32(min: Option<i32>, max: Option<i32>) -> Option<TokenStream> {
     match (min, max) {
         (Some(min), Some(max)) => Some(quote!(#[garde(range(min = #min, max = #max))])),
@@ -394,7 +854,7 @@ fn generate_range_attr_u64(min: Option<u64>, max: Option<u64>) -> Option<TokenSt
 }
 
 /// Strip common request suffixes from message name
-fn strip_request_suffix(name: &str) -> String {
+pub fn strip_request_suffix(name: &str) -> String {
     for suffix in &["Request", "Params", "Input", "Req"] {
         if let Some(stripped) = name.strip_suffix(suffix) {
             if !stripped.is_empty() {
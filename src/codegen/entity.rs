@@ -2,123 +2,2483 @@
 //!
 //! This module generates the main entity struct with SeaORM 2.0 dense format.
 
+use crate::codegen::column::{
+    column_type_attribute, custom_type, default_column_type, deprecation_note, duration_override,
+    enum_storage, generate_attributes, generated_column_sql, is_current_timestamp_expr,
+    is_custom_column_type, repeated_storage, shorten_identifier, templated_column_name,
+    temporal_override, text_cast_attribute, to_column_name, validate_generated,
+    validate_insert_default, validate_primary_key, widening, RepeatedStorage,
+};
+use crate::codegen::enum_gen::{
+    db_type_of, generate_inline_enum, int_value, resolve_enum_options, rust_enum_name,
+    stored_variants, string_value, variant_name, DbType, StoredVariants,
+};
 use crate::codegen::oneof::{
-    extract_oneofs, generate_flatten_fields, generate_json_fields, generate_tagged_fields,
-    is_oneof_field, OneofStrategy,
+    extract_oneofs, generate_flatten_fields, generate_json_enum, generate_json_fields,
+    generate_tagged_enum, generate_tagged_fields, is_oneof_field, oneof_enum_name, variant_types,
+    Discriminator, OneofInfo, OneofStrategy,
 };
-use crate::codegen::relation::generate_relation_fields;
+use crate::codegen::relation::{
+    generate_manual_relations, generate_related_entity, generate_relation_fields,
+    generate_relation_helpers, with_auto_reverse, RelationNaming,
+};
+use crate::codegen::{arbitrary_derive, specta_derive, Context};
+use crate::config::{ColumnSource, MessageHandling};
+use crate::options::seaorm::{RelationDef, RelationType};
 use crate::options::{
     get_cached_field_options, get_cached_message_options, parse_field_options,
     parse_message_options, seaorm,
 };
-use crate::types::map_proto_type;
+use crate::types::{is_well_known_message, map_map_entry, map_proto_type};
 use crate::GeneratorError;
-use heck::ToSnakeCase;
+use heck::{ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::TokenStream;
 use prost_types::compiler::code_generator_response::File;
-use prost_types::field_descriptor_proto::Label;
-use prost_types::{DescriptorProto, FieldDescriptorProto, FileDescriptorProto};
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorProto,
+};
 use quote::{format_ident, quote};
+use std::collections::HashMap;
+
+/// Generate a SeaORM entity file from a protobuf message
+pub fn generate(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    message: &DescriptorProto,
+) -> Result<Option<File>, GeneratorError> {
+    // Check if this message has SeaORM options
+    let message_options = match resolve_message_options(file, message) {
+        Some(opts) => opts,
+        None => return Ok(None), // No seaorm options, skip this message
+    };
+
+    // Skip if explicitly marked
+    if message_options.skip {
+        return Ok(None);
+    }
+
+    // Get message name
+    let message_name = message
+        .name
+        .as_ref()
+        .ok_or_else(|| GeneratorError::CodeGenError("Message has no name".to_string()))?;
+
+    // Determine table name, applying the global prefix to explicit and default names alike
+    let full_table_name = format!(
+        "{}{}",
+        ctx.config.table_prefix.as_deref().unwrap_or(""),
+        resolve_table_name(message_name, &message_options)
+    );
+    let table_name = shorten_identifier(&full_table_name, ctx.config.max_identifier_length);
+
+    // Generate the output filename
+    let module_name = message_name.to_snake_case();
+    let output_filename = format!(
+        "{}/{}.rs",
+        crate::generator::package_dir(&ctx.config, file.package.as_deref().unwrap_or("")),
+        module_name
+    );
+
+    // Generate field definitions (including any base columns)
+    let mut fields = collect_fields(ctx, file, message, &message_options)?;
+    apply_auto_timestamps(&mut fields, &message_options, message_name)?;
+
+    // Projections are plain query result structs without any entity machinery
+    if message_options.projection {
+        return Ok(Some(generate_projection(
+            message_name,
+            output_filename,
+            &fields,
+        )));
+    }
+
+    // Embed targets are stored as JSON inside other entities' columns
+    if message_options.embeddable {
+        return Ok(Some(generate_embeddable(
+            message_name,
+            message,
+            output_filename,
+            &fields,
+        )));
+    }
+
+    // Add the optimistic locking column unless the message already declares it
+    let version_column = to_column_name(&message_options.version_column, &ctx.config.acronyms);
+    if !version_column.is_empty() && !fields.iter().any(|f| f.name == version_column) {
+        let template = ctx.config.column_name_template.as_deref();
+        let attributes = std::iter::once("default_value = \"0\"".to_string())
+            .chain(
+                templated_column_name(template, &version_column)
+                    .map(|name| format!("column_name = {:?}", name)),
+            )
+            .collect();
+        fields.push(GeneratedField {
+            name: version_column.clone(),
+            rust_type: "i32".to_string(),
+            attributes,
+            primary_key: false,
+            blob_element: None,
+            widened_from: None,
+            proto_field: None,
+            inline_enum: None,
+            enum_variants: None,
+            newtype_adapters: None,
+            deprecated: None,
+            update_default: None,
+            serde_skip: false,
+            json_map: None,
+            auto_time: None,
+            generated: None,
+            filter_ops: Vec::new(),
+        });
+    }
+
+    // Add the soft-delete timestamp column unless the message already declares it
+    let soft_delete_column = to_column_name(&message_options.soft_delete, &ctx.config.acronyms);
+    if !soft_delete_column.is_empty() {
+        match fields.iter().find(|f| f.name == soft_delete_column) {
+            Some(f)
+                if !f.rust_type.starts_with("Option<")
+                    || current_time_value(&f.rust_type).is_none() =>
+            {
+                return Err(GeneratorError::InvalidConfig(format!(
+                    "soft_delete column {}.{} must be a nullable timestamp, not {}",
+                    message_name, f.name, f.rust_type
+                )));
+            }
+            Some(_) => {}
+            None => {
+                let template = ctx.config.column_name_template.as_deref();
+                let attributes =
+                    std::iter::once("column_type = \"TimestampWithTimeZone\"".to_string())
+                        .chain(
+                            templated_column_name(template, &soft_delete_column)
+                                .map(|name| format!("column_name = {:?}", name)),
+                        )
+                        .collect();
+                fields.push(GeneratedField {
+                    name: soft_delete_column.clone(),
+                    rust_type: "Option<DateTimeUtc>".to_string(),
+                    attributes,
+                    primary_key: false,
+                    blob_element: None,
+                    widened_from: None,
+                    proto_field: None,
+                    inline_enum: None,
+                    enum_variants: None,
+                    newtype_adapters: None,
+                    deprecated: None,
+                    update_default: None,
+                    serde_skip: false,
+                    json_map: None,
+                    auto_time: None,
+                    generated: None,
+                    filter_ops: Vec::new(),
+                });
+            }
+        }
+    }
+
+    let mut shortened = shorten_column_names(&mut fields, ctx.config.max_identifier_length);
+    if table_name != full_table_name {
+        shortened.insert(0, (full_table_name, table_name.clone()));
+    }
+
+    // Generate oneof fields, plus typed enums for oneofs stored as JSON or tagged columns
+    let (oneof_fields, oneof_enums) = generate_oneof_fields(ctx, file, message_name, message)?;
+    let has_json_oneof = extract_oneofs(message)
+        .iter()
+        .any(|o| o.strategy == OneofStrategy::Json);
+    let serde_import = if !has_json_oneof {
+        quote! {}
+    } else {
+        quote! { use serde::{Deserialize, Serialize}; }
+    };
+
+    // Build the entity struct
+    let struct_name = format_ident!("Model");
+    let table_name_lit = &table_name;
+
+    let field_tokens: Vec<_> = fields
+        .iter()
+        .map(|f| {
+            let name = format_ident!("{}", f.name);
+            let ty: syn::Type = syn::parse_str(&f.rust_type).unwrap_or_else(|_| {
+                // Fallback to parsing as a simple path
+                syn::parse_str::<syn::Type>(&f.rust_type.to_string())
+                    .unwrap_or_else(|_| syn::parse_quote!(String))
+            });
+
+            let deprecated = match &f.deprecated {
+                Some(Some(note)) => quote! { #[deprecated(note = #note)] },
+                Some(None) => quote! { #[deprecated] },
+                None => quote! {},
+            };
+            let serde_skip = if f.serde_skip && ctx.config.derive_serde {
+                quote! { #[serde(skip_serializing)] }
+            } else {
+                quote! {}
+            };
+
+            let attrs = &f.attributes;
+            if attrs.is_empty() {
+                Ok(quote! {
+                    #deprecated
+                    #serde_skip
+                    pub #name: #ty
+                })
+            } else {
+                let attr_tokens = attrs
+                    .iter()
+                    .map(|a| {
+                        let attr_content: proc_macro2::TokenStream = a.parse().map_err(|e| {
+                            GeneratorError::CodeGenError(format!(
+                                "{}.{}: invalid column attribute `{}`: {}",
+                                message_name, f.name, a, e
+                            ))
+                        })?;
+                        Ok(quote! { #[sea_orm(#attr_content)] })
+                    })
+                    .collect::<Result<Vec<_>, GeneratorError>>()?;
+                Ok(quote! {
+                    #deprecated
+                    #serde_skip
+                    #(#attr_tokens)*
+                    pub #name: #ty
+                })
+            }
+        })
+        .collect::<Result<_, GeneratorError>>()?;
+
+    // Uses generate_relation_fields to properly handle self-referential relation pairs,
+    // including reverse sides synthesized for `auto_reverse`
+    let mut declared_relations = message_options.relations.clone();
+    declared_relations.extend(default_message_relations(
+        ctx,
+        file,
+        message,
+        &message_options.relations,
+    )?);
+    let relations = with_auto_reverse(&declared_relations, message_name);
+    let naming = RelationNaming {
+        prefix: message_options.relation_enum_prefix.clone(),
+        columns: fields.iter().map(|f| f.name.clone()).collect(),
+    };
+    let relation_fields: Vec<TokenStream> = generate_relation_fields(
+        ctx,
+        file.package.as_deref(),
+        &relations,
+        message_name,
+        &naming,
+    )?;
+
+    // Associated functions emitted in `impl Entity`
+    let mut entity_items: Vec<TokenStream> = Vec::new();
+
+    if message_options.relation_helpers {
+        let primary_keys: Vec<&GeneratedField> = fields.iter().filter(|f| f.primary_key).collect();
+        match primary_keys.as_slice() {
+            [pk] => entity_items.extend(generate_relation_helpers(
+                ctx,
+                file.package.as_deref(),
+                &relations,
+                message_name,
+                (&pk.name, &pk.rust_type),
+            )),
+            _ => {
+                return Err(GeneratorError::InvalidConfig(format!(
+                    "relation_helpers on {} requires exactly one primary key field",
+                    message_name
+                )))
+            }
+        }
+    }
+
+    if !version_column.is_empty() {
+        entity_items.push(generate_update_with_lock(&version_column));
+    }
+
+    if let Some(column) = fields.iter().find(|f| f.name == soft_delete_column) {
+        match fields
+            .iter()
+            .filter(|f| f.primary_key)
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            [pk] => entity_items.push(generate_soft_delete(column, pk)),
+            _ => {
+                return Err(GeneratorError::InvalidConfig(format!(
+                    "soft_delete on {} requires exactly one primary key field",
+                    message_name
+                )))
+            }
+        }
+    }
+
+    if message_options.finders {
+        entity_items.push(generate_list_helper(&fields));
+        if let [pk] = fields
+            .iter()
+            .filter(|f| f.primary_key)
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            entity_items.push(generate_find_by_ids(pk));
+        }
+    }
+
+    if fields.iter().any(|f| f.update_default.is_some()) {
+        entity_items.push(generate_update_default_exprs(&fields));
+    }
+
+    if message_options.table_statement {
+        entity_items.push(generate_table_statement(
+            &table_name,
+            &fields,
+            &oneof_columns(message),
+            &message_options.indexes,
+        )?);
+    }
+
+    // Additional module-level items (helper structs and their impls)
+    let mut extra_items: Vec<TokenStream> = Vec::new();
+
+    if message_options.related_entity {
+        extra_items.push(generate_related_entity(
+            ctx,
+            file.package.as_deref(),
+            &relations,
+            message_name,
+            &naming,
+        ));
+    }
+
+    if message_options.changeset {
+        extra_items.push(generate_changed_columns(&fields, &oneof_columns(message)));
+    }
+
+    if message_options.empty_active_model {
+        extra_items.push(generate_empty_active_model(
+            &fields,
+            &oneof_columns(message),
+        ));
+    }
+
+    if message_options.field_mask {
+        extra_items.push(generate_field_mask_map(&fields));
+    }
+
+    if message_options.update_struct {
+        extra_items.push(generate_update_struct(
+            message_name,
+            &fields,
+            &[&version_column, &soft_delete_column],
+        ));
+    }
+
+    if message_options.filter_struct {
+        extra_items.push(generate_filter_struct(message_name, &fields)?);
+    }
+
+    if message_options.has_id_trait {
+        extra_items.push(generate_has_id_impl(message_name, &fields)?);
+    }
+
+    if let Some(accessors) = generate_json_map_accessors(&fields) {
+        extra_items.push(accessors);
+    }
+
+    if message_options.proto_codec {
+        extra_items.push(generate_proto_codec(message_name));
+    }
+
+    if message_options.identity_hash {
+        extra_items.push(generate_identity_hash(message_name, &fields)?);
+    }
+
+    // Combine regular fields, oneof fields, and relation fields (always last)
+    let relation_count = relation_fields.len();
+    let all_field_tokens: Vec<TokenStream> = field_tokens
+        .into_iter()
+        .chain(oneof_fields)
+        .chain(relation_fields)
+        .collect();
+
+    check_reserved_names(message_name, message, &all_field_tokens)?;
+
+    if message_options.derive_default {
+        extra_items.push(generate_default_impl(
+            message_name,
+            &fields,
+            &all_field_tokens,
+        )?);
+    }
+
+    if fields.iter().any(|f| f.blob_element.is_some()) {
+        extra_items.push(generate_blob_codecs(&fields));
+    }
+
+    if fields.iter().any(|f| f.widened_from.is_some()) {
+        extra_items.push(generate_narrowing_conversions(&fields));
+    }
+
+    let entity_impl = if entity_items.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            impl Entity {
+                #(#entity_items)*
+            }
+        }
+    };
+
+    // Record reserved (removed) field names so they aren't reintroduced by hand
+    let reserved_doc = if message.reserved_name.is_empty() {
+        quote! {}
+    } else {
+        let note = format!(
+            " Reserved field names: {}",
+            message.reserved_name.join(", ")
+        );
+        quote! {
+            #![doc = ""]
+            #![doc = #note]
+        }
+    };
+
+    // Record names shortened for max_identifier_length, since the schema won't match the proto
+    let shortened_doc = if shortened.is_empty() {
+        quote! {}
+    } else {
+        let notes = shortened
+            .iter()
+            .map(|(full, short)| format!(" - `{}` is named `{}`", full, short));
+        quote! {
+            #![doc = ""]
+            #![doc = " Identifiers shortened to fit max_identifier_length:"]
+            #(#![doc = #notes])*
+        }
+    };
+
+    let inline_enums = inline_enums(&fields);
+    let newtype_adapters = newtype_adapters(&fields);
+    let test_module = if message_options.generate_tests && !ctx.config.no_tests {
+        generate_test_module(&table_name, &fields)
+    } else {
+        quote! {}
+    };
+    let active_model_behavior = generate_active_model_behavior(&fields);
+
+    let specta = specta_derive(ctx.config.derive_specta);
+    let arbitrary = arbitrary_derive(ctx.config.derive_arbitrary);
+    let serde = if ctx.config.derive_serde {
+        quote! { #[derive(serde::Serialize, serde::Deserialize)] }
+    } else {
+        quote! {}
+    };
+    let model_attrs = quote! {
+        #serde
+        #specta
+        #arbitrary
+    };
+    // With identity_hash, equality is implemented by hand over the primary key
+    let model_comparisons = if message_options.identity_hash {
+        quote! {}
+    } else {
+        quote! { PartialEq, Eq, }
+    };
+
+    let entity_definition = if ctx.config.manual_entity {
+        let relation_items = generate_manual_relations(
+            ctx,
+            file.package.as_deref(),
+            &relations,
+            message_name,
+            &naming,
+        )?;
+        let column_fields = &all_field_tokens[..all_field_tokens.len() - relation_count];
+        generate_manual_entity(
+            message_name,
+            &table_name,
+            column_fields,
+            relation_items,
+            &model_attrs,
+            &model_comparisons,
+        )?
+    } else {
+        quote! {
+            #[sea_orm::model]
+            #[derive(Clone, Debug, #model_comparisons DeriveEntityModel)]
+            #model_attrs
+            #[sea_orm(table_name = #table_name_lit)]
+            pub struct #struct_name {
+                #(#all_field_tokens),*
+            }
+        }
+    };
+
+    let code = quote! {
+        //! SeaORM entity for the table
+        //!
+        //! Generated by protoc-gen-seaorm from protobuf definition.
+        //! @generated
+        #reserved_doc
+        #shortened_doc
+
+        #[allow(unused_imports)]
+        use super::prelude::*;
+        use sea_orm::entity::prelude::*;
+        #serde_import
+
+        #entity_definition
+
+        #active_model_behavior
+
+        #entity_impl
+
+        #(#extra_items)*
+
+        #(#oneof_enums)*
+
+        #(#inline_enums)*
+
+        #(#newtype_adapters)*
+
+        #test_module
+    };
+
+    // Format the generated code
+    let content = code.to_string();
+
+    // Try to format with prettyplease if we can parse it
+    let formatted = match syn::parse_file(&content) {
+        Ok(parsed) => prettyplease::unparse(&parsed),
+        Err(_) => content, // If parsing fails, use unformatted
+    };
+
+    Ok(Some(File {
+        name: Some(output_filename),
+        content: Some(formatted),
+        ..Default::default()
+    }))
+}
+
+/// Generate the expanded entity for `manual_entity`
+///
+/// Renders what `DeriveEntityModel` would expand `fields` into: the `Entity` unit
+/// struct with its table name, an attribute-free `Model`, and the `Column` and
+/// `PrimaryKey` enums with `ColumnTrait` and `PrimaryKeyTrait` impls built from each
+/// field's `#[sea_orm(...)]` attributes. Relation items come pre-rendered, and
+/// `model_attrs` is added to the `Model` as is, after the derived `comparisons`.
+fn generate_manual_entity(
+    message_name: &str,
+    table_name: &str,
+    fields: &[TokenStream],
+    relation_items: TokenStream,
+    model_attrs: &TokenStream,
+    comparisons: &TokenStream,
+) -> Result<TokenStream, GeneratorError> {
+    use syn::parse::Parser;
+
+    let mut model_fields = Vec::new();
+    let mut column_variants = Vec::new();
+    let mut column_defs = Vec::new();
+    let mut primary_keys: Vec<(syn::Ident, syn::Type, bool)> = Vec::new();
+
+    for tokens in fields {
+        let mut field = syn::Field::parse_named
+            .parse2(tokens.clone())
+            .map_err(|e| GeneratorError::CodeGenError(format!("{}: {}", message_name, e)))?;
+        let ident = field.ident.clone().ok_or_else(|| {
+            GeneratorError::CodeGenError(format!("{} has an unnamed field", message_name))
+        })?;
+        let column = ColumnAttrs::take_from(&mut field.attrs).map_err(|e| {
+            GeneratorError::CodeGenError(format!("{}.{}: {}", message_name, ident, e))
+        })?;
+        let variant = format_ident!("{}", ident.to_string().to_upper_camel_case());
+
+        // Nullable columns are `Option<T>`; the column type comes from `T`
+        let (inner_type, optional) = match option_inner(&field.ty) {
+            Some(inner) => (inner.clone(), true),
+            None => (field.ty.clone(), false),
+        };
+
+        let mut def = match &column.column_type {
+            Some(column_type) if is_custom_column_type(column_type) => {
+                let name = column_type
+                    .split_once('(')
+                    .and_then(|(_, rest)| rest.strip_suffix(')'))
+                    .and_then(|arg| syn::parse_str::<syn::LitStr>(arg).ok())
+                    .map(|lit| lit.value())
+                    .unwrap_or_default();
+                quote! {
+                    ColumnType::Custom(sea_orm::sea_query::IntoIden::into_iden(sea_orm::sea_query::Alias::new(#name))).def()
+                }
+            }
+            Some(column_type) => {
+                let column_type: TokenStream = column_type.parse().map_err(|_| {
+                    GeneratorError::CodeGenError(format!(
+                        "{}.{}: invalid column_type {}",
+                        message_name, ident, column_type
+                    ))
+                })?;
+                quote! { ColumnType::#column_type.def() }
+            }
+            None => quote! {
+                <#inner_type as sea_orm::sea_query::ValueType>::column_type().def()
+            },
+        };
+        if optional || column.nullable {
+            def = quote! { #def.null() };
+        }
+        if column.unique {
+            def = quote! { #def.unique() };
+        } else if column.indexed {
+            def = quote! { #def.indexed() };
+        }
+        if let Some(default_value) = &column.default_value {
+            def = quote! { #def.default_value(#default_value) };
+        }
+        // Like the derive, `default_expr` holds a Rust expression such as `Expr::current_timestamp()`
+        if let Some(default_expr) = &column.default_expr {
+            let expr: syn::Expr = syn::parse_str(default_expr).map_err(|e| {
+                GeneratorError::CodeGenError(format!(
+                    "{}.{}: invalid default_expr {}: {}",
+                    message_name, ident, default_expr, e
+                ))
+            })?;
+            def = quote! { #def.default(#expr) };
+        }
+
+        column_variants.push(match &column.column_name {
+            Some(name) => quote! {
+                #[sea_orm(column_name = #name)]
+                #variant
+            },
+            None => quote! { #variant },
+        });
+        column_defs.push(quote! { Self::#variant => #def, });
+        if column.primary_key {
+            let integer = matches!(
+                quote!(#inner_type).to_string().as_str(),
+                "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64"
+            );
+            primary_keys.push((variant, field.ty.clone(), column.auto_increment && integer));
+        }
+        model_fields.push(field);
+    }
+
+    let key_variants = primary_keys.iter().map(|(variant, _, _)| variant);
+    let (key_type, auto_increment) = match primary_keys.as_slice() {
+        [] => {
+            return Err(GeneratorError::InvalidConfig(format!(
+                "manual_entity requires a primary key on {}",
+                message_name
+            )))
+        }
+        [(_, ty, auto_increment)] => (quote! { #ty }, *auto_increment),
+        keys => {
+            let types = keys.iter().map(|(_, ty, _)| ty);
+            (quote! { (#(#types),*) }, false)
+        }
+    };
+
+    Ok(quote! {
+        #[derive(Copy, Clone, Default, Debug, DeriveEntity)]
+        pub struct Entity;
+
+        impl EntityName for Entity {
+            fn table_name(&self) -> &str {
+                #table_name
+            }
+        }
+
+        #[derive(Clone, Debug, #comparisons DeriveModel, DeriveActiveModel)]
+        #model_attrs
+        pub struct Model {
+            #(#model_fields),*
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
+        pub enum Column {
+            #(#column_variants,)*
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
+        pub enum PrimaryKey {
+            #(#key_variants,)*
+        }
+
+        impl PrimaryKeyTrait for PrimaryKey {
+            type ValueType = #key_type;
+
+            fn auto_increment() -> bool {
+                #auto_increment
+            }
+        }
+
+        impl ColumnTrait for Column {
+            type EntityName = Entity;
+
+            fn def(&self) -> ColumnDef {
+                match self {
+                    #(#column_defs)*
+                }
+            }
+        }
+
+        #relation_items
+    })
+}
+
+/// Column settings read back from a field's `#[sea_orm(...)]` attributes
+struct ColumnAttrs {
+    primary_key: bool,
+    auto_increment: bool,
+    unique: bool,
+    indexed: bool,
+    nullable: bool,
+    column_name: Option<String>,
+    column_type: Option<String>,
+    default_value: Option<syn::Expr>,
+    default_expr: Option<String>,
+}
+
+impl ColumnAttrs {
+    /// Remove the `sea_orm` attributes from a field and collect their settings
+    fn take_from(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Self> {
+        let mut column = ColumnAttrs {
+            primary_key: false,
+            auto_increment: true,
+            unique: false,
+            indexed: false,
+            nullable: false,
+            column_name: None,
+            column_type: None,
+            default_value: None,
+            default_expr: None,
+        };
+        let string = |expr: &syn::Expr| match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(s),
+                ..
+            }) => Ok(s.value()),
+            other => Err(syn::Error::new_spanned(other, "expected a string literal")),
+        };
+
+        for attr in attrs.iter().filter(|a| a.path().is_ident("sea_orm")) {
+            let metas = attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+            )?;
+            for meta in metas {
+                let key = meta.path().get_ident().map(|i| i.to_string());
+                match (key.as_deref(), &meta) {
+                    (Some("primary_key"), syn::Meta::Path(_)) => column.primary_key = true,
+                    (Some("unique"), syn::Meta::Path(_)) => column.unique = true,
+                    (Some("indexed"), syn::Meta::Path(_)) => column.indexed = true,
+                    (Some("nullable"), syn::Meta::Path(_)) => column.nullable = true,
+                    (Some("auto_increment"), syn::Meta::NameValue(nv)) => {
+                        column.auto_increment = !matches!(
+                            &nv.value,
+                            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Bool(b), .. }) if !b.value
+                        );
+                    }
+                    (Some("column_name"), syn::Meta::NameValue(nv)) => {
+                        column.column_name = Some(string(&nv.value)?)
+                    }
+                    (Some("column_type"), syn::Meta::NameValue(nv)) => {
+                        column.column_type = Some(string(&nv.value)?)
+                    }
+                    (Some("default_value"), syn::Meta::NameValue(nv)) => {
+                        column.default_value = Some(nv.value.clone())
+                    }
+                    (Some("default_expr"), syn::Meta::NameValue(nv)) => {
+                        column.default_expr = Some(string(&nv.value)?)
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            meta,
+                            "attribute is not supported by manual_entity",
+                        ))
+                    }
+                }
+            }
+        }
+        attrs.retain(|a| !a.path().is_ident("sea_orm"));
+
+        Ok(column)
+    }
+}
+
+/// The `T` of an `Option<T>` type
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Generate `Entity::update_with_lock` for optimistic locking
+///
+/// The update is filtered on the version the caller loaded and bumps it by one, so a
+/// concurrent writer makes the update match no rows and SeaORM returns
+/// `DbErr::RecordNotUpdated`.
+fn generate_update_with_lock(version_column: &str) -> TokenStream {
+    let field = format_ident!("{}", version_column);
+    let column = format_ident!("{}", version_column.to_upper_camel_case());
+    let missing = format!(
+        "update_with_lock requires {} to be set on the active model",
+        version_column
+    );
+
+    quote! {
+        /// Update a model if its version hasn't changed since it was loaded
+        ///
+        /// Returns `DbErr::RecordNotUpdated` when another writer updated the row first.
+        pub async fn update_with_lock<C>(db: &C, mut model: ActiveModel) -> Result<Model, DbErr>
+        where
+            C: ConnectionTrait,
+        {
+            let Some(expected) = model.#field.try_as_ref().copied() else {
+                return Err(DbErr::Custom(#missing.to_string()));
+            };
+            model.#field = sea_orm::ActiveValue::Set(expected + 1);
+
+            Self::update(model)
+                .filter(Column::#column.eq(expected))
+                .exec(db)
+                .await
+        }
+    }
+}
+
+/// Generate `Entity::find_active` and `Entity::soft_delete` for a soft-delete column
+///
+/// Soft-deleted rows keep their data and only have the column set to the deletion time.
+fn generate_soft_delete(column: &GeneratedField, pk: &GeneratedField) -> TokenStream {
+    let column_type: syn::Type = syn::parse_str(&column.rust_type)
+        .unwrap_or_else(|_| syn::parse_quote!(Option<DateTimeUtc>));
+    let now = current_time_value(&column.rust_type);
+    let column = format_ident!("{}", column.name.to_upper_camel_case());
+    let pk_column = format_ident!("{}", pk.name.to_upper_camel_case());
+    let pk_type: syn::Type =
+        syn::parse_str(&pk.rust_type).unwrap_or_else(|_| syn::parse_quote!(i64));
+
+    quote! {
+        /// Select the models that haven't been soft-deleted
+        pub fn find_active() -> Select<Entity> {
+            Self::find().filter(Column::#column.is_null())
+        }
+
+        /// Soft-delete a model by setting its deletion timestamp instead of removing the row
+        ///
+        /// Returns `DbErr::RecordNotUpdated` when there is no active model with the id.
+        pub async fn soft_delete<C>(db: &C, id: #pk_type) -> Result<(), DbErr>
+        where
+            C: ConnectionTrait,
+        {
+            let deleted_at: #column_type = #now;
+            let result = Self::update_many()
+                .col_expr(Column::#column, sea_orm::sea_query::Expr::value(deleted_at))
+                .filter(Column::#pk_column.eq(id))
+                .filter(Column::#column.is_null())
+                .exec(db)
+                .await?;
+
+            if result.rows_affected == 0 {
+                return Err(DbErr::RecordNotUpdated);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Generate `Entity::list` for paginated listing with a total count
+///
+/// Rows are ordered by the primary key so pages are stable between calls.
+fn generate_list_helper(fields: &[GeneratedField]) -> TokenStream {
+    let order_by = fields.iter().filter(|f| f.primary_key).map(|f| {
+        let column = format_ident!("{}", f.name.to_upper_camel_case());
+        quote! { .order_by_asc(Column::#column) }
+    });
+
+    quote! {
+        /// Fetch one zero-based page of models along with the total number of rows
+        pub async fn list<C>(db: &C, page: u64, page_size: u64) -> Result<(Vec<Model>, u64), DbErr>
+        where
+            C: ConnectionTrait,
+        {
+            use sea_orm::{PaginatorTrait, QueryOrder};
+
+            let paginator = Self::find()
+                #(#order_by)*
+                .paginate(db, page_size);
+            let total = paginator.num_items().await?;
+            let items = paginator.fetch_page(page).await?;
+
+            Ok((items, total))
+        }
+    }
+}
+
+/// Generate `Entity::create_table_statement` and, for indexed columns and declared
+/// `indexes`, `Entity::create_index_statements`
+fn generate_table_statement(
+    table_name: &str,
+    fields: &[GeneratedField],
+    oneof_columns: &[String],
+    declared_indexes: &[String],
+) -> Result<TokenStream, GeneratorError> {
+    let create_table = table_create_expr(
+        &quote! { Self },
+        &quote! { Column },
+        fields,
+        oneof_columns,
+        &[],
+    );
+    let indexes = index_create_exprs(
+        table_name,
+        &quote! { Self },
+        &quote! { Column },
+        fields,
+        declared_indexes,
+    )?;
+
+    let index_statements = if indexes.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            /// Statements creating the secondary indexes of the table
+            pub fn create_index_statements() -> Vec<sea_orm::sea_query::IndexCreateStatement> {
+                use sea_orm::sea_query::Index;
+                vec![#(#indexes),*]
+            }
+        }
+    };
+
+    Ok(quote! {
+        /// Statement creating the table with all of its columns, for use with `db.execute`
+        pub fn create_table_statement() -> sea_orm::sea_query::TableCreateStatement {
+            #[allow(unused_imports)]
+            use sea_orm::sea_query::{ColumnDef, Index, Table};
+            #create_table
+        }
+
+        #index_statements
+    })
+}
+
+/// SeaQuery expression creating a table with all of its columns
+///
+/// `table` and `column` address the entity and its `Column` enum. Scalar columns map to
+/// the matching SeaQuery column builder; anything else (explicit `column_type`s, enums,
+/// embedded types and oneof columns) takes its type from the entity's own column
+/// definition. `extra` calls (such as foreign keys) are chained before `to_owned`.
+fn table_create_expr(
+    table: &TokenStream,
+    column: &TokenStream,
+    fields: &[GeneratedField],
+    oneof_columns: &[String],
+    extra: &[TokenStream],
+) -> TokenStream {
+    let primary_keys: Vec<&GeneratedField> = fields.iter().filter(|f| f.primary_key).collect();
+
+    let columns = fields.iter().map(|f| {
+        let variant = format_ident!("{}", f.name.to_upper_camel_case());
+        let has_column_type = f.attributes.iter().any(|a| a.starts_with("column_type"));
+        let nullable = f.rust_type.starts_with("Option<");
+        let base_type = f
+            .rust_type
+            .strip_prefix("Option<")
+            .and_then(|t| t.strip_suffix('>'))
+            .unwrap_or(&f.rust_type);
+
+        let mut def = match sea_query_column_method(base_type).filter(|_| !has_column_type) {
+            Some(method) => {
+                let method = format_ident!("{}", method);
+                quote! { ColumnDef::new(#column::#variant).#method() }
+            }
+            None => quote! {
+                ColumnDef::new_with_type(#column::#variant, #column::#variant.def().get_column_type().clone())
+            },
+        };
+        if !nullable {
+            def = quote! { #def.not_null() };
+        }
+        if f.primary_key && primary_keys.len() == 1 {
+            def = quote! { #def.primary_key() };
+            if f.attributes.iter().any(|a| a == "primary_key") {
+                def = quote! { #def.auto_increment() };
+            }
+        }
+        if f.attributes.iter().any(|a| a == "unique") {
+            def = quote! { #def.unique_key() };
+        }
+        if let Some(generated) = &f.generated {
+            def = quote! { #def.extra(#generated) };
+        }
+        quote! { .col(#def) }
+    });
+
+    let oneof_columns = oneof_columns.iter().map(|name| {
+        let variant = format_ident!("{}", name.to_upper_camel_case());
+        quote! {
+            .col(ColumnDef::new_with_type(#column::#variant, #column::#variant.def().get_column_type().clone()).null())
+        }
+    });
+
+    let composite_key = if primary_keys.len() > 1 {
+        let keys = primary_keys
+            .iter()
+            .map(|f| format_ident!("{}", f.name.to_upper_camel_case()));
+        quote! { .primary_key(Index::create()#(.col(#column::#keys))*) }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        Table::create()
+            .table(#table)
+            .if_not_exists()
+            #(#columns)*
+            #(#oneof_columns)*
+            #composite_key
+            #(#extra)*
+            .to_owned()
+    }
+}
+
+/// SeaQuery expressions creating the secondary indexes of a table
+///
+/// Covers `indexed` columns (`idx_<table>_<column>`) and the message's `indexes`
+/// entries: `name:col_a,col_b` for a named (possibly composite) index, or a bare
+/// `idx_<column>` naming an index on that single column.
+fn index_create_exprs(
+    table_name: &str,
+    table: &TokenStream,
+    column: &TokenStream,
+    fields: &[GeneratedField],
+    declared_indexes: &[String],
+) -> Result<Vec<TokenStream>, GeneratorError> {
+    let mut indexes: Vec<(String, Vec<String>)> = fields
+        .iter()
+        .filter(|f| f.attributes.iter().any(|a| a == "indexed"))
+        .map(|f| {
+            (
+                format!("idx_{}_{}", table_name, f.name),
+                vec![f.name.clone()],
+            )
+        })
+        .collect();
+
+    for entry in declared_indexes {
+        let (name, columns) = match entry.split_once(':') {
+            Some((name, columns)) => (
+                name.trim().to_string(),
+                columns.split(',').map(|c| c.trim().to_string()).collect(),
+            ),
+            None => {
+                let column = entry.strip_prefix("idx_").unwrap_or(entry).trim();
+                (entry.trim().to_string(), vec![column.to_string()])
+            }
+        };
+        if let Some(unknown) = columns
+            .iter()
+            .find(|c| !fields.iter().any(|f| &f.name == *c))
+        {
+            return Err(GeneratorError::InvalidConfig(format!(
+                "index \"{}\" on {} references unknown column \"{}\"",
+                name, table_name, unknown
+            )));
+        }
+        indexes.push((name, columns));
+    }
+
+    Ok(indexes
+        .iter()
+        .map(|(name, columns)| {
+            let variants = columns
+                .iter()
+                .map(|c| format_ident!("{}", c.to_upper_camel_case()));
+            quote! {
+                Index::create()
+                    .name(#name)
+                    .table(#table)
+                    #(.col(#column::#variants))*
+                    .to_owned()
+            }
+        })
+        .collect())
+}
+
+/// Generate `Entity::update_default_exprs` listing each column's `update_default`
+///
+/// An `ActiveModel` only carries values, so the SQL expressions are applied to bulk
+/// updates instead: `update_many().col_expr(column, expr)` for each pair.
+fn generate_update_default_exprs(fields: &[GeneratedField]) -> TokenStream {
+    let pairs = fields.iter().filter_map(|f| {
+        let expr = f.update_default.as_deref()?;
+        let column = format_ident!("{}", f.name.to_upper_camel_case());
+        Some(quote! { (Column::#column, sea_orm::sea_query::Expr::cust(#expr)) })
+    });
+
+    quote! {
+        /// Columns rewritten on every update, with their SQL expressions
+        pub fn update_default_exprs() -> Vec<(Column, sea_orm::sea_query::SimpleExpr)> {
+            vec![#(#pairs),*]
+        }
+    }
+}
+
+/// SeaORM value adapters letting a `newtype(inner)` type back a column
+///
+/// The newtype is stored as its inner type; the impls convert to and from `Value`,
+/// read it from query results and let the active model take it directly.
+fn generate_newtype_adapters(newtype: &str, inner: &str) -> Result<TokenStream, GeneratorError> {
+    let parse = |ty: &str| {
+        syn::parse_str::<syn::Type>(ty)
+            .map_err(|e| GeneratorError::CodeGenError(format!("{}: {}", ty, e)))
+    };
+    let newtype_ty = parse(newtype)?;
+    let inner_ty = parse(inner)?;
+
+    Ok(quote! {
+        impl From<#newtype_ty> for sea_orm::Value {
+            fn from(value: #newtype_ty) -> Self {
+                value.0.into()
+            }
+        }
+
+        impl sea_orm::TryGetable for #newtype_ty {
+            fn try_get_by<I: sea_orm::ColIdx>(
+                res: &sea_orm::QueryResult,
+                index: I,
+            ) -> Result<Self, sea_orm::TryGetError> {
+                <#inner_ty as sea_orm::TryGetable>::try_get_by(res, index).map(#newtype_ty)
+            }
+        }
+
+        impl sea_orm::sea_query::ValueType for #newtype_ty {
+            fn try_from(v: sea_orm::Value) -> Result<Self, sea_orm::sea_query::ValueTypeErr> {
+                <#inner_ty as sea_orm::sea_query::ValueType>::try_from(v).map(#newtype_ty)
+            }
+
+            fn type_name() -> String {
+                #newtype.to_string()
+            }
+
+            fn array_type() -> sea_orm::sea_query::ArrayType {
+                <#inner_ty as sea_orm::sea_query::ValueType>::array_type()
+            }
+
+            fn column_type() -> sea_orm::sea_query::ColumnType {
+                <#inner_ty as sea_orm::sea_query::ValueType>::column_type()
+            }
+        }
+
+        impl sea_orm::sea_query::Nullable for #newtype_ty {
+            fn null() -> sea_orm::Value {
+                <#inner_ty as sea_orm::sea_query::Nullable>::null()
+            }
+        }
+
+        impl sea_orm::IntoActiveValue<#newtype_ty> for #newtype_ty {
+            fn into_active_value(self) -> sea_orm::ActiveValue<#newtype_ty> {
+                sea_orm::ActiveValue::Set(self)
+            }
+        }
+    })
+}
+
+/// Generate the `generate_tests` module checking the entity against its definition
+fn generate_test_module(table_name: &str, fields: &[GeneratedField]) -> TokenStream {
+    let primary_keys = fields.iter().filter(|f| f.primary_key).count();
+
+    quote! {
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #[test]
+            fn table_name_matches_definition() {
+                assert_eq!(Entity.table_name(), #table_name);
+            }
+
+            #[test]
+            fn primary_key_matches_definition() {
+                assert_eq!(PrimaryKey::iter().count(), #primary_keys);
+            }
+        }
+    }
+}
+
+/// Mark the columns stamped by `auto_create_time` / `auto_update_time`
+///
+/// With the model's `timestamps`, `created_at` and `updated_at` are stamped without
+/// setting the column options. Stamped columns must be timestamps.
+fn apply_auto_timestamps(
+    fields: &mut [GeneratedField],
+    options: &seaorm::MessageOptions,
+    message_name: &str,
+) -> Result<(), GeneratorError> {
+    for field in fields.iter_mut() {
+        if options.timestamps && field.auto_time.is_none() && field.generated.is_none() {
+            field.auto_time = match field.name.as_str() {
+                "created_at" => Some(AutoTime::Create),
+                "updated_at" => Some(AutoTime::Update),
+                _ => None,
+            };
+        }
+        if field.auto_time.is_some() && current_time_value(&field.rust_type).is_none() {
+            return Err(GeneratorError::InvalidConfig(format!(
+                "{}.{} is stamped with the current time but is a {} column, not a timestamp",
+                message_name, field.name, field.rust_type
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Shorten column names longer than `max_identifier_length`
+///
+/// The shortened name becomes the column's `column_name`. Returns the full and
+/// shortened name of each renamed column.
+fn shorten_column_names(
+    fields: &mut [GeneratedField],
+    max_len: Option<usize>,
+) -> Vec<(String, String)> {
+    let mut shortened = Vec::new();
+    for field in fields.iter_mut() {
+        let position = field
+            .attributes
+            .iter()
+            .position(|a| a.starts_with("column_name"));
+        let column = position
+            .and_then(|i| field.attributes[i].split_once('='))
+            .and_then(|(_, name)| syn::parse_str::<syn::LitStr>(name.trim()).ok())
+            .map(|name| name.value())
+            .unwrap_or_else(|| field.name.clone());
+        let short = shorten_identifier(&column, max_len);
+        if short == column {
+            continue;
+        }
+
+        let attribute = format!("column_name = {:?}", short);
+        match position {
+            Some(i) => field.attributes[i] = attribute,
+            None => field.attributes.push(attribute),
+        }
+        shortened.push((column, short));
+    }
+    shortened
+}
+
+/// Value setting a timestamp column of `rust_type` to the current time
+pub fn current_time_value(rust_type: &str) -> Option<TokenStream> {
+    let inner = rust_type
+        .strip_prefix("Option<")
+        .and_then(|t| t.strip_suffix('>'));
+    let now = match inner.unwrap_or(rust_type) {
+        "DateTimeUtc" | "DateTimeWithTimeZone" => quote! { chrono::Utc::now().into() },
+        "DateTimeLocal" => quote! { chrono::Local::now() },
+        "DateTime" => quote! { chrono::Utc::now().naive_utc() },
+        _ => return None,
+    };
+    Some(if inner.is_some() {
+        quote! { Some(#now) }
+    } else {
+        now
+    })
+}
+
+/// Generate the `ActiveModelBehavior` impl
+///
+/// SeaORM has no attribute for auto-populated timestamps, so they're stamped in
+/// `before_save`: `auto_create_time` columns on insert, `auto_update_time` columns on
+/// insert and update. Timestamp columns whose `update_default` is the current time are
+/// stamped on every update too, so single-model saves keep them current. `generated`
+/// columns are reset to `NotSet` so saves never write them. Without any of these, the
+/// impl keeps SeaORM's default behavior.
+fn generate_active_model_behavior(fields: &[GeneratedField]) -> TokenStream {
+    let stamp = |f: &GeneratedField| {
+        let field = format_ident!("{}", f.name);
+        current_time_value(&f.rust_type).map(|now| {
+            quote! {
+                self.#field = sea_orm::ActiveValue::Set(#now);
+            }
+        })
+    };
+    let on_insert: Vec<TokenStream> = fields
+        .iter()
+        .filter(|f| f.auto_time.is_some())
+        .filter_map(stamp)
+        .collect();
+    let on_update: Vec<TokenStream> = fields
+        .iter()
+        .filter(|f| {
+            f.auto_time == Some(AutoTime::Update)
+                || f.update_default
+                    .as_deref()
+                    .map(is_current_timestamp_expr)
+                    .unwrap_or(false)
+        })
+        .filter_map(stamp)
+        .collect();
+
+    // The database computes generated columns, so they are never written
+    let read_only: Vec<TokenStream> = fields
+        .iter()
+        .filter(|f| f.generated.is_some())
+        .map(|f| {
+            let field = format_ident!("{}", f.name);
+            quote! {
+                self.#field = sea_orm::ActiveValue::NotSet;
+            }
+        })
+        .collect();
+
+    if on_insert.is_empty() && on_update.is_empty() && read_only.is_empty() {
+        return quote! {
+            impl ActiveModelBehavior for ActiveModel {}
+        };
+    }
+
+    let stamps = match (on_insert.is_empty(), on_update.is_empty()) {
+        (true, true) => quote! {},
+        (true, false) => quote! {
+            if !insert {
+                #(#on_update)*
+            }
+        },
+        (false, true) => quote! {
+            if insert {
+                #(#on_insert)*
+            }
+        },
+        (false, false) => quote! {
+            if insert {
+                #(#on_insert)*
+            } else {
+                #(#on_update)*
+            }
+        },
+    };
+
+    quote! {
+        #[async_trait::async_trait]
+        impl ActiveModelBehavior for ActiveModel {
+            async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+            where
+                C: ConnectionTrait,
+            {
+                #stamps
+                #(#read_only)*
+                Ok(self)
+            }
+        }
+    }
+}
+
+/// Generate `ActiveModel::changed_columns` listing the columns that are `Set`
+///
+/// Unchanged and `NotSet` columns are left out, so the result is exactly what an
+/// update would write.
+fn generate_changed_columns(fields: &[GeneratedField], oneof_columns: &[String]) -> TokenStream {
+    let checks = fields
+        .iter()
+        .map(|f| f.name.as_str())
+        .chain(oneof_columns.iter().map(String::as_str))
+        .map(|name| {
+            let field = format_ident!("{}", name);
+            let column = format_ident!("{}", name.to_upper_camel_case());
+            quote! {
+                if self.#field.is_set() {
+                    columns.push(Column::#column);
+                }
+            }
+        });
+
+    quote! {
+        impl ActiveModel {
+            /// Columns holding a `Set` value, e.g. for audit logging
+            pub fn changed_columns(&self) -> Vec<Column> {
+                let mut columns = Vec::new();
+                #(#checks)*
+                columns
+            }
+        }
+    }
+}
+
+/// Generate `ActiveModel::empty` with every column `NotSet`
+///
+/// Unlike `ActiveModel::new()`, no `ActiveModelBehavior` defaults are applied, so only
+/// the columns set afterwards are written by an update.
+fn generate_empty_active_model(fields: &[GeneratedField], oneof_columns: &[String]) -> TokenStream {
+    let columns = fields
+        .iter()
+        .map(|f| f.name.as_str())
+        .chain(oneof_columns.iter().map(String::as_str))
+        .map(|name| format_ident!("{}", name));
+
+    quote! {
+        impl ActiveModel {
+            /// An active model with every column `NotSet`, for building partial updates
+            pub fn empty() -> Self {
+                Self {
+                    #(#columns: sea_orm::ActiveValue::NotSet),*
+                }
+            }
+        }
+    }
+}
+
+/// Generate `column_for_field_path` and `column_for_field_number` lookups
+///
+/// Paths are the proto field names used by `google.protobuf.FieldMask`. Columns with no
+/// backing proto field (such as the version column) have no entry.
+fn generate_field_mask_map(fields: &[GeneratedField]) -> TokenStream {
+    let (paths, numbers): (Vec<_>, Vec<_>) = fields
+        .iter()
+        .filter_map(|f| {
+            let (path, number) = f.proto_field.as_ref()?;
+            let column = format_ident!("{}", f.name.to_upper_camel_case());
+            let number = proc_macro2::Literal::i32_unsuffixed(*number);
+            Some((
+                quote! { #path => Some(Column::#column), },
+                quote! { #number => Some(Column::#column), },
+            ))
+        })
+        .unzip();
+
+    quote! {
+        /// Resolve a `FieldMask` path to the column it updates
+        pub fn column_for_field_path(path: &str) -> Option<Column> {
+            match path {
+                #(#paths)*
+                _ => None,
+            }
+        }
+
+        /// Resolve a proto field number to the column it maps to
+        pub fn column_for_field_number(number: i32) -> Option<Column> {
+            match number {
+                #(#numbers)*
+                _ => None,
+            }
+        }
+    }
+}
+
+/// SeaQuery `ColumnDef` builder method for a scalar Rust column type
+fn sea_query_column_method(rust_type: &str) -> Option<&'static str> {
+    Some(match rust_type {
+        "i16" => "small_integer",
+        "i32" => "integer",
+        "i64" => "big_integer",
+        "u32" => "unsigned",
+        "u64" => "big_unsigned",
+        "f32" => "float",
+        "f64" => "double",
+        "bool" => "boolean",
+        "String" => "string",
+        "Vec<u8>" => "binary",
+        "Uuid" => "uuid",
+        "Decimal" => "decimal",
+        "Date" => "date",
+        "Time" => "time",
+        "DateTime" => "date_time",
+        "DateTimeUtc" | "DateTimeWithTimeZone" => "timestamp_with_time_zone",
+        _ => return None,
+    })
+}
+
+/// Model field names of the columns a message's oneofs generate
+fn oneof_columns(message: &DescriptorProto) -> Vec<String> {
+    extract_oneofs(message)
+        .iter()
+        .flat_map(|oneof| {
+            let base = oneof.name.to_snake_case();
+            match oneof.strategy {
+                OneofStrategy::Flatten => oneof
+                    .fields
+                    .iter()
+                    .map(|f| f.name.to_snake_case())
+                    .collect(),
+                OneofStrategy::Json => vec![base],
+                OneofStrategy::Tagged => {
+                    let disc = if oneof.discriminator_column.is_empty() {
+                        format!("{}_type", base)
+                    } else {
+                        oneof.discriminator_column.to_snake_case()
+                    };
+                    vec![disc, format!("{}_value", base)]
+                }
+            }
+        })
+        .collect()
+}
+
+/// Generate `Entity::find_by_ids` for batch lookups by a single-column primary key
+fn generate_find_by_ids(pk: &GeneratedField) -> TokenStream {
+    let column = format_ident!("{}", pk.name.to_upper_camel_case());
+    let pk_type: syn::Type =
+        syn::parse_str(&pk.rust_type).unwrap_or_else(|_| syn::parse_quote!(i64));
+
+    quote! {
+        /// Fetch every model whose primary key is in `ids`
+        pub async fn find_by_ids<C>(db: &C, ids: &[#pk_type]) -> Result<Vec<Model>, DbErr>
+        where
+            C: ConnectionTrait,
+        {
+            if ids.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            Self::find()
+                .filter(Column::#column.is_in(ids.iter().cloned()))
+                .all(db)
+                .await
+        }
+    }
+}
+
+/// Generate the `HasId` impl exposing the model's primary key
+///
+/// Composite keys are returned as a tuple in field order.
+fn generate_has_id_impl(
+    message_name: &str,
+    fields: &[GeneratedField],
+) -> Result<TokenStream, GeneratorError> {
+    let primary_keys: Vec<&GeneratedField> = fields.iter().filter(|f| f.primary_key).collect();
+    if primary_keys.is_empty() {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "has_id_trait on {} requires a primary key field",
+            message_name
+        )));
+    }
+
+    let types = primary_keys.iter().map(|pk| {
+        syn::parse_str::<syn::Type>(&pk.rust_type).unwrap_or_else(|_| syn::parse_quote!(i64))
+    });
+    let values = primary_keys.iter().map(|pk| {
+        let name = format_ident!("{}", pk.name);
+        if is_copy_type(&pk.rust_type) {
+            quote! { self.#name }
+        } else {
+            quote! { self.#name.clone() }
+        }
+    });
+    let (id_type, id_value) = if primary_keys.len() == 1 {
+        (quote! { #(#types)* }, quote! { #(#values)* })
+    } else {
+        (quote! { (#(#types),*) }, quote! { (#(#values),*) })
+    };
+
+    Ok(quote! {
+        impl super::support::HasId for Model {
+            type Id = #id_type;
+
+            fn id(&self) -> Self::Id {
+                #id_value
+            }
+        }
+    })
+}
+
+/// Generate `Model::<field>_map` accessors deserializing `map<K, V>` JSON columns
+///
+/// Returns None when no column has a typed `HashMap`.
+fn generate_json_map_accessors(fields: &[GeneratedField]) -> Option<TokenStream> {
+    let accessors: Vec<TokenStream> = fields
+        .iter()
+        .filter_map(|f| {
+            let map_type: syn::Type = syn::parse_str(f.json_map.as_deref()?).ok()?;
+            let field = format_ident!("{}", f.name);
+            let method = format_ident!("{}_map", f.name);
+            let doc = format!(" `{}` deserialized from its JSON column", f.name);
+            Some(quote! {
+                #[doc = #doc]
+                pub fn #method(&self) -> Result<#map_type, serde_json::Error> {
+                    serde_json::from_value(self.#field.clone())
+                }
+            })
+        })
+        .collect();
+    if accessors.is_empty() {
+        return None;
+    }
+
+    Some(quote! {
+        impl Model {
+            #(#accessors)*
+        }
+    })
+}
+
+/// Generate protobuf byte encoding for a model through its message
+///
+/// Round-trips through the `From` conversions between the model and the message, which
+/// must be in scope through the package prelude like the message type itself.
+fn generate_proto_codec(message_name: &str) -> TokenStream {
+    let message = format_ident!("{}", message_name);
+    let to_doc = format!(" Encode the model as a `{}` protobuf message", message_name);
+    let from_doc = format!(
+        " Decode a model from the bytes of a `{}` protobuf message",
+        message_name
+    );
+
+    quote! {
+        impl Model {
+            #[doc = #to_doc]
+            pub fn to_proto_bytes(&self) -> Vec<u8> {
+                prost::Message::encode_to_vec(&#message::from(self.clone()))
+            }
+
+            #[doc = #from_doc]
+            pub fn from_proto_bytes(bytes: &[u8]) -> Result<Model, prost::DecodeError> {
+                <#message as prost::Message>::decode(bytes).map(Model::from)
+            }
+        }
+
+        impl TryFrom<&[u8]> for Model {
+            type Error = prost::DecodeError;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                Self::from_proto_bytes(bytes)
+            }
+        }
+    }
+}
+
+/// Generate `PartialEq`, `Eq` and `Hash` impls comparing models by primary key only
+///
+/// Two models for the same row are equal whatever their other fields, so they can be
+/// deduplicated in a `HashSet` or used as `HashMap` keys by identity.
+fn generate_identity_hash(
+    message_name: &str,
+    fields: &[GeneratedField],
+) -> Result<TokenStream, GeneratorError> {
+    let primary_keys: Vec<syn::Ident> = fields
+        .iter()
+        .filter(|f| f.primary_key)
+        .map(|f| format_ident!("{}", f.name))
+        .collect();
+    if primary_keys.is_empty() {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "identity_hash on {} requires a primary key field",
+            message_name
+        )));
+    }
+
+    Ok(quote! {
+        impl PartialEq for Model {
+            fn eq(&self, other: &Self) -> bool {
+                #(self.#primary_keys == other.#primary_keys)&&*
+            }
+        }
+
+        impl Eq for Model {}
+
+        impl std::hash::Hash for Model {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                #(std::hash::Hash::hash(&self.#primary_keys, state);)*
+            }
+        }
+    })
+}
+
+/// Whether a primary key type is `Copy`, so it can be returned without cloning
+fn is_copy_type(rust_type: &str) -> bool {
+    matches!(
+        rust_type,
+        "i8" | "i16"
+            | "i32"
+            | "i64"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "f32"
+            | "f64"
+            | "bool"
+            | "Uuid"
+            | "uuid::Uuid"
+    )
+}
+
+/// Generate the per-package `support` module defining the `HasId` trait
+///
+/// Emitted into `dir`, the package's output directory, when any of its entities sets
+/// `has_id_trait`.
+pub fn generate_support_module(dir: &str) -> File {
+    let code = quote! {
+        //! Support traits for the generated entities
+        //!
+        //! Generated by protoc-gen-seaorm.
+        //! @generated
+
+        /// A model with a primary key, for code generic over entities
+        pub trait HasId {
+            /// The primary key type, a tuple for composite keys
+            type Id;
+
+            /// The model's primary key value
+            fn id(&self) -> Self::Id;
+        }
+    };
+
+    let content = code.to_string();
+    let formatted = match syn::parse_file(&content) {
+        Ok(parsed) => prettyplease::unparse(&parsed),
+        Err(_) => content,
+    };
+
+    let name = if dir.is_empty() {
+        "support.rs".to_string()
+    } else {
+        format!("{}/support.rs", dir)
+    };
+
+    File {
+        name: Some(name),
+        content: Some(formatted),
+        ..Default::default()
+    }
+}
+
+/// Generate `encode_<field>` / `decode_<field>` helpers for `repeated_as: "blob"` columns
+///
+/// The blob is a little-endian `u32` element count followed by each element in
+/// little-endian byte order. Encoding fails for more than `u32::MAX` elements, which
+/// the count can't hold.
+fn generate_blob_codecs(fields: &[GeneratedField]) -> TokenStream {
+    let codecs = fields.iter().filter_map(|f| {
+        let element: syn::Type = syn::parse_str(f.blob_element.as_deref()?).ok()?;
+        let encode = format_ident!("encode_{}", f.name);
+        let decode = format_ident!("decode_{}", f.name);
+        let encode_doc = format!("Pack {} values into the stored blob", f.name);
+        let decode_doc = format!("Unpack the stored {} blob", f.name);
+        let invalid = format!("invalid {} blob", f.name);
+        let too_long = format!("too many {} values for a blob", f.name);
+
+        Some(quote! {
+            #[doc = #encode_doc]
+            pub fn #encode(values: &[#element]) -> Result<Vec<u8>, DbErr> {
+                let len = u32::try_from(values.len()).map_err(|_| DbErr::Custom(#too_long.to_string()))?;
+                let mut bytes = Vec::with_capacity(4 + values.len() * std::mem::size_of::<#element>());
+                bytes.extend_from_slice(&len.to_le_bytes());
+                for value in values {
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+                Ok(bytes)
+            }
+
+            #[doc = #decode_doc]
+            pub fn #decode(bytes: &[u8]) -> Result<Vec<#element>, DbErr> {
+                const WIDTH: usize = std::mem::size_of::<#element>();
+                let invalid = || DbErr::Custom(#invalid.to_string());
+
+                let header = bytes.get(..4).ok_or_else(invalid)?;
+                let body = &bytes[4..];
+                let len = u32::from_le_bytes(header.try_into().map_err(|_| invalid())?) as usize;
+                if len.checked_mul(WIDTH) != Some(body.len()) {
+                    return Err(invalid());
+                }
+
+                body.chunks_exact(WIDTH)
+                    .map(|chunk| chunk.try_into().map(#element::from_le_bytes).map_err(|_| invalid()))
+                    .collect()
+            }
+        })
+    });
+
+    quote! {
+        impl Model {
+            #(#codecs)*
+        }
+    }
+}
+
+/// Generate `<field>_narrowed` accessors converting widened columns back to the proto type
+///
+/// Values written through the proto type always fit, but rows written by other
+/// clients may not, so the conversion is fallible.
+fn generate_narrowing_conversions(fields: &[GeneratedField]) -> TokenStream {
+    let conversions = fields.iter().filter_map(|f| {
+        let proto_type: syn::Type = syn::parse_str(f.widened_from.as_deref()?).ok()?;
+        let ident = format_ident!("{}", f.name);
+        let method = format_ident!("{}_narrowed", f.name);
+        let doc = format!(
+            "The {} column as the proto's {}",
+            f.name,
+            f.widened_from.as_deref()?
+        );
+
+        Some(if f.rust_type.starts_with("Option<") {
+            quote! {
+                #[doc = #doc]
+                pub fn #method(&self) -> Result<Option<#proto_type>, std::num::TryFromIntError> {
+                    self.#ident.map(#proto_type::try_from).transpose()
+                }
+            }
+        } else {
+            quote! {
+                #[doc = #doc]
+                pub fn #method(&self) -> Result<#proto_type, std::num::TryFromIntError> {
+                    #proto_type::try_from(self.#ident)
+                }
+            }
+        })
+    });
+
+    quote! {
+        impl Model {
+            #(#conversions)*
+        }
+    }
+}
+
+/// Reject model fields that reuse a name the message reserves
+///
+/// protoc already keeps declared fields off reserved names, but columns that come
+/// from elsewhere (relations, oneof columns, included base columns, the version
+/// column) could still bring back a removed field's name.
+fn check_reserved_names(
+    message_name: &str,
+    message: &DescriptorProto,
+    model_fields: &[TokenStream],
+) -> Result<(), GeneratorError> {
+    use syn::parse::Parser;
+
+    if message.reserved_name.is_empty() {
+        return Ok(());
+    }
+
+    for tokens in model_fields {
+        let Some(ident) = syn::Field::parse_named
+            .parse2(tokens.clone())
+            .ok()
+            .and_then(|f| f.ident)
+        else {
+            continue;
+        };
+        let name = ident.to_string();
+        if message.reserved_name.contains(&name) {
+            return Err(GeneratorError::InvalidConfig(format!(
+                "{} would generate a field named \"{}\", which the message reserves; rename the relation, oneof or column",
+                message_name, name
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate `impl Default for Model` honoring configured column defaults
+///
+/// Columns with a literal `default_value` (or proto2 default) start from that value,
+/// enum columns from the variant it stores; everything else, including oneof and
+/// relation fields, uses `Default::default()`.
+fn generate_default_impl(
+    message_name: &str,
+    fields: &[GeneratedField],
+    model_fields: &[TokenStream],
+) -> Result<TokenStream, GeneratorError> {
+    use syn::parse::Parser;
+
+    let mut initializers = Vec::new();
+    for tokens in model_fields {
+        let Ok(field) = syn::Field::parse_named.parse2(tokens.clone()) else {
+            continue;
+        };
+        let Some(ident) = field.ident else {
+            continue;
+        };
+        let value = match fields.iter().find(|f| ident == f.name) {
+            Some(f) => field_default(message_name, f)?,
+            None => quote! { Default::default() },
+        };
+        initializers.push(quote! { #ident: #value });
+    }
+
+    Ok(quote! {
+        impl Default for Model {
+            fn default() -> Self {
+                Self {
+                    #(#initializers),*
+                }
+            }
+        }
+    })
+}
+
+/// Rust expression for a column in the `Default` impl
+///
+/// Enum columns map their `default_value` (the stored value) back to its variant, and
+/// otherwise need the enum's own `Default`; they fail when neither is available.
+fn field_default(
+    message_name: &str,
+    field: &GeneratedField,
+) -> Result<TokenStream, GeneratorError> {
+    let default = default_value_of(field);
+    let Some(ref enum_variants) = field.enum_variants else {
+        return Ok(default
+            .and_then(|value| default_value_expr(&field.rust_type, &value))
+            .unwrap_or_else(|| quote! { Default::default() }));
+    };
+
+    let nullable = field.rust_type.starts_with("Option<");
+    let variant = match default {
+        Some(value) => enum_variants
+            .variants
+            .iter()
+            .find(|(stored, _)| *stored == value)
+            .map(|(_, variant)| variant),
+        None if nullable => return Ok(quote! { None }),
+        None => enum_variants.default.as_ref(),
+    };
+    let Some(variant) = variant else {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "derive_default on {} has no default for {}.{}; set a default_value naming \
+             a stored value of {}, or derive Default on the enum",
+            message_name, message_name, field.name, enum_variants.rust_name
+        )));
+    };
+
+    let enum_ident = format_ident!("{}", enum_variants.rust_name);
+    let variant = format_ident!("{}", variant);
+    Ok(if nullable {
+        quote! { Some(#enum_ident::#variant) }
+    } else {
+        quote! { #enum_ident::#variant }
+    })
+}
+
+/// The `default_value` configured on a column, unquoted
+fn default_value_of(field: &GeneratedField) -> Option<String> {
+    field.attributes.iter().find_map(|attr| {
+        let literal = attr.strip_prefix("default_value = ")?;
+        syn::parse_str::<syn::LitStr>(literal)
+            .ok()
+            .map(|lit| lit.value())
+    })
+}
+
+/// Rust expression for a column default, if the value is a literal of the column's type
+fn default_value_expr(rust_type: &str, value: &str) -> Option<TokenStream> {
+    if let Some(inner) = rust_type
+        .strip_prefix("Option<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        let inner = default_value_expr(inner, value)?;
+        return Some(quote! { Some(#inner) });
+    }
+
+    match rust_type {
+        "String" => Some(quote! { #value.to_string() }),
+        "bool" => value.parse::<bool>().ok().map(|b| quote! { #b }),
+        "f32" | "f64" => value
+            .parse::<f64>()
+            .ok()
+            .map(proc_macro2::Literal::f64_unsuffixed)
+            .map(|lit| quote! { #lit }),
+        "i32" | "i64" | "u32" | "u64" => value
+            .parse::<i128>()
+            .ok()
+            .map(proc_macro2::Literal::i128_unsuffixed)
+            .map(|lit| quote! { #lit }),
+        _ => None,
+    }
+}
+
+/// Generate a `<Message>Update` struct for partial (PATCH-style) updates
+///
+/// Every column the caller may write becomes an `Option`; `apply_to` only sets the
+/// columns that are `Some`, leaving the rest of the active model untouched.
+/// Nullable columns become `Option<Option<T>>` so they can be explicitly cleared.
+/// Primary keys, `generated` columns and the `managed` columns (the version and
+/// soft-delete columns, maintained by their own helpers) are left out.
+fn generate_update_struct(
+    message_name: &str,
+    fields: &[GeneratedField],
+    managed: &[&str],
+) -> TokenStream {
+    let struct_ident = format_ident!("{}Update", message_name.to_upper_camel_case());
+    let struct_doc = format!(
+        "Partial update for {}; only `Some` fields are applied",
+        message_name
+    );
+
+    let updatable: Vec<&GeneratedField> = fields
+        .iter()
+        .filter(|f| !f.primary_key && f.generated.is_none())
+        .filter(|f| !managed.contains(&f.name.as_str()))
+        .collect();
+
+    let field_tokens = updatable.iter().map(|f| {
+        let name = format_ident!("{}", f.name);
+        let ty: syn::Type =
+            syn::parse_str(&f.rust_type).unwrap_or_else(|_| syn::parse_quote!(String));
+        quote! { pub #name: Option<#ty> }
+    });
+
+    let assignments = updatable.iter().map(|f| {
+        let name = format_ident!("{}", f.name);
+        quote! {
+            if let Some(value) = &self.#name {
+                model.#name = sea_orm::ActiveValue::Set(value.clone());
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #struct_doc]
+        #[derive(Clone, Debug, Default, PartialEq)]
+        pub struct #struct_ident {
+            #(#field_tokens),*
+        }
+
+        impl #struct_ident {
+            /// Set the provided fields on an active model, leaving the others unchanged
+            pub fn apply_to(&self, model: &mut ActiveModel) {
+                #(#assignments)*
+            }
+        }
+    }
+}
+
+/// Generate a `<Message>Filter` struct turning each `Some` field into a query filter
+///
+/// Every column gets the operations listed in its `filter_ops`, or `eq` by default.
+/// `eq` keeps the column's name; other operations are suffixed (`email_contains`).
+/// JSON and blob columns are left out.
+fn generate_filter_struct(
+    message_name: &str,
+    fields: &[GeneratedField],
+) -> Result<TokenStream, GeneratorError> {
+    let struct_ident = format_ident!("{}Filter", message_name.to_upper_camel_case());
+    let struct_doc = format!(
+        "Query filters for {}; each `Some` field adds a condition",
+        message_name
+    );
+
+    let mut field_tokens = Vec::new();
+    let mut conditions = Vec::new();
+
+    for f in fields {
+        let value_type = f
+            .rust_type
+            .strip_prefix("Option<")
+            .and_then(|t| t.strip_suffix('>'))
+            .unwrap_or(&f.rust_type);
+        if f.blob_element.is_some() || value_type.starts_with("Vec<") || value_type.contains("Json")
+        {
+            continue;
+        }
+        let ty: syn::Type =
+            syn::parse_str(value_type).unwrap_or_else(|_| syn::parse_quote!(String));
+        let column = format_ident!("{}", f.name.to_upper_camel_case());
+        let is_string = value_type == "String";
+
+        let ops: Vec<&str> = if f.filter_ops.is_empty() {
+            vec!["eq"]
+        } else {
+            f.filter_ops.iter().map(String::as_str).collect()
+        };
+
+        for op in ops {
+            let name = if op == "eq" {
+                format_ident!("{}", f.name)
+            } else {
+                format_ident!("{}_{}", f.name, op)
+            };
+            let (field_ty, condition) = match op {
+                "eq" | "ne" | "gt" | "gte" | "lt" | "lte" => {
+                    let method = format_ident!("{}", op);
+                    (quote! { #ty }, quote! { Column::#column.#method(value) })
+                }
+                "in" => (quote! { Vec<#ty> }, quote! { Column::#column.is_in(value) }),
+                "contains" | "starts_with" if is_string => {
+                    let method = format_ident!("{}", op);
+                    (quote! { String }, quote! { Column::#column.#method(value) })
+                }
+                "contains" | "starts_with" => {
+                    return Err(GeneratorError::InvalidConfig(format!(
+                        "filter_ops \"{}\" on {}.{} requires a string column",
+                        op, message_name, f.name
+                    )))
+                }
+                other => {
+                    return Err(GeneratorError::InvalidConfig(format!(
+                        "Unknown filter_ops value \"{}\" on {}.{}",
+                        other, message_name, f.name
+                    )))
+                }
+            };
+
+            field_tokens.push(quote! { pub #name: Option<#field_ty> });
+            conditions.push(quote! {
+                if let Some(value) = self.#name {
+                    query = query.filter(#condition);
+                }
+            });
+        }
+    }
+
+    Ok(quote! {
+        #[doc = #struct_doc]
+        #[derive(Clone, Debug, Default, PartialEq)]
+        pub struct #struct_ident {
+            #(#field_tokens),*
+        }
+
+        impl #struct_ident {
+            /// Add a filter to `query` for every `Some` field
+            pub fn apply(self, mut query: Select<Entity>) -> Select<Entity> {
+                #(#conditions)*
+                query
+            }
+        }
+    })
+}
+
+/// Look up the SeaORM message options for a message
+///
+/// First tries the cache (populated from prost-reflect), then falls back to prost-types parsing.
+pub fn resolve_message_options(
+    file: &FileDescriptorProto,
+    message: &DescriptorProto,
+) -> Option<seaorm::MessageOptions> {
+    let file_name = file.name.as_deref().unwrap_or("");
+    let msg_name = message.name.as_deref().unwrap_or("");
+
+    get_cached_message_options(file_name, msg_name).or_else(|| parse_message_options(message))
+}
+
+/// Determine the table name for a message (explicit option or snake_case of the name)
+pub fn resolve_table_name(message_name: &str, options: &seaorm::MessageOptions) -> String {
+    if options.table_name.is_empty() {
+        message_name.to_snake_case()
+    } else {
+        options.table_name.clone()
+    }
+}
+
+/// Return the table name a message will be generated with, if it produces an entity
+///
+/// Messages without model options, skipped messages, projections and embeddables
+/// have no table.
+pub fn entity_table_name(file: &FileDescriptorProto, message: &DescriptorProto) -> Option<String> {
+    let options = resolve_message_options(file, message)?;
+    if options.skip || options.projection || options.embeddable {
+        return None;
+    }
+    Some(resolve_table_name(message.name.as_deref()?, &options))
+}
+
+/// A column field of a generated entity, as seen by code that builds its models
+#[derive(Debug, Clone)]
+pub struct EntityColumn {
+    /// Field name on the model
+    pub name: String,
+    /// Rust type of the field
+    pub rust_type: String,
+    /// Whether the column is (part of) the primary key
+    pub primary_key: bool,
+    /// Whether the column has a configured `default_value` or `default_expr`
+    pub has_default: bool,
+    /// Whether the column is stored wider than its proto field (`widen`)
+    pub widened: bool,
+}
+
+/// The column fields an entity message generates, in declaration order
+///
+/// Relation and oneof fields are excluded since they don't map to a single column.
+pub fn entity_columns(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    message: &DescriptorProto,
+) -> Result<Vec<EntityColumn>, GeneratorError> {
+    let options = resolve_message_options(file, message).unwrap_or_default();
+    Ok(collect_fields(ctx, file, message, &options)?
+        .into_iter()
+        .map(|f| EntityColumn {
+            has_default: f
+                .attributes
+                .iter()
+                .any(|a| a.starts_with("default_value") || a.starts_with("default_expr")),
+            widened: f.widened_from.is_some(),
+            name: f.name,
+            rust_type: f.rust_type,
+            primary_key: f.primary_key,
+        })
+        .collect())
+}
+
+/// The table name of an entity message with the `table_prefix` parameter applied
+pub fn prefixed_table_name(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    message: &DescriptorProto,
+) -> Option<String> {
+    let table_name = entity_table_name(file, message)?;
+    let table_name = format!(
+        "{}{}",
+        ctx.config.table_prefix.as_deref().unwrap_or(""),
+        table_name
+    );
+    Some(shorten_identifier(
+        &table_name,
+        ctx.config.max_identifier_length,
+    ))
+}
+
+/// SeaQuery expressions building an entity's schema, for its migration
+pub struct SchemaStatements {
+    /// Name of the entity's table
+    pub table_name: String,
+    /// `TableCreateStatement` expression with every column and the given extra calls
+    pub create_table: TokenStream,
+    /// `IndexCreateStatement` expressions for indexed columns and declared `indexes`
+    pub create_indexes: Vec<TokenStream>,
+}
 
-/// Generate a SeaORM entity file from a protobuf message
-pub fn generate(
+/// Build the schema statements of an entity message, addressing its items through `module`
+///
+/// `extra` calls (such as foreign keys) are chained onto the table statement. Returns
+/// None for messages that aren't entities.
+pub fn schema_statements(
+    ctx: &Context,
     file: &FileDescriptorProto,
     message: &DescriptorProto,
-) -> Result<Option<File>, GeneratorError> {
-    let file_name = file.name.as_deref().unwrap_or("");
-    let msg_name = message.name.as_deref().unwrap_or("");
+    module: &TokenStream,
+    extra: &[TokenStream],
+) -> Result<Option<SchemaStatements>, GeneratorError> {
+    let Some(table_name) = prefixed_table_name(ctx, file, message) else {
+        return Ok(None);
+    };
+    let options = resolve_message_options(file, message).unwrap_or_default();
+    let fields = collect_fields(ctx, file, message, &options)?;
 
-    // Check if this message has SeaORM options
-    // First try the cache (populated from prost-reflect), then fall back to prost-types parsing
-    let message_options = match get_cached_message_options(file_name, msg_name) {
-        Some(opts) => opts,
-        None => match parse_message_options(message) {
-            Some(opts) => opts,
-            None => return Ok(None), // No seaorm options, skip this message
-        },
+    let table = quote! { #module::Entity };
+    let column = quote! { #module::Column };
+    let create_table = table_create_expr(&table, &column, &fields, &oneof_columns(message), extra);
+    let create_indexes =
+        index_create_exprs(&table_name, &table, &column, &fields, &options.indexes)?;
+
+    Ok(Some(SchemaStatements {
+        table_name,
+        create_table,
+        create_indexes,
+    }))
+}
+
+/// Check an entity for problems that don't stop generation but break at compile or run time
+///
+/// Reports a missing primary key and message-level relations whose `related` entity
+/// isn't generated from any file in the request. Messages that aren't entities, or
+/// whose fields fail to generate (reported by `generate`), yield no problems here.
+pub fn check_entity(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    message: &DescriptorProto,
+) -> Vec<GeneratorError> {
+    let mut problems = Vec::new();
+    if entity_table_name(file, message).is_none() {
+        return problems;
+    }
+    let Some(options) = resolve_message_options(file, message) else {
+        return problems;
     };
+    let message_name = message.name.as_deref().unwrap_or("");
 
-    // Skip if explicitly marked
-    if message_options.skip {
-        return Ok(None);
+    if let Ok(fields) = collect_fields(ctx, file, message, &options) {
+        if !fields.iter().any(|f| f.primary_key) {
+            problems.push(GeneratorError::InvalidConfig(format!(
+                "{} has no primary key; mark a column with primary_key: true",
+                message_name
+            )));
+        }
     }
 
-    // Get message name
-    let message_name = message
-        .name
-        .as_ref()
-        .ok_or_else(|| GeneratorError::CodeGenError("Message has no name".to_string()))?;
+    for relation in options.relations.iter().filter(|r| !r.related.is_empty()) {
+        let exists = ctx
+            .resolver
+            .resolve_message(&relation.related, file.package.as_deref())
+            .is_some_and(|m| entity_table_name(m.file, m.message).is_some());
+        if !exists {
+            problems.push(GeneratorError::InvalidConfig(format!(
+                "Relation \"{}\" on {} points to unknown entity \"{}\"",
+                relation.name, message_name, relation.related
+            )));
+        }
+    }
 
-    // Determine table name
-    let table_name = if message_options.table_name.is_empty() {
-        message_name.to_snake_case()
-    } else {
-        message_options.table_name.clone()
-    };
+    problems
+}
 
-    // Generate the output filename
-    let module_name = message_name.to_snake_case();
-    let output_filename = format!(
-        "{}/{}.rs",
-        file.package.as_deref().unwrap_or("").replace('.', "/"),
-        module_name
-    );
+/// Generate the column fields of an entity, merging in an `include`d base message
+///
+/// Base columns come first, in the base message's order. A field redeclared on the
+/// entity replaces the base field of the same name, and if the entity declares its
+/// own primary key the base's primary key columns become regular columns.
+fn collect_fields(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    message: &DescriptorProto,
+    options: &seaorm::MessageOptions,
+) -> Result<Vec<GeneratedField>, GeneratorError> {
+    let msg_name = message.name.as_deref().unwrap_or("");
 
-    // Generate field definitions
-    let fields = generate_fields(message, file_name, msg_name)?;
+    let own_fields = generate_fields(ctx, file, message)?;
+    if options.include.is_empty() {
+        return Ok(own_fields);
+    }
 
-    // Generate oneof fields
-    let oneof_fields = generate_oneof_fields(message);
+    let base = ctx
+        .resolver
+        .resolve_message(&options.include, file.package.as_deref())
+        .ok_or_else(|| {
+            GeneratorError::InvalidConfig(format!(
+                "{} includes unknown message \"{}\"",
+                msg_name, options.include
+            ))
+        })?;
+    let base_fields = generate_fields(ctx, base.file, base.message)?;
 
-    // Build the entity struct
-    let struct_name = format_ident!("Model");
-    let table_name_lit = &table_name;
+    let own_has_pk = own_fields.iter().any(|f| f.primary_key);
+    let mut fields: Vec<GeneratedField> = base_fields
+        .into_iter()
+        .filter(|base_field| !own_fields.iter().any(|f| f.name == base_field.name))
+        .map(|mut base_field| {
+            if own_has_pk && base_field.primary_key {
+                base_field.primary_key = false;
+                base_field
+                    .attributes
+                    .retain(|a| !a.starts_with("primary_key"));
+            }
+            base_field
+        })
+        .collect();
+    fields.extend(own_fields);
 
-    let field_tokens: Vec<_> = fields
+    Ok(fields)
+}
+
+/// Generate a read-only projection struct deriving `FromQueryResult`
+///
+/// Projections are used for custom selects (`into_model::<T>()`) and carry no
+/// table, column or relation definitions, so column attributes are dropped.
+fn generate_projection(
+    message_name: &str,
+    output_filename: String,
+    fields: &[GeneratedField],
+) -> File {
+    let struct_name = format_ident!("{}", message_name.to_upper_camel_case());
+
+    let field_tokens: Vec<TokenStream> = fields
         .iter()
         .map(|f| {
             let name = format_ident!("{}", f.name);
-            let ty: syn::Type = syn::parse_str(&f.rust_type).unwrap_or_else(|_| {
-                // Fallback to parsing as a simple path
-                syn::parse_str::<syn::Type>(&f.rust_type.to_string())
-                    .unwrap_or_else(|_| syn::parse_quote!(String))
-            });
-
-            let attrs = &f.attributes;
-            if attrs.is_empty() {
-                quote! {
-                    pub #name: #ty
-                }
-            } else {
-                let attr_tokens: Vec<proc_macro2::TokenStream> = attrs
-                    .iter()
-                    .map(|a| {
-                        let attr_content: proc_macro2::TokenStream = a.parse().unwrap();
-                        quote! { #[sea_orm(#attr_content)] }
-                    })
-                    .collect();
-                quote! {
-                    #(#attr_tokens)*
-                    pub #name: #ty
-                }
-            }
+            let ty: syn::Type =
+                syn::parse_str(&f.rust_type).unwrap_or_else(|_| syn::parse_quote!(String));
+            quote! { pub #name: #ty }
         })
         .collect();
+    let inline_enums = inline_enums(fields);
+
+    let code = quote! {
+        //! SeaORM query result projection
+        //!
+        //! Generated by protoc-gen-seaorm from protobuf definition.
+        //! @generated
 
-    // Generate relation fields from message-level relation definitions (SeaORM 2.0 dense format)
-    // Uses generate_relation_fields to properly handle self-referential relation pairs
-    let relation_fields: Vec<TokenStream> =
-        generate_relation_fields(&message_options.relations, message_name);
+        #[allow(unused_imports)]
+        use super::prelude::*;
+        use sea_orm::entity::prelude::*;
+        use sea_orm::FromQueryResult;
 
-    // Combine regular fields, oneof fields, and relation fields
-    let all_field_tokens: Vec<TokenStream> = field_tokens
-        .into_iter()
-        .chain(oneof_fields)
-        .chain(relation_fields)
+        #[derive(Clone, Debug, PartialEq, FromQueryResult)]
+        pub struct #struct_name {
+            #(#field_tokens),*
+        }
+
+        #(#inline_enums)*
+    };
+
+    let content = code.to_string();
+    let formatted = match syn::parse_file(&content) {
+        Ok(parsed) => prettyplease::unparse(&parsed),
+        Err(_) => content,
+    };
+
+    File {
+        name: Some(output_filename),
+        content: Some(formatted),
+        ..Default::default()
+    }
+}
+
+/// Generate a JSON-serializable struct for a message used as an `embed` target
+///
+/// `Eq` is only derived when no field is a float, since the embedding entity's
+/// `Model` derives `Eq` and floats would make that impossible anyway.
+fn generate_embeddable(
+    message_name: &str,
+    message: &DescriptorProto,
+    output_filename: String,
+    fields: &[GeneratedField],
+) -> File {
+    let struct_name = format_ident!("{}", message_name.to_upper_camel_case());
+    let struct_doc = format!("{} stored as JSON in embedding entities", message_name);
+
+    let mut field_tokens: Vec<TokenStream> = fields
+        .iter()
+        .map(|f| {
+            let name = format_ident!("{}", f.name);
+            let ty: syn::Type =
+                syn::parse_str(&f.rust_type).unwrap_or_else(|_| syn::parse_quote!(String));
+            quote! { pub #name: #ty }
+        })
         .collect();
 
+    // Oneofs become typed enums; every strategy collapses to JSON inside an embed
+    let oneofs = extract_oneofs(message);
+    let is_float = |ty: &str| ty.contains("f32") || ty.contains("f64");
+    let has_float = fields.iter().any(|f| is_float(&f.rust_type))
+        || oneofs
+            .iter()
+            .flat_map(|o| variant_types(o, message))
+            .any(|ty| is_float(&ty));
+    let eq_derive = if has_float {
+        quote! {}
+    } else {
+        quote! { Eq, }
+    };
+
+    let mut oneof_enums: Vec<TokenStream> = Vec::new();
+    for oneof in &oneofs {
+        let enum_name = oneof_enum_name(message_name, oneof);
+        let enum_ident = format_ident!("{}", enum_name);
+        let field_ident = format_ident!("{}", oneof.name.to_snake_case());
+        field_tokens.push(quote! { pub #field_ident: Option<#enum_ident> });
+        oneof_enums.push(generate_json_enum(&enum_name, oneof, message, !has_float));
+    }
+
     let code = quote! {
-        //! SeaORM entity for the table
+        //! Embeddable JSON type
         //!
         //! Generated by protoc-gen-seaorm from protobuf definition.
         //! @generated
@@ -126,31 +2486,28 @@ pub fn generate(
         #[allow(unused_imports)]
         use super::prelude::*;
         use sea_orm::entity::prelude::*;
+        use serde::{Deserialize, Serialize};
 
-        #[sea_orm::model]
-        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
-        #[sea_orm(table_name = #table_name_lit)]
+        #[doc = #struct_doc]
+        #[derive(Clone, Debug, PartialEq, #eq_derive Serialize, Deserialize, FromJsonQueryResult)]
         pub struct #struct_name {
-            #(#all_field_tokens),*
+            #(#field_tokens),*
         }
 
-        impl ActiveModelBehavior for ActiveModel {}
+        #(#oneof_enums)*
     };
 
-    // Format the generated code
     let content = code.to_string();
-
-    // Try to format with prettyplease if we can parse it
     let formatted = match syn::parse_file(&content) {
         Ok(parsed) => prettyplease::unparse(&parsed),
-        Err(_) => content, // If parsing fails, use unformatted
+        Err(_) => content,
     };
 
-    Ok(Some(File {
+    File {
         name: Some(output_filename),
         content: Some(formatted),
         ..Default::default()
-    }))
+    }
 }
 
 /// Generated field information
@@ -158,17 +2515,80 @@ struct GeneratedField {
     name: String,
     rust_type: String,
     attributes: Vec<String>,
+    primary_key: bool,
+    /// Element type of a repeated field stored with `repeated_as: "blob"`
+    blob_element: Option<String>,
+    /// Proto type of a column stored wider with `widen`
+    widened_from: Option<String>,
+    /// Proto field name and number backing the column, if any
+    proto_field: Option<(String, i32)>,
+    /// Active enum generated in place for an un-annotated enum with `enum_as`
+    inline_enum: Option<TokenStream>,
+    /// Variants of the generated enum an enum column stores
+    enum_variants: Option<StoredVariants>,
+    /// SeaORM value adapters for a `newtype_inner` column type
+    newtype_adapters: Option<TokenStream>,
+    /// `#[deprecated]` of a `[deprecated = true]` field, with its note if any
+    deprecated: Option<Option<String>>,
+    /// SQL expression written to the column on every update
+    update_default: Option<String>,
+    /// Whether the column is left out when serializing with `derive_serde`
+    serde_skip: bool,
+    /// `HashMap` a `map<K, V>` JSON column deserializes to, if it has one
+    json_map: Option<String>,
+    /// When the column is stamped with the current time in `before_save`
+    auto_time: Option<AutoTime>,
+    /// `GENERATED ALWAYS AS (...)` clause of a column computed by the database
+    generated: Option<String>,
+    /// Filter operations for the `filter_struct`, empty for the default `eq`
+    filter_ops: Vec<String>,
+}
+
+/// When a timestamp column is stamped with the current time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AutoTime {
+    /// On insert only (`auto_create_time`)
+    Create,
+    /// On insert and every update (`auto_update_time`)
+    Update,
+}
+
+/// Inline enums needed by the fields, each emitted once
+fn inline_enums(fields: &[GeneratedField]) -> Vec<TokenStream> {
+    dedup_items(fields.iter().filter_map(|f| f.inline_enum.clone()))
+}
+
+/// Newtype value adapters needed by the fields, each emitted once
+fn newtype_adapters(fields: &[GeneratedField]) -> Vec<TokenStream> {
+    dedup_items(fields.iter().filter_map(|f| f.newtype_adapters.clone()))
+}
+
+/// Drop repeated items, keeping the first occurrence
+fn dedup_items(items: impl Iterator<Item = TokenStream>) -> Vec<TokenStream> {
+    let mut seen = Vec::new();
+    items
+        .filter(|tokens| {
+            let key = tokens.to_string();
+            let first = !seen.contains(&key);
+            seen.push(key);
+            first
+        })
+        .collect()
 }
 
 /// Generate field definitions from message fields
 fn generate_fields(
+    ctx: &Context,
+    file: &FileDescriptorProto,
     message: &DescriptorProto,
-    file_name: &str,
-    msg_name: &str,
 ) -> Result<Vec<GeneratedField>, GeneratorError> {
+    let file_name = file.name.as_deref().unwrap_or("");
+    let msg_name = message.name.as_deref().unwrap_or("");
     let mut fields = Vec::new();
+    // Normalized Rust identifier -> original proto field name, for collision detection
+    let mut seen_names: HashMap<String, &str> = HashMap::new();
 
-    for field in &message.field {
+    for (field_index, field) in message.field.iter().enumerate() {
         let field_name = field
             .name
             .as_ref()
@@ -190,10 +2610,138 @@ fn generate_fields(
             continue;
         }
 
+        // Message fields without embed or a relation follow `default_message_handling`
+        if is_unannotated_message_field(field, &field_options) {
+            match ctx.config.default_message_handling {
+                MessageHandling::Json => {}
+                MessageHandling::Relation => continue,
+                MessageHandling::Error => {
+                    return Err(GeneratorError::InvalidConfig(format!(
+                        "{}.{} is a message field without embed or a relation",
+                        msg_name, field_name
+                    )))
+                }
+            }
+        }
+
         // Determine the Rust type
         let proto_type = field.r#type();
         let type_name = field.type_name.as_deref();
-        let mapped = map_proto_type(proto_type, type_name);
+        let mut mapped = map_proto_type(proto_type, type_name);
+
+        // Maps arrive as a repeated synthetic entry message; they're stored whole as JSON
+        let map = map_entry_message(message, field).and_then(map_map_entry);
+        if let Some(ref map) = map {
+            if field_options
+                .as_ref()
+                .is_some_and(|o| !o.repeated_as.is_empty())
+            {
+                return Err(GeneratorError::InvalidConfig(format!(
+                    "{}.{} is a map, which is always stored as JSON; drop repeated_as",
+                    msg_name, field_name
+                )));
+            }
+            mapped = map.mapped.clone();
+        }
+
+        // Enum columns reference the generated enum, which may have been renamed; enums
+        // without one get an inline active enum when the field sets `enum_as`, as do
+        // integer enums stored as strings (cast through `text`)
+        let enum_as = enum_storage(msg_name, field, field_options.as_ref())?;
+        let mut inline_enum = None;
+        let mut text_cast = false;
+        let mut stored_enum = None;
+        let mut enum_variants = None;
+        if proto_type == Type::Enum {
+            let resolved =
+                type_name.and_then(|name| ctx.resolver.resolve_enum(name, file.package.as_deref()));
+            if let Some(resolved) = resolved {
+                let proto_name = resolved.enum_desc.name.as_deref().unwrap_or("");
+                let enum_options = resolve_enum_options(resolved.file, resolved.enum_desc);
+                let generated_enum = enum_options.is_some() || enum_as.is_some();
+                let stored_as = match (enum_options, enum_as) {
+                    (Some(opts), Some(DbType::String)) if db_type_of(&opts) == DbType::Integer => {
+                        let name = format!("{}Text", rust_enum_name(proto_name, &opts));
+                        inline_enum = Some(generate_inline_enum(
+                            ctx,
+                            resolved.file,
+                            resolved.enum_desc,
+                            &name,
+                            DbType::String,
+                        )?);
+                        mapped.rust_type = name;
+                        text_cast = true;
+                        DbType::String
+                    }
+                    (Some(_), Some(_)) => {
+                        return Err(GeneratorError::InvalidConfig(format!(
+                            "{}.{} sets enum_as, but {} already has (seaorm.enum_opt); \
+                             set its db_type instead",
+                            msg_name, field_name, proto_name
+                        )))
+                    }
+                    (Some(opts), None) => {
+                        mapped.rust_type = rust_enum_name(proto_name, &opts);
+                        db_type_of(&opts)
+                    }
+                    (None, Some(db_type)) => {
+                        let name = proto_name.to_upper_camel_case();
+                        inline_enum = Some(generate_inline_enum(
+                            ctx,
+                            resolved.file,
+                            resolved.enum_desc,
+                            &name,
+                            db_type,
+                        )?);
+                        mapped.rust_type = name;
+                        db_type
+                    }
+                    (None, None) => DbType::Integer,
+                };
+                if generated_enum {
+                    enum_variants = Some(stored_variants(
+                        resolved.file,
+                        resolved.enum_desc,
+                        &mapped.rust_type,
+                        stored_as,
+                    ));
+                }
+                stored_enum = Some((resolved.file, resolved.enum_desc, stored_as));
+            } else if enum_as.is_some() {
+                return Err(GeneratorError::InvalidConfig(format!(
+                    "{}.{} sets enum_as, but its enum {} could not be resolved",
+                    msg_name,
+                    field_name,
+                    type_name.unwrap_or("")
+                )));
+            }
+        }
+
+        // Timestamps can pick another chrono type, stored in the matching column type
+        let temporal = temporal_override(msg_name, field, field_options.as_ref())?;
+        if let Some(ref temporal) = temporal {
+            mapped = temporal.clone();
+        }
+
+        // Durations are stored as nanoseconds unless `duration_as` picks an interval
+        if let Some(duration) = duration_override(msg_name, field, field_options.as_ref())? {
+            mapped = duration;
+        }
+
+        let custom = custom_type(msg_name, field, field_options.as_ref(), &mapped)?;
+
+        validate_primary_key(msg_name, field, field_options.as_ref(), &mapped)?;
+        validate_insert_default(msg_name, field, field_options.as_ref())?;
+        validate_generated(msg_name, field, field_options.as_ref())?;
+        let repeated =
+            repeated_storage(msg_name, field, field_options.as_ref(), &mapped.rust_type)?;
+        let widened = widening(
+            msg_name,
+            field,
+            field_options.as_ref(),
+            &mapped,
+            ctx.config.widen_pk,
+        )?;
 
         // Check if the field is nullable
         let is_nullable = is_field_nullable(field, &field_options);
@@ -221,7 +2769,7 @@ fn generate_fields(
         // Override type if specified in options (but not for embedded fields)
         let rust_type = if !is_embedded {
             if let Some(ref opts) = field_options {
-                if !opts.column_type.is_empty() {
+                if !opts.column_type.is_empty() && !is_custom_column_type(&opts.column_type) {
                     map_column_type_to_rust(&opts.column_type, is_nullable)
                 } else {
                     rust_type
@@ -233,37 +2781,264 @@ fn generate_fields(
             rust_type
         };
 
+        // Custom Rust types replace the mapped type, keeping nullability
+        let rust_type = match &custom {
+            Some(custom) if is_nullable => format!("Option<{}>", custom.rust_type),
+            Some(custom) => custom.rust_type.clone(),
+            None => rust_type,
+        };
+        let newtype_adapters = custom
+            .as_ref()
+            .and_then(|c| Some((&c.rust_type, c.newtype_inner.as_ref()?)))
+            .map(|(newtype, inner)| generate_newtype_adapters(newtype, inner))
+            .transpose()?;
+
         // Generate attributes
-        let attributes = generate_field_attributes(field_name, &field_options, &mapped);
+        let json_column_type = ctx.config.backend.json_column_type();
+        let proto_default =
+            proto_column_default(msg_name, field, field_options.as_ref(), stored_enum)?;
+        let mut attributes = generate_attributes(
+            field_options.as_ref(),
+            &mapped,
+            proto_default.as_deref(),
+            json_column_type,
+        );
+
+        if text_cast {
+            attributes.push(text_cast_attribute());
+        }
+
+        // Bytes, timestamps and durations spell out their column type
+        let has_column_type = attributes.iter().any(|a| a.starts_with("column_type"));
+        if !has_column_type && !is_embedded {
+            if let Some(column_type) = default_column_type(field, &mapped) {
+                attributes.push(column_type_attribute(column_type));
+            } else if map.is_some() {
+                attributes.push(column_type_attribute(json_column_type));
+            }
+        }
+
+        // Widened columns swap in the wider type unless a column_type overrides it
+        let has_column_type = attributes.iter().any(|a| a.starts_with("column_type"));
+        let widened = widened.filter(|_| !has_column_type);
+        let rust_type = match &widened {
+            Some(w) => {
+                attributes.push(column_type_attribute(w.column_type));
+                if is_nullable {
+                    format!("Option<{}>", w.rust_type)
+                } else {
+                    w.rust_type.to_string()
+                }
+            }
+            _ => rust_type,
+        };
+
+        // Repeated fields stored whole in a single column
+        let has_column_type = attributes.iter().any(|a| a.starts_with("column_type"));
+        let (rust_type, blob_element) = match repeated {
+            Some(RepeatedStorage::Jsonb) => {
+                if !has_column_type {
+                    attributes.push(column_type_attribute(json_column_type));
+                }
+                (format!("Vec<{}>", mapped.rust_type), None)
+            }
+            Some(RepeatedStorage::Blob) => {
+                if !has_column_type {
+                    attributes.push("column_type = \"VarBinary(StringLen::None)\"".to_string());
+                }
+                ("Vec<u8>".to_string(), Some(mapped.rust_type.clone()))
+            }
+            None => (rust_type, None),
+        };
+
+        // Use snake_case for field name, keeping configured acronyms whole
+        let snake_name = to_column_name(field_name, &ctx.config.acronyms);
+
+        // Distinct proto names like `fooBar` and `foo_bar` normalize to the same identifier
+        if let Some(existing) = seen_names.insert(snake_name.clone(), field_name) {
+            return Err(GeneratorError::InvalidConfig(format!(
+                "Fields \"{}\" and \"{}\" on {} both normalize to \"{}\"; rename one of them",
+                existing, field_name, msg_name, snake_name
+            )));
+        }
+
+        // Defaulted column names come from the field or JSON name and follow the global
+        // template; explicit names win
+        if !attributes.iter().any(|a| a.starts_with("column_name")) {
+            let default_column = match ctx.config.column_from {
+                ColumnSource::Name => snake_name.clone(),
+                ColumnSource::JsonName => field
+                    .json_name
+                    .clone()
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| snake_name.clone()),
+            };
+            let template = ctx.config.column_name_template.as_deref();
+            let column_name =
+                templated_column_name(template, &default_column).unwrap_or(default_column);
+            if column_name != snake_name {
+                attributes.push(format!("column_name = {:?}", column_name));
+            }
+        }
+
+        // proto3 `optional` fields track presence, so the column spells out its nullability
+        if field.proto3_optional.unwrap_or(false) && rust_type.starts_with("Option<") {
+            attributes.push("nullable".to_string());
+        }
+
+        let primary_key = field_options
+            .as_ref()
+            .map(|o| o.primary_key)
+            .unwrap_or(false);
 
-        // Use snake_case for field name
-        let snake_name = field_name.to_snake_case();
+        // Custom types and column_type overrides replace the enum
+        let enum_variants = enum_variants.filter(|v| {
+            rust_type == v.rust_name || rust_type == format!("Option<{}>", v.rust_name)
+        });
 
         fields.push(GeneratedField {
             name: snake_name,
             rust_type,
             attributes,
+            primary_key,
+            blob_element,
+            widened_from: widened.map(|w| w.proto_type),
+            proto_field: Some((field_name.clone(), field_number)),
+            inline_enum,
+            enum_variants,
+            newtype_adapters,
+            deprecated: deprecation_note(file, message, field_index),
+            update_default: field_options
+                .as_ref()
+                .map(|o| o.update_default.clone())
+                .filter(|d| !d.is_empty()),
+            serde_skip: field_options
+                .as_ref()
+                .map(|o| o.serde_skip)
+                .unwrap_or(false),
+            json_map: map.and_then(|m| m.hash_map).filter(|_| custom.is_none()),
+            auto_time: field_options.as_ref().and_then(|o| {
+                if o.auto_create_time {
+                    Some(AutoTime::Create)
+                } else if o.auto_update_time {
+                    Some(AutoTime::Update)
+                } else {
+                    None
+                }
+            }),
+            generated: field_options
+                .as_ref()
+                .and_then(|o| generated_column_sql(o, ctx.config.backend)),
+            filter_ops: field_options
+                .as_ref()
+                .map(|o| o.filter_ops.clone())
+                .unwrap_or_default(),
         });
     }
 
     Ok(fields)
 }
 
-/// Generate fields for all oneofs in a message
-fn generate_oneof_fields(message: &DescriptorProto) -> Vec<TokenStream> {
+/// Generate fields for all oneofs in a message, plus the typed enums backing them
+fn generate_oneof_fields(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    message_name: &str,
+    message: &DescriptorProto,
+) -> Result<(Vec<TokenStream>, Vec<TokenStream>), GeneratorError> {
     let oneofs = extract_oneofs(message);
     let mut fields = Vec::new();
+    let mut enums = Vec::new();
 
     for oneof in oneofs {
+        let eq = !variant_types(&oneof, message)
+            .iter()
+            .any(|ty| ty.contains("f32") || ty.contains("f64"));
         let oneof_fields = match oneof.strategy {
             OneofStrategy::Flatten => generate_flatten_fields(&oneof, message),
-            OneofStrategy::Json => generate_json_fields(&oneof),
-            OneofStrategy::Tagged => generate_tagged_fields(&oneof),
+            OneofStrategy::Json => {
+                let enum_name = oneof_enum_name(message_name, &oneof);
+                enums.push(generate_json_enum(&enum_name, &oneof, message, eq));
+                generate_json_fields(&oneof, &enum_name)
+            }
+            OneofStrategy::Tagged => {
+                let enum_name = oneof_enum_name(message_name, &oneof);
+                let discriminator = oneof_discriminator(ctx, file, message_name, &oneof)?;
+                enums.push(generate_tagged_enum(
+                    &enum_name,
+                    &oneof,
+                    message,
+                    eq,
+                    discriminator.as_ref(),
+                ));
+                generate_tagged_fields(&oneof, discriminator.as_ref())
+            }
         };
         fields.extend(oneof_fields);
     }
 
-    fields
+    Ok((fields, enums))
+}
+
+/// Resolve the `discriminator_enum` of a tagged oneof, if any
+///
+/// The enum must carry `(seaorm.enum_opt)` and have a variant named after each oneof
+/// field (`card` needs `Card`); extra variants are allowed.
+fn oneof_discriminator(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    message_name: &str,
+    oneof: &OneofInfo,
+) -> Result<Option<Discriminator>, GeneratorError> {
+    if oneof.discriminator_enum.is_empty() {
+        return Ok(None);
+    }
+
+    let resolved = ctx
+        .resolver
+        .resolve_enum(&oneof.discriminator_enum, file.package.as_deref())
+        .ok_or_else(|| {
+            GeneratorError::InvalidConfig(format!(
+                "{}.{} has discriminator_enum {}, which could not be resolved",
+                message_name, oneof.name, oneof.discriminator_enum
+            ))
+        })?;
+    let proto_name = resolved.enum_desc.name.as_deref().unwrap_or("");
+    let options = resolve_enum_options(resolved.file, resolved.enum_desc)
+        .filter(|o| !o.skip)
+        .ok_or_else(|| {
+            GeneratorError::InvalidConfig(format!(
+                "{}.{} has discriminator_enum {}, which needs (seaorm.enum_opt)",
+                message_name, oneof.name, proto_name
+            ))
+        })?;
+
+    let variants: Vec<String> = resolved
+        .enum_desc
+        .value
+        .iter()
+        .filter_map(|value| variant_name(resolved.file, resolved.enum_desc, value))
+        .collect();
+    let missing: Vec<String> = oneof
+        .fields
+        .iter()
+        .map(|f| f.name.to_upper_camel_case())
+        .filter(|variant| !variants.contains(variant))
+        .collect();
+    if !missing.is_empty() {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} has discriminator_enum {} without variants {} for its fields",
+            message_name,
+            oneof.name,
+            proto_name,
+            missing.join(", ")
+        )));
+    }
+
+    Ok(Some(Discriminator {
+        rust_name: rust_enum_name(proto_name, &options),
+        exhaustive: variants.len() == oneof.fields.len(),
+    }))
 }
 
 /// Check if a field is a relation field
@@ -278,6 +3053,91 @@ fn is_relation_field(options: &Option<seaorm::FieldOptions>) -> bool {
     }
 }
 
+/// The synthetic map entry message of a `map<K, V>` field, nested in its message
+fn map_entry_message<'a>(
+    message: &'a DescriptorProto,
+    field: &FieldDescriptorProto,
+) -> Option<&'a DescriptorProto> {
+    if field.r#type() != Type::Message || field.label() != Label::Repeated {
+        return None;
+    }
+    let entry_name = field.type_name.as_deref()?.rsplit('.').next()?;
+    message
+        .nested_type
+        .iter()
+        .find(|nested| nested.name.as_deref() == Some(entry_name))
+}
+
+/// Whether a field is a singular, non-well-known message field that sets neither
+/// `embed` nor a relation
+fn is_unannotated_message_field(
+    field: &FieldDescriptorProto,
+    options: &Option<seaorm::FieldOptions>,
+) -> bool {
+    field.r#type() == Type::Message
+        && field.label() != Label::Repeated
+        && !field
+            .type_name
+            .as_deref()
+            .is_some_and(is_well_known_message)
+        && !options.as_ref().is_some_and(|o| o.embed)
+        && !is_relation_field(options)
+}
+
+/// `has_one` relations for unannotated message fields under
+/// `default_message_handling=relation`
+///
+/// Each relation is named after its field and targets the message's entity; fields
+/// already covered by a declared relation of the same name are left to it.
+fn default_message_relations(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    message: &DescriptorProto,
+    declared: &[RelationDef],
+) -> Result<Vec<RelationDef>, GeneratorError> {
+    if ctx.config.default_message_handling != MessageHandling::Relation {
+        return Ok(Vec::new());
+    }
+
+    let file_name = file.name.as_deref().unwrap_or("");
+    let msg_name = message.name.as_deref().unwrap_or("");
+    let mut relations = Vec::new();
+
+    for field in &message.field {
+        let field_name = field.name.as_deref().unwrap_or("");
+        let field_options =
+            get_cached_field_options(file_name, msg_name, field.number.unwrap_or(0))
+                .or_else(|| parse_field_options(field));
+        if !is_unannotated_message_field(field, &field_options)
+            || is_oneof_field(field, message)
+            || declared.iter().any(|r| r.name == field_name)
+        {
+            continue;
+        }
+
+        let type_name = field.type_name.as_deref().unwrap_or("");
+        let target = ctx
+            .resolver
+            .resolve_message(type_name, file.package.as_deref())
+            .filter(|target| entity_table_name(target.file, target.message).is_some())
+            .ok_or_else(|| {
+                GeneratorError::InvalidConfig(format!(
+                    "{}.{} can't become a relation: {} is not an entity",
+                    msg_name, field_name, type_name
+                ))
+            })?;
+
+        relations.push(RelationDef {
+            name: field_name.to_string(),
+            r#type: RelationType::HasOne as i32,
+            related: target.message.name.clone().unwrap_or_default(),
+            ..Default::default()
+        });
+    }
+
+    Ok(relations)
+}
+
 /// Determine if a field should be nullable
 fn is_field_nullable(field: &FieldDescriptorProto, options: &Option<seaorm::FieldOptions>) -> bool {
     // Check explicit nullable option
@@ -298,53 +3158,55 @@ fn is_field_nullable(field: &FieldDescriptorProto, options: &Option<seaorm::Fiel
     field.proto3_optional.unwrap_or(false)
 }
 
-/// Generate SeaORM attributes for a field
-fn generate_field_attributes(
-    _field_name: &str,
-    options: &Option<seaorm::FieldOptions>,
-    _mapped: &crate::types::MappedType,
-) -> Vec<String> {
-    let mut attrs = Vec::new();
-
-    if let Some(ref opts) = options {
-        if opts.primary_key {
-            if opts.auto_increment {
-                attrs.push("primary_key".to_string());
-            } else {
-                attrs.push("primary_key, auto_increment = false".to_string());
-            }
-        }
-
-        if opts.unique {
-            attrs.push("unique".to_string());
-        }
-
-        if !opts.column_name.is_empty() {
-            attrs.push(format!("column_name = \"{}\"", opts.column_name));
-        }
-
-        // Handle column_type - embed implies JsonBinary if not explicitly set
-        if !opts.column_type.is_empty() {
-            // Map common type names to SeaORM 2.0 ColumnType variants
-            let column_type = match opts.column_type.as_str() {
-                "JsonB" | "Jsonb" | "jsonb" => "JsonBinary",
-                other => other,
+/// The column default a proto2 `[default = ...]` stands for, unless a seaorm default
+/// is configured
+///
+/// Enum defaults name a value, which becomes that value's database value. Bytes
+/// defaults arrive C-escaped and have no portable column literal, so they're rejected.
+fn proto_column_default(
+    msg_name: &str,
+    field: &FieldDescriptorProto,
+    field_options: Option<&seaorm::FieldOptions>,
+    stored_enum: Option<(&FileDescriptorProto, &EnumDescriptorProto, DbType)>,
+) -> Result<Option<String>, GeneratorError> {
+    let has_seaorm_default = field_options.is_some_and(|o| {
+        !o.default_value.is_empty() || !o.default_expr.is_empty() || !o.insert_default.is_empty()
+    });
+    let Some(default) = field
+        .default_value
+        .as_deref()
+        .filter(|_| !has_seaorm_default)
+    else {
+        return Ok(None);
+    };
+    let field_name = field.name.as_deref().unwrap_or("");
+    match field.r#type() {
+        Type::Bytes => Err(GeneratorError::InvalidConfig(format!(
+            "{}.{} has a proto default, which bytes columns don't support; use default_expr",
+            msg_name, field_name
+        ))),
+        Type::Enum => {
+            let Some((file, enum_desc, db_type)) = stored_enum else {
+                return Ok(None);
             };
-            attrs.push(format!("column_type = \"{}\"", column_type));
-        } else if opts.embed {
-            attrs.push("column_type = \"JsonBinary\"".to_string());
-        }
-
-        if !opts.default_value.is_empty() {
-            attrs.push(format!("default_value = \"{}\"", opts.default_value));
-        }
-
-        if !opts.default_expr.is_empty() {
-            attrs.push(format!("default_expr = \"{}\"", opts.default_expr));
+            let value = enum_desc
+                .value
+                .iter()
+                .find(|v| v.name.as_deref() == Some(default))
+                .filter(|v| variant_name(file, enum_desc, v).is_some())
+                .ok_or_else(|| {
+                    GeneratorError::InvalidConfig(format!(
+                        "{}.{} defaults to {}, which is not a stored value of its enum",
+                        msg_name, field_name, default
+                    ))
+                })?;
+            Ok(Some(match db_type {
+                DbType::String | DbType::Native => string_value(file, enum_desc, value),
+                DbType::Integer => int_value(file, enum_desc, value).to_string(),
+            }))
         }
+        _ => Ok(Some(default.to_string())),
     }
-
-    attrs
 }
 
 /// Map a SeaORM column type string to a Rust type
@@ -391,7 +3253,6 @@ fn extract_embedded_type_name(type_name: Option<&str>) -> String {
                 .trim_start_matches('.');
 
             // Convert to PascalCase for Rust convention
-            use heck::ToUpperCamelCase;
             type_part.to_upper_camel_case()
         }
         None => "serde_json::Value".to_string(), // Fallback for unknown types
@@ -3,12 +3,17 @@
 //! Generates SeaORM-compatible enum types from protobuf enum definitions.
 //! Supports both string and integer database representations.
 
-use crate::options::{parse_enum_options, parse_enum_value_options};
+use crate::codegen::{arbitrary_derive, non_exhaustive_attribute, specta_derive, Context};
+use crate::options::seaorm::{EnumOptions, EnumValueOptions};
+use crate::options::{
+    get_cached_enum_options, get_cached_enum_value_options, parse_enum_options,
+    parse_enum_value_options,
+};
 use crate::GeneratorError;
 use heck::{ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::TokenStream;
 use prost_types::compiler::code_generator_response::File;
-use prost_types::{EnumDescriptorProto, FileDescriptorProto};
+use prost_types::{EnumDescriptorProto, EnumValueDescriptorProto, FileDescriptorProto};
 use quote::{format_ident, quote};
 
 /// Database type for enum storage
@@ -19,12 +24,28 @@ pub enum DbType {
     String,
     /// Store as integer
     Integer,
+    /// Store as a native database enum type (Postgres `CREATE TYPE ... AS ENUM`)
+    Native,
+}
+
+/// How a generated enum is stored and what it derives
+#[derive(Debug, Clone, Copy, Default)]
+struct EnumSettings {
+    /// How the values are stored
+    db_type: DbType,
+    /// Whether the name is a `name` override, which is also recorded as the `enum_name`
+    custom_name: bool,
+    /// Swap `DeriveActiveEnum` for plain `Value` conversions
+    value_conversions: bool,
+    /// Derive `Default` with the zero-numbered value as the default
+    derive_default: bool,
 }
 
 /// Generate a SeaORM enum from a protobuf enum definition
 ///
 /// Returns None if the enum should be skipped
 pub fn generate(
+    ctx: &Context,
     file: &FileDescriptorProto,
     enum_desc: &EnumDescriptorProto,
 ) -> Result<Option<File>, GeneratorError> {
@@ -34,7 +55,7 @@ pub fn generate(
         .ok_or_else(|| GeneratorError::CodeGenError("Enum missing name".to_string()))?;
 
     // Parse seaorm options
-    let options = parse_enum_options(enum_desc);
+    let options = resolve_enum_options(file, enum_desc);
 
     // Check if explicitly skipped
     if let Some(ref opts) = options {
@@ -51,28 +72,43 @@ pub fn generate(
     let options = options.unwrap();
 
     // Determine the Rust enum name
-    let rust_enum_name = if options.name.is_empty() {
-        enum_name.to_upper_camel_case()
-    } else {
-        options.name.clone()
-    };
+    let rust_enum_name = rust_enum_name(enum_name, &options);
+    let custom_name = !options.name.is_empty();
 
     // Determine database type
-    let db_type = match options.db_type.as_str() {
-        "integer" | "int" => DbType::Integer,
-        _ => DbType::String,
-    };
+    let db_type = db_type_of(&options);
+
+    if options.value_conversions && db_type == DbType::Native {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "Enum {} sets value_conversions, which needs db_type \"string\" or \"integer\"",
+            rust_enum_name
+        )));
+    }
 
     // Generate the enum code
-    let enum_tokens = generate_enum_tokens(enum_desc, &rust_enum_name, db_type)?;
+    let settings = EnumSettings {
+        db_type,
+        custom_name,
+        value_conversions: options.value_conversions,
+        derive_default: options.derive_default.unwrap_or(true),
+    };
+    let enum_tokens = generate_enum_tokens(
+        file,
+        enum_desc,
+        &rust_enum_name,
+        settings,
+        &enum_attributes(ctx),
+    )?;
 
     // Format the code
     let code = format_code(enum_tokens)?;
 
     // Determine output file path
-    let proto_path = file.name.as_deref().unwrap_or("unknown.proto");
-    let output_path =
-        proto_path.replace(".proto", &format!("/{}.rs", rust_enum_name.to_snake_case()));
+    let output_path = format!(
+        "{}/{}.rs",
+        crate::generator::package_dir(&ctx.config, file.package.as_deref().unwrap_or("")),
+        rust_enum_name.to_snake_case()
+    );
 
     Ok(Some(File {
         name: Some(output_path),
@@ -81,103 +117,473 @@ pub fn generate(
     }))
 }
 
-/// Generate the TokenStream for a SeaORM enum
-fn generate_enum_tokens(
+/// Look up the seaorm options of an enum, preferring options cached from raw request bytes
+pub fn resolve_enum_options(
+    file: &FileDescriptorProto,
+    enum_desc: &EnumDescriptorProto,
+) -> Option<EnumOptions> {
+    let file_name = file.name.as_deref().unwrap_or("");
+    let enum_name = enum_desc.name.as_deref().unwrap_or("");
+    get_cached_enum_options(file_name, enum_name).or_else(|| parse_enum_options(enum_desc))
+}
+
+/// Look up the seaorm options of an enum value, preferring options cached from raw
+/// request bytes
+pub fn resolve_enum_value_options(
+    file: &FileDescriptorProto,
+    enum_desc: &EnumDescriptorProto,
+    value: &EnumValueDescriptorProto,
+) -> Option<EnumValueOptions> {
+    let file_name = file.name.as_deref().unwrap_or("");
+    let enum_name = enum_desc.name.as_deref().unwrap_or("");
+    get_cached_enum_value_options(file_name, enum_name, value.number.unwrap_or(0))
+        .or_else(|| parse_enum_value_options(value))
+}
+
+/// How an annotated enum is stored, from its `db_type` (strings unless stated otherwise)
+pub fn db_type_of(options: &EnumOptions) -> DbType {
+    match options.db_type.as_str() {
+        "integer" | "int" => DbType::Integer,
+        "native" => DbType::Native,
+        _ => DbType::String,
+    }
+}
+
+/// The Rust name of a generated enum: the `name` override, or the PascalCase proto name
+pub fn rust_enum_name(proto_name: &str, options: &EnumOptions) -> String {
+    if options.name.is_empty() {
+        proto_name.to_upper_camel_case()
+    } else {
+        options.name.clone()
+    }
+}
+
+/// Generate an active enum named `rust_enum_name`, stored per a field's `enum_as`
+///
+/// The enum is emitted inline in the entity file that uses it, for enums without
+/// `(seaorm.enum_opt)` (which get no standalone enum file) or stored differently from
+/// their standalone enum.
+pub fn generate_inline_enum(
+    ctx: &Context,
+    file: &FileDescriptorProto,
     enum_desc: &EnumDescriptorProto,
     rust_enum_name: &str,
     db_type: DbType,
 ) -> Result<TokenStream, GeneratorError> {
+    let derive_default = resolve_enum_options(file, enum_desc)
+        .and_then(|o| o.derive_default)
+        .unwrap_or(true);
+    let settings = EnumSettings {
+        db_type,
+        derive_default,
+        ..Default::default()
+    };
+    generate_enum_definition(
+        file,
+        enum_desc,
+        rust_enum_name,
+        settings,
+        &enum_attributes(ctx),
+    )
+}
+
+/// Extra attributes for generated enums, from the `non_exhaustive`, `derive_specta` and
+/// `derive_arbitrary` parameters
+fn enum_attributes(ctx: &Context) -> TokenStream {
+    let non_exhaustive = non_exhaustive_attribute(ctx.config.non_exhaustive);
+    let specta = specta_derive(ctx.config.derive_specta);
+    let arbitrary = arbitrary_derive(ctx.config.derive_arbitrary);
+    quote! {
+        #non_exhaustive
+        #specta
+        #arbitrary
+    }
+}
+
+/// Generate the TokenStream for a SeaORM enum file
+///
+/// `extra_attrs` are added to the enum after its derives.
+fn generate_enum_tokens(
+    file: &FileDescriptorProto,
+    enum_desc: &EnumDescriptorProto,
+    rust_enum_name: &str,
+    settings: EnumSettings,
+    extra_attrs: &TokenStream,
+) -> Result<TokenStream, GeneratorError> {
+    let definition =
+        generate_enum_definition(file, enum_desc, rust_enum_name, settings, extra_attrs)?;
+
+    Ok(quote! {
+        //! SeaORM enum generated from protobuf
+
+        use sea_orm::entity::prelude::*;
+
+        #definition
+    })
+}
+
+/// Generate a SeaORM enum and its value helpers, without any module items
+///
+/// With `derive_default`, the first zero-numbered value (the proto3 default) is marked
+/// `#[default]`; enums without one, or that skip it, don't derive `Default`.
+fn generate_enum_definition(
+    file: &FileDescriptorProto,
+    enum_desc: &EnumDescriptorProto,
+    rust_enum_name: &str,
+    settings: EnumSettings,
+    extra_attrs: &TokenStream,
+) -> Result<TokenStream, GeneratorError> {
+    let EnumSettings {
+        db_type,
+        custom_name,
+        value_conversions,
+        derive_default,
+    } = settings;
     let enum_ident = format_ident!("{}", rust_enum_name);
+    let type_name = rust_enum_name.to_snake_case();
 
-    // Generate variants
+    // Generate variants, remembering each variant's database value
     let mut variants = Vec::new();
+    let mut db_values = Vec::new();
+    let mut has_default = false;
     for value in &enum_desc.value {
-        let value_name = value
-            .name
-            .as_ref()
-            .ok_or_else(|| GeneratorError::CodeGenError("Enum value missing name".to_string()))?;
+        if value.name.is_none() {
+            return Err(GeneratorError::CodeGenError(
+                "Enum value missing name".to_string(),
+            ));
+        }
 
         let value_number = value.number.unwrap_or(0);
 
-        // Parse enum value options
-        let value_options = parse_enum_value_options(value);
-
-        // Determine variant name
-        let variant_name = if let Some(ref opts) = value_options {
-            if !opts.name.is_empty() {
-                opts.name.clone()
-            } else {
-                convert_enum_variant_name(value_name)
-            }
-        } else {
-            convert_enum_variant_name(value_name)
+        // Values that never round-trip through the database are left out entirely
+        let Some(variant_name) = variant_name(file, enum_desc, value) else {
+            continue;
         };
 
         let variant_ident = format_ident!("{}", variant_name);
 
         // Generate value attribute based on db_type
         let value_attr = match db_type {
-            DbType::String => {
-                // Determine string value
-                let string_val = if let Some(ref opts) = value_options {
-                    if !opts.string_value.is_empty() {
-                        opts.string_value.clone()
-                    } else {
-                        value_name.to_snake_case()
-                    }
-                } else {
-                    value_name.to_snake_case()
-                };
+            DbType::String | DbType::Native => {
+                let string_val = string_value(file, enum_desc, value);
+                db_values.push((variant_ident.clone(), quote! { #string_val }));
                 quote! { #[sea_orm(string_value = #string_val)] }
             }
             DbType::Integer => {
-                // Determine int value
-                let int_val = if let Some(ref opts) = value_options {
-                    if opts.int_value != 0 {
-                        opts.int_value
-                    } else {
-                        value_number
-                    }
-                } else {
-                    value_number
-                };
+                let int_val = int_value(file, enum_desc, value);
+                let int_lit = proc_macro2::Literal::i32_unsuffixed(int_val);
+                db_values.push((variant_ident.clone(), quote! { #int_lit }));
                 quote! { #[sea_orm(num_value = #int_val)] }
             }
         };
 
+        // Without DeriveActiveEnum there is nothing to read the value attributes
+        let value_attr = if value_conversions {
+            quote! {}
+        } else {
+            value_attr
+        };
+        let default_attr = if derive_default && value_number == 0 && !has_default {
+            has_default = true;
+            quote! { #[default] }
+        } else {
+            quote! {}
+        };
         variants.push(quote! {
+            #default_attr
             #value_attr
             #variant_ident
         });
     }
 
+    if variants.is_empty() {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "Enum {} skips every value; at least one variant must remain",
+            rust_enum_name
+        )));
+    }
+
+    // Raw database value helpers for use in hand-written SeaQuery filters
+    let value_type = match db_type {
+        DbType::String | DbType::Native => quote! { &'static str },
+        DbType::Integer => quote! { i32 },
+    };
+    let value_arms = db_values
+        .iter()
+        .map(|(variant, value)| quote! { Self::#variant => #value });
+    let value_list = db_values.iter().map(|(_, value)| value);
+
     // Generate type attributes based on db_type
-    let type_attrs = match db_type {
-        DbType::String => {
+    let type_attrs = match (db_type, custom_name) {
+        (DbType::String, false) => {
             quote! {
                 #[sea_orm(rs_type = "String", db_type = "String(StringLen::N(64))")]
             }
         }
-        DbType::Integer => {
+        (DbType::String, true) => {
+            quote! {
+                #[sea_orm(rs_type = "String", db_type = "String(StringLen::N(64))", enum_name = #type_name)]
+            }
+        }
+        (DbType::Integer, false) => {
             quote! {
                 #[sea_orm(rs_type = "i32", db_type = "Integer")]
             }
         }
+        (DbType::Integer, true) => {
+            quote! {
+                #[sea_orm(rs_type = "i32", db_type = "Integer", enum_name = #type_name)]
+            }
+        }
+        (DbType::Native, _) => {
+            quote! {
+                #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = #type_name)]
+            }
+        }
     };
 
-    Ok(quote! {
-        //! SeaORM enum generated from protobuf
+    // Native enums need their type created in migrations before any column uses it
+    let migration_items = if db_type == DbType::Native {
+        quote! {
+            /// Name of the native enum type in the database
+            pub const TYPE_NAME: &'static str = #type_name;
 
-        use sea_orm::entity::prelude::*;
+            /// Iden of the native enum type, for `Type::create().as_enum(...)` in migrations
+            pub fn type_iden() -> sea_orm::sea_query::Alias {
+                sea_orm::sea_query::Alias::new(Self::TYPE_NAME)
+            }
+
+            /// Idens of every variant, for `Type::create().values(...)` in migrations
+            pub fn variant_idens() -> Vec<sea_orm::sea_query::Alias> {
+                Self::VALUES
+                    .iter()
+                    .map(|value| sea_orm::sea_query::Alias::new(*value))
+                    .collect()
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let default_derive = if has_default {
+        quote! { Default, }
+    } else {
+        quote! {}
+    };
+    let (derives, type_attrs, conversions) = if value_conversions {
+        (
+            quote! { #[derive(Clone, Copy, Debug, PartialEq, Eq, #default_derive EnumIter)] },
+            quote! {},
+            generate_value_conversions(&enum_ident, rust_enum_name, db_type, &db_values),
+        )
+    } else {
+        (
+            quote! { #[derive(Clone, Copy, Debug, PartialEq, Eq, #default_derive EnumIter, DeriveActiveEnum)] },
+            type_attrs,
+            quote! {},
+        )
+    };
 
-        #[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+    Ok(quote! {
+        #derives
         #type_attrs
+        #extra_attrs
         pub enum #enum_ident {
             #(#variants),*
         }
+
+        impl #enum_ident {
+            /// Database values of all variants, in declaration order
+            pub const VALUES: &'static [#value_type] = &[#(#value_list),*];
+
+            /// The value stored in the database for this variant
+            pub fn db_value(&self) -> #value_type {
+                match self {
+                    #(#value_arms),*
+                }
+            }
+
+            #migration_items
+        }
+
+        #conversions
     })
 }
 
+/// `Value` conversions for an enum stored as its database value in an ordinary column
+///
+/// Stands in for the impls `DeriveActiveEnum` would provide: `From<Enum> for Value`,
+/// `TryGetable`, `ValueType` and `Nullable`, plus a `from_db_value` lookup. Reading an
+/// unknown database value fails with a type error.
+fn generate_value_conversions(
+    enum_ident: &proc_macro2::Ident,
+    rust_enum_name: &str,
+    db_type: DbType,
+    db_values: &[(proc_macro2::Ident, TokenStream)],
+) -> TokenStream {
+    let (inner_type, column_type, lookup) = match db_type {
+        DbType::Integer => (
+            quote! { i32 },
+            quote! { sea_orm::sea_query::ColumnType::Integer },
+            quote! { value },
+        ),
+        DbType::String | DbType::Native => (
+            quote! { String },
+            quote! {
+                sea_orm::sea_query::ColumnType::String(sea_orm::sea_query::StringLen::N(64))
+            },
+            quote! { value.as_str() },
+        ),
+    };
+    let from_arms = db_values
+        .iter()
+        .map(|(variant, value)| quote! { #value => Some(Self::#variant) });
+
+    quote! {
+        impl #enum_ident {
+            /// The variant stored as `value` in the database, if any
+            pub fn from_db_value(value: #inner_type) -> Option<Self> {
+                match #lookup {
+                    #(#from_arms,)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl From<#enum_ident> for sea_orm::Value {
+            fn from(value: #enum_ident) -> Self {
+                value.db_value().into()
+            }
+        }
+
+        impl sea_orm::TryGetable for #enum_ident {
+            fn try_get_by<I: sea_orm::ColIdx>(
+                res: &sea_orm::QueryResult,
+                index: I,
+            ) -> Result<Self, sea_orm::TryGetError> {
+                let value = <#inner_type as sea_orm::TryGetable>::try_get_by(res, index)?;
+                Self::from_db_value(value.clone()).ok_or_else(|| {
+                    sea_orm::TryGetError::DbErr(sea_orm::DbErr::Type(format!(
+                        "{:?} is not a {} value",
+                        value, #rust_enum_name
+                    )))
+                })
+            }
+        }
+
+        impl sea_orm::sea_query::ValueType for #enum_ident {
+            fn try_from(v: sea_orm::Value) -> Result<Self, sea_orm::sea_query::ValueTypeErr> {
+                <#inner_type as sea_orm::sea_query::ValueType>::try_from(v)
+                    .ok()
+                    .and_then(Self::from_db_value)
+                    .ok_or(sea_orm::sea_query::ValueTypeErr)
+            }
+
+            fn type_name() -> String {
+                #rust_enum_name.to_string()
+            }
+
+            fn array_type() -> sea_orm::sea_query::ArrayType {
+                <#inner_type as sea_orm::sea_query::ValueType>::array_type()
+            }
+
+            fn column_type() -> sea_orm::sea_query::ColumnType {
+                #column_type
+            }
+        }
+
+        impl sea_orm::sea_query::Nullable for #enum_ident {
+            fn null() -> sea_orm::Value {
+                <#inner_type as sea_orm::sea_query::Nullable>::null()
+            }
+        }
+    }
+}
+
+/// Rust variant name of an enum value, or None for values with `skip`
+pub fn variant_name(
+    file: &FileDescriptorProto,
+    enum_desc: &EnumDescriptorProto,
+    value: &EnumValueDescriptorProto,
+) -> Option<String> {
+    let value_name = value.name.as_deref().unwrap_or("");
+    match resolve_enum_value_options(file, enum_desc, value) {
+        Some(opts) if opts.skip => None,
+        Some(opts) if !opts.name.is_empty() => Some(opts.name),
+        _ => Some(convert_enum_variant_name(value_name)),
+    }
+}
+
+/// The variants of a generated enum, for mapping stored values back to Rust
+#[derive(Debug, Clone)]
+pub struct StoredVariants {
+    /// Rust name of the enum
+    pub rust_name: String,
+    /// Each stored database value with the variant it maps to
+    pub variants: Vec<(String, String)>,
+    /// The `#[default]` variant, if the enum derives `Default`
+    pub default: Option<String>,
+}
+
+/// The variants of `enum_desc` generated as `rust_name`, keyed by their value stored
+/// per `db_type`
+pub fn stored_variants(
+    file: &FileDescriptorProto,
+    enum_desc: &EnumDescriptorProto,
+    rust_name: &str,
+    db_type: DbType,
+) -> StoredVariants {
+    let derive_default = resolve_enum_options(file, enum_desc)
+        .and_then(|o| o.derive_default)
+        .unwrap_or(true);
+    let mut variants = Vec::new();
+    let mut default = None;
+    for value in &enum_desc.value {
+        let Some(variant) = variant_name(file, enum_desc, value) else {
+            continue;
+        };
+        if derive_default && default.is_none() && value.number.unwrap_or(0) == 0 {
+            default = Some(variant.clone());
+        }
+        let stored = match db_type {
+            DbType::String | DbType::Native => string_value(file, enum_desc, value),
+            DbType::Integer => int_value(file, enum_desc, value).to_string(),
+        };
+        variants.push((stored, variant));
+    }
+    StoredVariants {
+        rust_name: rust_name.to_string(),
+        variants,
+        default,
+    }
+}
+
+/// Database value of an enum value stored as a string: its `string_value`, or the
+/// snake_case proto name
+pub fn string_value(
+    file: &FileDescriptorProto,
+    enum_desc: &EnumDescriptorProto,
+    value: &EnumValueDescriptorProto,
+) -> String {
+    match resolve_enum_value_options(file, enum_desc, value) {
+        Some(opts) if !opts.string_value.is_empty() => opts.string_value,
+        _ => value.name.as_deref().unwrap_or("").to_snake_case(),
+    }
+}
+
+/// Database value of an enum value stored as an integer: its `int_value`, or the
+/// proto number
+pub fn int_value(
+    file: &FileDescriptorProto,
+    enum_desc: &EnumDescriptorProto,
+    value: &EnumValueDescriptorProto,
+) -> i32 {
+    match resolve_enum_value_options(file, enum_desc, value) {
+        Some(opts) if opts.int_value != 0 => opts.int_value,
+        _ => value.number.unwrap_or(0),
+    }
+}
+
 /// Convert a protobuf enum value name to a Rust variant name
 ///
 /// Protobuf convention is SCREAMING_SNAKE_CASE (e.g., STATUS_ACTIVE)
@@ -200,7 +606,7 @@ fn format_code(tokens: TokenStream) -> Result<String, GeneratorError> {
 mod tests {
     use super::*;
     use prost_types::uninterpreted_option::NamePart;
-    use prost_types::{EnumOptions, EnumValueDescriptorProto, UninterpretedOption};
+    use prost_types::{EnumOptions, UninterpretedOption};
 
     fn create_test_enum() -> EnumDescriptorProto {
         // Create seaorm.enum_opt option
@@ -240,10 +646,26 @@ mod tests {
         }
     }
 
+    /// Settings for a plain enum stored as `db_type`
+    fn settings(db_type: DbType) -> EnumSettings {
+        EnumSettings {
+            db_type,
+            derive_default: true,
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_generate_enum_tokens_string() {
         let enum_desc = create_test_enum();
-        let tokens = generate_enum_tokens(&enum_desc, "Status", DbType::String).unwrap();
+        let tokens = generate_enum_tokens(
+            &FileDescriptorProto::default(),
+            &enum_desc,
+            "Status",
+            settings(DbType::String),
+            &TokenStream::new(),
+        )
+        .unwrap();
         let code = tokens.to_string();
 
         assert!(code.contains("DeriveActiveEnum"));
@@ -254,7 +676,14 @@ mod tests {
     #[test]
     fn test_generate_enum_tokens_integer() {
         let enum_desc = create_test_enum();
-        let tokens = generate_enum_tokens(&enum_desc, "Status", DbType::Integer).unwrap();
+        let tokens = generate_enum_tokens(
+            &FileDescriptorProto::default(),
+            &enum_desc,
+            "Status",
+            settings(DbType::Integer),
+            &TokenStream::new(),
+        )
+        .unwrap();
         let code = tokens.to_string();
 
         assert!(code.contains("DeriveActiveEnum"));
@@ -262,6 +691,147 @@ mod tests {
         assert!(code.contains("num_value"));
     }
 
+    #[test]
+    fn test_generate_db_value_helpers() {
+        let enum_desc = create_test_enum();
+
+        let tokens = generate_enum_tokens(
+            &FileDescriptorProto::default(),
+            &enum_desc,
+            "Status",
+            settings(DbType::String),
+            &TokenStream::new(),
+        )
+        .unwrap();
+        let code = format_code(tokens).unwrap();
+        assert!(code.contains("pub const VALUES: &'static [&'static str] = &[\n"));
+        assert!(code.contains("\"status_unknown\",\n        \"status_active\","));
+        assert!(code.contains("pub fn db_value(&self) -> &'static str"));
+        assert!(code.contains("Self::StatusActive => \"status_active\""));
+
+        let tokens = generate_enum_tokens(
+            &FileDescriptorProto::default(),
+            &enum_desc,
+            "Status",
+            settings(DbType::Integer),
+            &TokenStream::new(),
+        )
+        .unwrap();
+        let code = format_code(tokens).unwrap();
+        assert!(code.contains("pub const VALUES: &'static [i32] = &[0, 1, 2];"));
+        assert!(code.contains("pub fn db_value(&self) -> i32"));
+        assert!(code.contains("Self::StatusInactive => 2"));
+    }
+
+    #[test]
+    fn test_generate_native_enum_migration_idens() {
+        let enum_desc = create_test_enum();
+        let tokens = generate_enum_tokens(
+            &FileDescriptorProto::default(),
+            &enum_desc,
+            "Status",
+            settings(DbType::Native),
+            &TokenStream::new(),
+        )
+        .unwrap();
+        let code = format_code(tokens).unwrap();
+
+        assert!(code.contains(
+            "#[sea_orm(rs_type = \"String\", db_type = \"Enum\", enum_name = \"status\")]"
+        ));
+        assert!(code.contains("#[sea_orm(string_value = \"status_active\")]"));
+        assert!(code.contains("pub const TYPE_NAME: &'static str = \"status\";"));
+        assert!(code.contains("pub fn type_iden() -> sea_orm::sea_query::Alias"));
+        assert!(code.contains("pub fn variant_idens() -> Vec<sea_orm::sea_query::Alias>"));
+        assert!(code.contains(
+            "\"status_unknown\",\n        \"status_active\",\n        \"status_inactive\","
+        ));
+
+        // Non-native enums have no database type to create
+        let tokens = generate_enum_tokens(
+            &FileDescriptorProto::default(),
+            &enum_desc,
+            "Status",
+            settings(DbType::String),
+            &TokenStream::new(),
+        )
+        .unwrap();
+        assert!(!format_code(tokens).unwrap().contains("type_iden"));
+    }
+
+    #[test]
+    fn test_skipped_enum_value_is_omitted() {
+        let skip = |value: &mut EnumValueDescriptorProto| {
+            value.options = Some(prost_types::EnumValueOptions {
+                uninterpreted_option: vec![UninterpretedOption {
+                    name: vec![NamePart {
+                        name_part: "seaorm.enum_value".to_string(),
+                        is_extension: true,
+                    }],
+                    aggregate_value: Some("skip: true".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            });
+        };
+
+        let mut enum_desc = create_test_enum();
+        skip(&mut enum_desc.value[2]);
+        let tokens = generate_enum_tokens(
+            &FileDescriptorProto::default(),
+            &enum_desc,
+            "Status",
+            settings(DbType::String),
+            &TokenStream::new(),
+        )
+        .unwrap();
+        let code = format_code(tokens).unwrap();
+        assert!(code.contains("StatusActive"));
+        assert!(!code.contains("StatusInactive"));
+        assert!(!code.contains("status_inactive"));
+
+        // Skipping every value would leave an empty, unusable enum
+        for value in &mut enum_desc.value {
+            skip(value);
+        }
+        assert!(matches!(
+            generate_enum_tokens(
+                &FileDescriptorProto::default(),
+                &enum_desc,
+                "Status",
+                settings(DbType::String),
+                &TokenStream::new()
+            ),
+            Err(GeneratorError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_generate_value_conversions() {
+        let enum_desc = create_test_enum();
+        let tokens = generate_enum_tokens(
+            &FileDescriptorProto::default(),
+            &enum_desc,
+            "Status",
+            EnumSettings {
+                value_conversions: true,
+                ..settings(DbType::Integer)
+            },
+            &TokenStream::new(),
+        )
+        .unwrap();
+        let code = format_code(tokens).unwrap();
+
+        assert!(!code.contains("DeriveActiveEnum"));
+        assert!(!code.contains("num_value"));
+        assert!(code.contains("pub fn from_db_value(value: i32) -> Option<Self>"));
+        assert!(code.contains("impl From<Status> for sea_orm::Value"));
+        assert!(code.contains("impl sea_orm::TryGetable for Status"));
+        assert!(code.contains("<i32 as sea_orm::TryGetable>::try_get_by(res, index)?"));
+        assert!(code.contains("impl sea_orm::sea_query::ValueType for Status"));
+        assert!(code.contains("impl sea_orm::sea_query::Nullable for Status"));
+    }
+
     #[test]
     fn test_convert_enum_variant_name() {
         assert_eq!(convert_enum_variant_name("STATUS_ACTIVE"), "StatusActive");
@@ -0,0 +1,608 @@
+//! Explicit imports for generated modules
+//!
+//! Generated code normally glob-imports `super::prelude` and SeaORM's entity prelude.
+//! With the `no_prelude_glob` parameter each glob is replaced by the names the module
+//! actually refers to: names SeaORM's prelude provides (plus the traits needed for the
+//! methods it calls) come from SeaORM, and the package's own types (its messages, enums
+//! and the types named in options) come from `super::prelude`.
+//!
+//! With the `no_prelude` parameter SeaORM's prelude glob is dropped altogether and every
+//! name it provided is written as a full `sea_orm::entity::prelude::` path instead.
+
+use proc_macro2::{Group, TokenStream, TokenTree};
+use quote::ToTokens;
+use std::collections::BTreeSet;
+
+/// Names exported by `sea_orm::entity::prelude`
+///
+/// Kept complete rather than limited to the names generated code uses today, so a
+/// SeaORM name added to a generator is never mistaken for one from `super::prelude`.
+const SEA_ORM_PRELUDE: &[&str] = &[
+    "ActiveEnum",
+    "ActiveModelBehavior",
+    "ActiveModelTrait",
+    "ActiveValue",
+    "BigDecimal",
+    "ColumnDef",
+    "ColumnTrait",
+    "ColumnType",
+    "ColumnTypeTrait",
+    "ConnectionTrait",
+    "CursorTrait",
+    "DatabaseConnection",
+    "Date",
+    "DateTime",
+    "DateTimeLocal",
+    "DateTimeUtc",
+    "DateTimeWithTimeZone",
+    "DbConn",
+    "DbErr",
+    "Decimal",
+    "DeriveActiveEnum",
+    "DeriveActiveModel",
+    "DeriveActiveModelBehavior",
+    "DeriveColumn",
+    "DeriveCustomColumn",
+    "DeriveDisplay",
+    "DeriveEntity",
+    "DeriveEntityModel",
+    "DeriveIden",
+    "DeriveIntoActiveModel",
+    "DeriveModel",
+    "DerivePartialModel",
+    "DerivePrimaryKey",
+    "DeriveRelatedEntity",
+    "DeriveRelation",
+    "DeriveValueType",
+    "EntityName",
+    "EntityTrait",
+    "EnumIter",
+    "ForeignKeyAction",
+    "FromJsonQueryResult",
+    "HasMany",
+    "HasOne",
+    "Iden",
+    "IdenStatic",
+    "Json",
+    "Linked",
+    "LoaderTrait",
+    "ModelTrait",
+    "PaginatorTrait",
+    "PrimaryKeyArity",
+    "PrimaryKeyToColumn",
+    "PrimaryKeyTrait",
+    "QueryFilter",
+    "QueryResult",
+    "Related",
+    "RelationDef",
+    "RelationTrait",
+    "RuntimeErr",
+    "Select",
+    "StringLen",
+    "Time",
+    "Uuid",
+    "Value",
+];
+
+/// SeaORM prelude traits providing the methods and associated functions generated code calls
+const SEA_ORM_METHOD_TRAITS: &[(&str, &[&str])] = &[
+    ("eq", &["ColumnTrait"]),
+    ("ne", &["ColumnTrait"]),
+    ("gt", &["ColumnTrait"]),
+    ("gte", &["ColumnTrait"]),
+    ("lt", &["ColumnTrait"]),
+    ("lte", &["ColumnTrait"]),
+    ("contains", &["ColumnTrait"]),
+    ("starts_with", &["ColumnTrait"]),
+    ("is_in", &["ColumnTrait"]),
+    ("is_null", &["ColumnTrait"]),
+    ("filter", &["QueryFilter"]),
+    ("find", &["EntityTrait"]),
+    ("find_by_id", &["EntityTrait"]),
+    ("update", &["EntityTrait"]),
+    ("update_many", &["EntityTrait"]),
+    ("delete_by_id", &["EntityTrait"]),
+    ("insert", &["EntityTrait", "ActiveModelTrait"]),
+    ("save", &["ActiveModelTrait"]),
+    ("find_related", &["ModelTrait"]),
+    ("paginate", &["PaginatorTrait"]),
+    ("def", &["ColumnTrait", "ColumnTypeTrait", "RelationTrait"]),
+];
+
+/// Names available without any import
+const STD_PRELUDE: &[&str] = &[
+    "AsMut",
+    "AsRef",
+    "Box",
+    "Clone",
+    "Copy",
+    "Debug",
+    "Default",
+    "Eq",
+    "Err",
+    "From",
+    "Hash",
+    "Into",
+    "Iterator",
+    "None",
+    "Ok",
+    "Option",
+    "Ord",
+    "PartialEq",
+    "PartialOrd",
+    "Result",
+    "Self",
+    "Send",
+    "Some",
+    "String",
+    "Sync",
+    "ToString",
+    "TryFrom",
+    "TryInto",
+    "Vec",
+];
+
+/// Keywords introducing a declaration; the identifier after one names the new item
+/// rather than referring to a name in scope (`type EntityName = Entity;`)
+const DECLARATION_KEYWORDS: &[&str] = &[
+    "const", "enum", "fn", "mod", "static", "struct", "trait", "type",
+];
+
+/// Items `#[sea_orm::model]` / `DeriveEntityModel` define next to the model struct
+const ENTITY_MODEL_ITEMS: &[&str] = &[
+    "ActiveModel",
+    "ActiveModelEx",
+    "Column",
+    "Entity",
+    "Model",
+    "ModelEx",
+    "PrimaryKey",
+    "Relation",
+];
+
+/// Replace glob imports in a generated module with explicit named imports
+///
+/// `prelude_names` are the names `super::prelude` provides. Content that doesn't parse
+/// is returned unchanged.
+pub fn expand_glob_imports(content: &str, prelude_names: &BTreeSet<String>) -> String {
+    let Ok(mut file) = syn::parse_file(content) else {
+        return content.to_string();
+    };
+
+    let usage = Usage::of(&file);
+
+    let mut items = Vec::with_capacity(file.items.len());
+    for item in std::mem::take(&mut file.items) {
+        let syn::Item::Use(item_use) = item else {
+            items.push(item);
+            continue;
+        };
+        let Some(prefix) = glob_prefix(&item_use.tree) else {
+            items.push(syn::Item::Use(item_use));
+            continue;
+        };
+
+        let names = match prefix.as_str() {
+            "sea_orm::entity::prelude" | "sea_orm::prelude" => usage.sea_orm_names(),
+            _ => usage.prelude_names(prelude_names),
+        };
+        if names.is_empty() {
+            continue;
+        }
+
+        let path: syn::Path = syn::parse_str(&prefix).expect("glob prefix is a valid path");
+        let mut names: Vec<_> = names
+            .iter()
+            .map(|n| quote::format_ident!("{}", n))
+            .collect();
+        items.push(if names.len() == 1 {
+            let name = names.remove(0);
+            syn::parse_quote! {
+                #[allow(unused_imports)]
+                use #path::#name;
+            }
+        } else {
+            syn::parse_quote! {
+                #[allow(unused_imports)]
+                use #path::{#(#names),*};
+            }
+        });
+    }
+    file.items = items;
+
+    prettyplease::unparse(&file)
+}
+
+/// Replace SeaORM's prelude glob with fully-qualified paths at each use site
+///
+/// Traits whose methods the module calls can't be named at the call site, so they are
+/// imported anonymously (`use sea_orm::entity::prelude::EntityTrait as _;`), which
+/// brings their methods into scope without binding any names. Modules without the glob
+/// and content that doesn't parse are returned unchanged.
+pub fn qualify_sea_orm_paths(content: &str) -> String {
+    let Ok(mut file) = syn::parse_file(content) else {
+        return content.to_string();
+    };
+
+    let is_sea_orm_glob = |item: &syn::Item| match item {
+        syn::Item::Use(item_use) => matches!(
+            glob_prefix(&item_use.tree).as_deref(),
+            Some("sea_orm::entity::prelude" | "sea_orm::prelude")
+        ),
+        _ => false,
+    };
+    if !file.items.iter().any(is_sea_orm_glob) {
+        return content.to_string();
+    }
+
+    let usage = Usage::of(&file);
+    let mut traits: BTreeSet<&str> = BTreeSet::new();
+    for (method, method_traits) in SEA_ORM_METHOD_TRAITS {
+        if usage.members.contains(*method) {
+            traits.extend(method_traits.iter().copied());
+        }
+    }
+
+    let mut items: Vec<syn::Item> = traits
+        .into_iter()
+        .map(|name| {
+            let name = quote::format_ident!("{}", name);
+            syn::parse_quote! {
+                #[allow(unused_imports)]
+                use sea_orm::entity::prelude::#name as _;
+            }
+        })
+        .collect();
+    for item in std::mem::take(&mut file.items) {
+        if is_sea_orm_glob(&item) {
+            continue;
+        }
+        if matches!(item, syn::Item::Use(_)) {
+            items.push(item);
+            continue;
+        }
+        let qualified = usage.qualify(item.to_token_stream());
+        items.push(syn::parse2(qualified).expect("qualified paths keep the item valid"));
+    }
+    file.items = items;
+
+    prettyplease::unparse(&file)
+}
+
+/// The path a glob import reads from, e.g. `super::prelude` for `use super::prelude::*;`
+fn glob_prefix(tree: &syn::UseTree) -> Option<String> {
+    match tree {
+        syn::UseTree::Path(path) => match glob_prefix(&path.tree)? {
+            rest if rest.is_empty() => Some(path.ident.to_string()),
+            rest => Some(format!("{}::{}", path.ident, rest)),
+        },
+        syn::UseTree::Glob(_) => Some(String::new()),
+        _ => None,
+    }
+}
+
+/// Names a generated module refers to and names it defines itself
+#[derive(Default)]
+struct Usage {
+    /// Identifiers used at the start of a path or as a bare name
+    referenced: BTreeSet<String>,
+    /// Identifiers following `.` or `::`: methods, associated items and path segments
+    members: BTreeSet<String>,
+    /// Items, variants, associated types, generic parameters and non-glob imports
+    /// declared in the module
+    defined: BTreeSet<String>,
+}
+
+impl Usage {
+    fn of(file: &syn::File) -> Self {
+        let mut usage = Usage::default();
+
+        for item in &file.items {
+            usage.define_item(item);
+            if !matches!(item, syn::Item::Use(_)) {
+                usage.walk(item.to_token_stream());
+            }
+        }
+
+        if usage.referenced.contains("DeriveEntityModel") {
+            usage
+                .defined
+                .extend(ENTITY_MODEL_ITEMS.iter().map(|n| n.to_string()));
+        }
+
+        usage
+    }
+
+    /// SeaORM prelude names the module needs
+    fn sea_orm_names(&self) -> BTreeSet<&'static str> {
+        let mut names: BTreeSet<&'static str> = SEA_ORM_PRELUDE
+            .iter()
+            .copied()
+            .filter(|n| self.referenced.contains(*n) && !self.defined.contains(*n))
+            .collect();
+        for (method, traits) in SEA_ORM_METHOD_TRAITS {
+            if self.members.contains(*method) {
+                names.extend(traits.iter().copied());
+            }
+        }
+        names
+    }
+
+    /// Names the module takes from `super::prelude`, out of those it provides
+    fn prelude_names<'a>(&self, provided: &'a BTreeSet<String>) -> BTreeSet<&'a str> {
+        provided
+            .iter()
+            .map(String::as_str)
+            .filter(|n| self.referenced.contains(*n) && !self.defined.contains(*n))
+            .filter(|n| !SEA_ORM_PRELUDE.contains(n) && !STD_PRELUDE.contains(n))
+            .collect()
+    }
+
+    /// Record the names an item brings into scope
+    fn define_item(&mut self, item: &syn::Item) {
+        let ident = match item {
+            syn::Item::Const(i) => Some(&i.ident),
+            syn::Item::Enum(i) => {
+                for variant in &i.variants {
+                    self.defined.insert(variant.ident.to_string());
+                }
+                Some(&i.ident)
+            }
+            syn::Item::Fn(i) => {
+                self.define_generics(&i.sig.generics);
+                Some(&i.sig.ident)
+            }
+            syn::Item::Mod(i) => Some(&i.ident),
+            syn::Item::Static(i) => Some(&i.ident),
+            syn::Item::Struct(i) => Some(&i.ident),
+            syn::Item::Trait(i) => {
+                for trait_item in &i.items {
+                    if let syn::TraitItem::Fn(f) = trait_item {
+                        self.define_generics(&f.sig.generics);
+                    }
+                }
+                Some(&i.ident)
+            }
+            syn::Item::Type(i) => Some(&i.ident),
+            syn::Item::Impl(i) => {
+                self.define_generics(&i.generics);
+                for impl_item in &i.items {
+                    if let syn::ImplItem::Fn(f) = impl_item {
+                        self.define_generics(&f.sig.generics);
+                    }
+                }
+                None
+            }
+            syn::Item::Use(i) => {
+                self.define_use(&i.tree);
+                None
+            }
+            _ => None,
+        };
+        if let Some(ident) = ident {
+            self.defined.insert(ident.to_string());
+        }
+    }
+
+    fn define_generics(&mut self, generics: &syn::Generics) {
+        for param in generics.type_params() {
+            self.defined.insert(param.ident.to_string());
+        }
+    }
+
+    fn define_use(&mut self, tree: &syn::UseTree) {
+        match tree {
+            syn::UseTree::Path(path) => self.define_use(&path.tree),
+            syn::UseTree::Name(name) => {
+                self.defined.insert(name.ident.to_string());
+            }
+            syn::UseTree::Rename(rename) => {
+                self.defined.insert(rename.rename.to_string());
+            }
+            syn::UseTree::Group(group) => group.items.iter().for_each(|t| self.define_use(t)),
+            syn::UseTree::Glob(_) => {}
+        }
+    }
+
+    /// Collect referenced names and method calls from a token stream
+    ///
+    /// Identifiers following `::` or `.` are members (methods, associated functions,
+    /// fields or path segments) rather than names in scope, as is the name following a
+    /// declaration keyword. Names inside a nested `use` (e.g. one scoped to a function
+    /// body) are treated as defined.
+    fn walk(&mut self, tokens: TokenStream) {
+        let mut after_colons = false;
+        let mut after_dot = false;
+        let mut colon_count = 0;
+        let mut in_use = false;
+        let mut after_keyword = false;
+
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) => {
+                    let name = ident.to_string();
+                    let declared = after_keyword;
+                    after_keyword = DECLARATION_KEYWORDS.contains(&name.as_str());
+                    if in_use {
+                        self.defined.insert(name);
+                    } else if name == "use" {
+                        in_use = true;
+                    } else if after_dot || after_colons {
+                        self.members.insert(name);
+                    } else if !declared {
+                        self.referenced.insert(name);
+                    }
+                    after_colons = false;
+                    after_dot = false;
+                    colon_count = 0;
+                }
+                TokenTree::Punct(punct) => {
+                    match punct.as_char() {
+                        ':' => colon_count += 1,
+                        ';' => in_use = false,
+                        _ => colon_count = 0,
+                    }
+                    after_colons = colon_count >= 2;
+                    after_dot = punct.as_char() == '.';
+                    after_keyword = false;
+                }
+                TokenTree::Group(group) => {
+                    if in_use {
+                        self.define_use_group(group.stream());
+                    } else {
+                        self.walk(group.stream());
+                    }
+                    after_colons = false;
+                    after_dot = false;
+                    colon_count = 0;
+                    after_keyword = false;
+                }
+                TokenTree::Literal(_) => {
+                    after_colons = false;
+                    after_dot = false;
+                    colon_count = 0;
+                    after_keyword = false;
+                }
+            }
+        }
+    }
+
+    /// Prefix every SeaORM prelude name the module doesn't define with its full path
+    ///
+    /// Tracks `::`, `.` and nested `use` the same way [`Usage::walk`] does, so only
+    /// names that would have been resolved through the glob are rewritten.
+    fn qualify(&self, tokens: TokenStream) -> TokenStream {
+        let mut out = TokenStream::new();
+        let mut after_colons = false;
+        let mut after_dot = false;
+        let mut colon_count = 0;
+        let mut in_use = false;
+        let mut after_keyword = false;
+
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) => {
+                    let name = ident.to_string();
+                    let declared = after_keyword;
+                    after_keyword = DECLARATION_KEYWORDS.contains(&name.as_str());
+                    if name == "use" {
+                        in_use = true;
+                    } else if !in_use
+                        && !after_dot
+                        && !after_colons
+                        && !declared
+                        && SEA_ORM_PRELUDE.contains(&name.as_str())
+                        && !self.defined.contains(&name)
+                    {
+                        out.extend(quote::quote! { sea_orm::entity::prelude:: });
+                    }
+                    out.extend([TokenTree::Ident(ident)]);
+                    after_colons = false;
+                    after_dot = false;
+                    colon_count = 0;
+                }
+                TokenTree::Punct(punct) => {
+                    match punct.as_char() {
+                        ':' => colon_count += 1,
+                        ';' => in_use = false,
+                        _ => colon_count = 0,
+                    }
+                    after_colons = colon_count >= 2;
+                    after_dot = punct.as_char() == '.';
+                    after_keyword = false;
+                    out.extend([TokenTree::Punct(punct)]);
+                }
+                TokenTree::Group(group) => {
+                    let stream = if in_use {
+                        group.stream()
+                    } else {
+                        self.qualify(group.stream())
+                    };
+                    let mut qualified = Group::new(group.delimiter(), stream);
+                    qualified.set_span(group.span());
+                    out.extend([TokenTree::Group(qualified)]);
+                    after_colons = false;
+                    after_dot = false;
+                    colon_count = 0;
+                    after_keyword = false;
+                }
+                TokenTree::Literal(literal) => {
+                    out.extend([TokenTree::Literal(literal)]);
+                    after_colons = false;
+                    after_dot = false;
+                    colon_count = 0;
+                    after_keyword = false;
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Treat every identifier of a nested `use` group as defined
+    fn define_use_group(&mut self, tokens: TokenStream) {
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) => {
+                    self.defined.insert(ident.to_string());
+                }
+                TokenTree::Group(group) => self.define_use_group(group.stream()),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_glob_imports() {
+        let content = r#"
+            #[allow(unused_imports)]
+            use super::prelude::*;
+            use sea_orm::entity::prelude::*;
+
+            #[sea_orm::model]
+            #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+            #[sea_orm(table_name = "orders")]
+            pub struct Model {
+                #[sea_orm(primary_key)]
+                pub id: i64,
+                pub status: OrderState,
+                pub placed_at: DateTimeUtc,
+                #[sea_orm(has_many)]
+                pub items: HasMany<super::item::Entity>,
+            }
+
+            impl ActiveModelBehavior for ActiveModel {}
+
+            impl Entity {
+                pub async fn list<C>(db: &C) -> Result<Vec<Model>, DbErr>
+                where
+                    C: ConnectionTrait,
+                {
+                    use sea_orm::QueryOrder;
+                    Self::find().filter(Column::Id.eq(1)).order_by_asc(Column::Id).all(db).await
+                }
+            }
+        "#;
+
+        let provided = BTreeSet::from(["OrderState".to_string(), "Order".to_string()]);
+        let expanded = expand_glob_imports(content, &provided);
+        assert!(!expanded.contains("::*"), "Got:\n{}", expanded);
+        assert!(
+            expanded.contains("use super::prelude::OrderState;"),
+            "Got:\n{}",
+            expanded
+        );
+        assert!(
+            expanded.contains(
+                "use sea_orm::entity::prelude::{\n    ActiveModelBehavior, ColumnTrait, ConnectionTrait, DateTimeUtc, DbErr,\n    DeriveEntityModel, EntityTrait, HasMany, QueryFilter,\n};"
+            ),
+            "Got:\n{}",
+            expanded
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Migration generation for SeaORM entities
+//!
+//! With the `emit_migrations` parameter every entity gets a `sea_orm_migration`
+//! migration next to it, creating the table with its indexes and the foreign keys of
+//! its `belongs_to` relations, and dropping the table again on `down`.
+
+use crate::codegen::entity::{
+    entity_columns, prefixed_table_name, resolve_message_options, schema_statements,
+};
+use crate::codegen::relation::{related_module_path, with_auto_reverse};
+use crate::codegen::Context;
+use crate::options::seaorm::RelationType;
+use crate::GeneratorError;
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use proc_macro2::TokenStream;
+use prost_types::compiler::code_generator_response::File;
+use prost_types::{DescriptorProto, FileDescriptorProto};
+use quote::quote;
+
+/// Module name of the migration creating `table_name` (`m_create_<table>`)
+pub fn migration_module_name(table_name: &str) -> String {
+    format!("m_create_{}", table_name)
+}
+
+/// Generate the migration for an entity message
+///
+/// Returns None for messages that aren't entities.
+pub fn generate(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    message: &DescriptorProto,
+) -> Result<Option<File>, GeneratorError> {
+    let message_name = message.name.as_deref().unwrap_or("");
+    let module: TokenStream = format!("super::{}", message_name.to_snake_case())
+        .parse()
+        .map_err(|e| GeneratorError::CodeGenError(format!("{}: {}", message_name, e)))?;
+
+    let Some(table_name) = prefixed_table_name(ctx, file, message) else {
+        return Ok(None);
+    };
+    let foreign_keys = foreign_keys(ctx, file, message, &table_name, &module)?;
+    let Some(statements) = schema_statements(ctx, file, message, &module, &foreign_keys)? else {
+        return Ok(None);
+    };
+
+    let create_table = &statements.create_table;
+    let create_indexes = &statements.create_indexes;
+    let module_doc = format!(" Migration creating the `{}` table", statements.table_name);
+
+    let code = quote! {
+        #![doc = #module_doc]
+        //!
+        //! Generated by protoc-gen-seaorm from protobuf definition.
+        //! @generated
+
+        use sea_orm_migration::prelude::*;
+        #[allow(unused_imports)]
+        use sea_orm_migration::sea_orm::ColumnTrait as _;
+
+        #[derive(DeriveMigrationName)]
+        pub struct Migration;
+
+        #[async_trait::async_trait]
+        impl MigrationTrait for Migration {
+            async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+                manager.create_table(#create_table).await?;
+                #(manager.create_index(#create_indexes).await?;)*
+                Ok(())
+            }
+
+            async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+                manager
+                    .drop_table(Table::drop().table(#module::Entity).to_owned())
+                    .await
+            }
+        }
+    };
+
+    // Format the generated code
+    let content = code.to_string();
+    let formatted = match syn::parse_file(&content) {
+        Ok(parsed) => prettyplease::unparse(&parsed),
+        Err(_) => content,
+    };
+
+    Ok(Some(File {
+        name: Some(format!(
+            "{}/{}.rs",
+            crate::generator::package_dir(&ctx.config, file.package.as_deref().unwrap_or("")),
+            migration_module_name(&statements.table_name)
+        )),
+        content: Some(formatted),
+        ..Default::default()
+    }))
+}
+
+/// `.foreign_key(...)` calls for the entity's `belongs_to` relations
+///
+/// Each key is named `fk_<table>_<relation>` and runs from the relation's
+/// `foreign_key` column (default `<related>_id`) to its `references` column (default
+/// `id`) on the related entity.
+fn foreign_keys(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    message: &DescriptorProto,
+    table_name: &str,
+    module: &TokenStream,
+) -> Result<Vec<TokenStream>, GeneratorError> {
+    let Some(options) = resolve_message_options(file, message) else {
+        return Ok(Vec::new());
+    };
+    let message_name = message.name.as_deref().unwrap_or("");
+    let columns = entity_columns(ctx, file, message)?;
+
+    with_auto_reverse(&options.relations, message_name)
+        .iter()
+        .filter(|rel| rel.r#type == RelationType::BelongsTo as i32 && !rel.related.is_empty())
+        .map(|rel| {
+            let from = if rel.foreign_key.is_empty() {
+                format!("{}_id", rel.related.to_snake_case())
+            } else {
+                rel.foreign_key.clone()
+            };
+            let to = if rel.references.is_empty() {
+                "id".to_string()
+            } else {
+                rel.references.clone()
+            };
+            if !columns.iter().any(|c| c.name == from) {
+                return Err(GeneratorError::InvalidConfig(format!(
+                    "belongs_to relation {} on {} needs a \"{}\" column for its foreign key",
+                    rel.name, message_name, from
+                )));
+            }
+
+            let name = format!("fk_{}_{}", table_name, rel.name.to_snake_case());
+            let from = quote::format_ident!("{}", from.to_upper_camel_case());
+            let to = quote::format_ident!("{}", to.to_upper_camel_case());
+            let target: TokenStream =
+                related_module_path(ctx, file.package.as_deref(), &rel.related)
+                    .parse()
+                    .map_err(|e| {
+                        GeneratorError::CodeGenError(format!("{}: {}", message_name, e))
+                    })?;
+
+            Ok(quote! {
+                .foreign_key(
+                    ForeignKey::create()
+                        .name(#name)
+                        .from(#module::Entity, #module::Column::#from)
+                        .to(#target::Entity, #target::Column::#to)
+                )
+            })
+        })
+        .collect()
+}
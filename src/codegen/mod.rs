@@ -7,52 +7,120 @@ pub mod column;
 pub mod domain;
 pub mod entity;
 pub mod enum_gen;
+pub mod imports;
+pub mod migration;
 pub mod oneof;
 pub mod relation;
+pub mod resolver;
 pub mod service;
 
+use crate::config::Config;
 use crate::GeneratorError;
+use proc_macro2::TokenStream;
 use prost_types::compiler::code_generator_response::File;
 use prost_types::{
     DescriptorProto, EnumDescriptorProto, FileDescriptorProto, ServiceDescriptorProto,
 };
+use resolver::TypeResolver;
+
+/// Shared state for generating code from a single CodeGeneratorRequest
+pub struct Context<'a> {
+    /// Plugin configuration parsed from the request parameter
+    pub config: Config,
+    /// Resolves message references across every file in the request
+    pub resolver: TypeResolver<'a>,
+}
+
+impl<'a> Context<'a> {
+    /// Build a context over all files of a request
+    pub fn new(config: Config, files: &'a [FileDescriptorProto]) -> Self {
+        Self {
+            config,
+            resolver: TypeResolver::new(files),
+        }
+    }
+}
+
+/// `#[non_exhaustive]` when enabled by the `non_exhaustive` parameter, otherwise nothing
+pub fn non_exhaustive_attribute(enabled: bool) -> TokenStream {
+    if enabled {
+        quote::quote! { #[non_exhaustive] }
+    } else {
+        TokenStream::new()
+    }
+}
+
+/// `#[cfg_attr(feature = "specta", derive(specta::Type))]` when enabled by the `derive_specta`
+/// parameter, otherwise nothing
+pub fn specta_derive(enabled: bool) -> TokenStream {
+    if enabled {
+        quote::quote! { #[cfg_attr(feature = "specta", derive(specta::Type))] }
+    } else {
+        TokenStream::new()
+    }
+}
+
+/// `#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]` when enabled by the
+/// `derive_arbitrary` parameter, otherwise nothing
+pub fn arbitrary_derive(enabled: bool) -> TokenStream {
+    if enabled {
+        quote::quote! { #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))] }
+    } else {
+        TokenStream::new()
+    }
+}
 
 /// Generate a SeaORM entity from a protobuf message
 ///
 /// Returns None if the message should be skipped (no seaorm options)
 pub fn generate_entity(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    message: &DescriptorProto,
+) -> Result<Option<File>, GeneratorError> {
+    entity::generate(ctx, file, message)
+}
+
+/// Generate a `sea_orm_migration` migration for an entity message
+///
+/// Returns None if the message isn't an entity
+pub fn generate_migration(
+    ctx: &Context,
     file: &FileDescriptorProto,
     message: &DescriptorProto,
 ) -> Result<Option<File>, GeneratorError> {
-    entity::generate(file, message)
+    migration::generate(ctx, file, message)
 }
 
 /// Generate a SeaORM enum from a protobuf enum definition
 ///
 /// Returns None if the enum should be skipped (no seaorm options)
 pub fn generate_enum(
+    ctx: &Context,
     file: &FileDescriptorProto,
     enum_desc: &EnumDescriptorProto,
 ) -> Result<Option<File>, GeneratorError> {
-    enum_gen::generate(file, enum_desc)
+    enum_gen::generate(ctx, file, enum_desc)
 }
 
 /// Generate a Storage trait from a protobuf service definition
 ///
 /// Returns None if the service should be skipped (no seaorm options or generate_storage is false)
 pub fn generate_service(
+    ctx: &Context,
     file: &FileDescriptorProto,
     service: &ServiceDescriptorProto,
 ) -> Result<Option<File>, GeneratorError> {
-    service::generate(file, service)
+    service::generate(ctx, file, service)
 }
 
 /// Generate a domain type with garde validation from a protobuf message
 ///
 /// Returns None if the message has no input options
 pub fn generate_domain(
+    ctx: &Context,
     file: &FileDescriptorProto,
     message: &DescriptorProto,
 ) -> Result<Option<File>, GeneratorError> {
-    domain::generate(file, message)
+    domain::generate(ctx, file, message)
 }
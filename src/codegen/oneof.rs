@@ -7,7 +7,7 @@
 
 use crate::options::{parse_oneof_options, seaorm::OneofOptions};
 use crate::types::map_proto_type;
-use heck::ToSnakeCase;
+use heck::{ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::TokenStream;
 use prost_types::{DescriptorProto, FieldDescriptorProto};
 use quote::{format_ident, quote};
@@ -47,6 +47,8 @@ pub struct OneofInfo {
     pub column_prefix: String,
     /// Discriminator column name for tagged strategy
     pub discriminator_column: String,
+    /// Existing enum typing the discriminator column for tagged strategy
+    pub discriminator_enum: String,
     /// Fields that belong to this oneof
     pub fields: Vec<OneofField>,
 }
@@ -77,6 +79,10 @@ pub fn extract_oneofs(message: &DescriptorProto) -> Vec<OneofInfo> {
         // Parse options
         let options = parse_oneof_options(oneof_desc);
         let (strategy, column_prefix, discriminator_column) = extract_oneof_settings(&options);
+        let discriminator_enum = options
+            .as_ref()
+            .map(|o| o.discriminator_enum.clone())
+            .unwrap_or_default();
 
         // Find all fields belonging to this oneof
         let fields: Vec<OneofField> = message
@@ -95,6 +101,7 @@ pub fn extract_oneofs(message: &DescriptorProto) -> Vec<OneofInfo> {
             strategy,
             column_prefix,
             discriminator_column,
+            discriminator_enum,
             fields,
         });
     }
@@ -131,6 +138,9 @@ pub fn is_oneof_field(field: &FieldDescriptorProto, message: &DescriptorProto) -
 }
 
 /// Generate fields for a flatten strategy oneof
+///
+/// Each variant becomes its own nullable column with no discriminator, so a oneof with
+/// a single variant behaves exactly like an optional field of that variant's type.
 pub fn generate_flatten_fields(oneof: &OneofInfo, message: &DescriptorProto) -> Vec<TokenStream> {
     let mut fields = Vec::new();
 
@@ -151,8 +161,14 @@ pub fn generate_flatten_fields(oneof: &OneofInfo, message: &DescriptorProto) ->
 
             let field_ident = format_ident!("{}", field_name.to_snake_case());
             let mapped = map_proto_type(field.r#type(), field.type_name.as_deref());
-            let rust_type: syn::Type =
-                syn::parse_str(&mapped.rust_type).unwrap_or_else(|_| syn::parse_quote!(String));
+            // Wrapper types (e.g. google.protobuf.StringValue) are already optional
+            let nullable_type = if mapped.rust_type.starts_with("Option<") {
+                mapped.rust_type.clone()
+            } else {
+                format!("Option<{}>", mapped.rust_type)
+            };
+            let rust_type: syn::Type = syn::parse_str(&nullable_type)
+                .unwrap_or_else(|_| syn::parse_quote!(Option<String>));
 
             // All oneof fields are nullable since only one can be set
             let column_attr = quote! {
@@ -161,7 +177,7 @@ pub fn generate_flatten_fields(oneof: &OneofInfo, message: &DescriptorProto) ->
 
             fields.push(quote! {
                 #column_attr
-                pub #field_ident: Option<#rust_type>
+                pub #field_ident: #rust_type
             });
         }
     }
@@ -169,21 +185,223 @@ pub fn generate_flatten_fields(oneof: &OneofInfo, message: &DescriptorProto) ->
     fields
 }
 
+/// Name of the typed enum generated for a oneof stored as JSON or tagged columns
+/// (`{Message}{Oneof}`)
+pub fn oneof_enum_name(message_name: &str, oneof: &OneofInfo) -> String {
+    format!(
+        "{}{}",
+        message_name.to_upper_camel_case(),
+        oneof.name.to_upper_camel_case()
+    )
+}
+
 /// Generate fields for a JSON strategy oneof
-pub fn generate_json_fields(oneof: &OneofInfo) -> Vec<TokenStream> {
+///
+/// The column holds the typed enum from [`generate_json_enum`], stored as JSON.
+pub fn generate_json_fields(oneof: &OneofInfo, enum_name: &str) -> Vec<TokenStream> {
     let field_name = format_ident!("{}", oneof.name.to_snake_case());
     let column_name = oneof.name.to_snake_case();
+    let enum_ident = format_ident!("{}", enum_name);
 
-    // For JSON strategy, we store the entire oneof as a JSON column
-    // The actual Rust type would be an enum, but for simplicity we use Json<serde_json::Value>
     vec![quote! {
         #[sea_orm(column_name = #column_name, column_type = "Json")]
-        pub #field_name: Option<sea_orm::prelude::Json>
+        pub #field_name: Option<#enum_ident>
     }]
 }
 
+/// Rust types of each variant payload in a oneof
+pub fn variant_types(oneof: &OneofInfo, message: &DescriptorProto) -> Vec<String> {
+    oneof
+        .fields
+        .iter()
+        .filter_map(|oneof_field| {
+            message
+                .field
+                .iter()
+                .find(|f| f.name.as_ref() == Some(&oneof_field.name))
+        })
+        .map(|field| map_proto_type(field.r#type(), field.type_name.as_deref()).rust_type)
+        .collect()
+}
+
+/// Generate a typed, serde-serializable enum for a oneof stored inside JSON
+///
+/// Each oneof field becomes a newtype variant named after the field, serialized
+/// externally tagged with the original snake_case field name (`{"card": "..."}`).
+/// `FromJsonQueryResult` supplies the `TryGetable`/`ValueType` glue SeaORM needs to
+/// use the enum directly as a JSON column type.
+pub fn generate_json_enum(
+    enum_name: &str,
+    oneof: &OneofInfo,
+    message: &DescriptorProto,
+    eq: bool,
+) -> TokenStream {
+    let enum_ident = format_ident!("{}", enum_name);
+    let enum_doc = format!("Variants of the `{}` oneof", oneof.name);
+
+    let variants: Vec<TokenStream> = oneof
+        .fields
+        .iter()
+        .zip(variant_types(oneof, message))
+        .map(|(oneof_field, rust_type)| {
+            let variant = format_ident!("{}", oneof_field.name.to_upper_camel_case());
+            let ty: syn::Type =
+                syn::parse_str(&rust_type).unwrap_or_else(|_| syn::parse_quote!(String));
+            quote! { #variant(#ty) }
+        })
+        .collect();
+
+    let eq_derive = if eq {
+        quote! { Eq, }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #[doc = #enum_doc]
+        #[derive(Clone, Debug, PartialEq, #eq_derive Serialize, Deserialize, FromJsonQueryResult)]
+        #[serde(rename_all = "snake_case")]
+        pub enum #enum_ident {
+            #(#variants),*
+        }
+    }
+}
+
+/// An existing enum typing a tagged oneof's discriminator (`discriminator_enum`)
+#[derive(Debug, Clone)]
+pub struct Discriminator {
+    /// Rust name of the enum
+    pub rust_name: String,
+    /// Whether every enum variant has a oneof field, making the match exhaustive
+    pub exhaustive: bool,
+}
+
+/// Payload types stored in the tagged value column through `Display` / `FromStr`
+const TEXT_PAYLOAD_TYPES: &[&str] = &["String", "bool", "i32", "i64", "u32", "u64", "f32", "f64"];
+
+/// Generate the typed enum for a tagged oneof, with conversions to and from its columns
+///
+/// The discriminator holds the snake_case field name of the set variant, or with a
+/// `discriminator` the variant of that enum named after the field. Scalar payloads are
+/// stored as their text form; any other payload (messages, bytes, enums) is stored as
+/// JSON, so those types must implement serde's traits.
+pub fn generate_tagged_enum(
+    enum_name: &str,
+    oneof: &OneofInfo,
+    message: &DescriptorProto,
+    eq: bool,
+    discriminator: Option<&Discriminator>,
+) -> TokenStream {
+    let enum_ident = format_ident!("{}", enum_name);
+    let enum_doc = format!("Variants of the `{}` oneof", oneof.name);
+    let oneof_name = &oneof.name;
+
+    let mut variants = Vec::new();
+    let mut from_arms = Vec::new();
+    let mut to_arms = Vec::new();
+    for (oneof_field, rust_type) in oneof.fields.iter().zip(variant_types(oneof, message)) {
+        let variant = format_ident!("{}", oneof_field.name.to_upper_camel_case());
+        let tag = match discriminator {
+            Some(disc) => {
+                let disc_ident = format_ident!("{}", disc.rust_name);
+                quote! { #disc_ident::#variant }
+            }
+            None => {
+                let tag = oneof_field.name.to_snake_case();
+                quote! { #tag }
+            }
+        };
+        let tag_value = match discriminator {
+            Some(_) => tag.clone(),
+            None => quote! { #tag.to_string() },
+        };
+        let ty: syn::Type =
+            syn::parse_str(&rust_type).unwrap_or_else(|_| syn::parse_quote!(String));
+        variants.push(quote! { #variant(#ty) });
+
+        if TEXT_PAYLOAD_TYPES.contains(&rust_type.as_str()) {
+            from_arms.push(quote! {
+                #tag => value.parse().map(Self::#variant).map_err(|e| invalid(&e)),
+            });
+            to_arms.push(quote! {
+                Self::#variant(value) => (#tag_value, value.to_string()),
+            });
+        } else {
+            from_arms.push(quote! {
+                #tag => serde_json::from_str(value).map(Self::#variant).map_err(|e| invalid(&e)),
+            });
+            to_arms.push(quote! {
+                Self::#variant(value) => (
+                    #tag_value,
+                    serde_json::to_string(value).expect("oneof payload serializes to JSON"),
+                ),
+            });
+        }
+    }
+
+    let eq_derive = if eq {
+        quote! { Eq, }
+    } else {
+        quote! {}
+    };
+    let (disc_type, unknown_arm, invalid) = match discriminator {
+        Some(disc) => {
+            let disc_ident = format_ident!("{}", disc.rust_name);
+            let unknown = format!("{} has no {} variant {{:?}}", oneof_name, disc.rust_name);
+            let unknown_arm = if disc.exhaustive {
+                quote! {}
+            } else {
+                quote! { other => Err(DbErr::Custom(format!(#unknown, other))), }
+            };
+            let invalid = format!("invalid {} value for {{:?}}: {{}}", oneof_name);
+            (quote! { #disc_ident }, unknown_arm, invalid)
+        }
+        None => {
+            let unknown = format!("unknown {} tag \"{{}}\"", oneof_name);
+            let unknown_arm = quote! { other => Err(DbErr::Custom(format!(#unknown, other))), };
+            let invalid = format!("invalid {} value for \"{{}}\": {{}}", oneof_name);
+            (quote! { &str }, unknown_arm, invalid)
+        }
+    };
+    let disc_owned = match discriminator {
+        Some(_) => disc_type.clone(),
+        None => quote! { String },
+    };
+
+    quote! {
+        #[doc = #enum_doc]
+        #[derive(Clone, Debug, PartialEq, #eq_derive)]
+        pub enum #enum_ident {
+            #(#variants),*
+        }
+
+        impl #enum_ident {
+            /// Rebuild the oneof from its discriminator and value columns
+            pub fn from_db(disc: #disc_type, value: &str) -> Result<Self, DbErr> {
+                let invalid = |e: &dyn std::fmt::Display| DbErr::Custom(format!(#invalid, disc, e));
+                match disc {
+                    #(#from_arms)*
+                    #unknown_arm
+                }
+            }
+
+            /// Split the oneof into its discriminator and value columns
+            pub fn to_db(&self) -> (#disc_owned, String) {
+                match self {
+                    #(#to_arms)*
+                }
+            }
+        }
+    }
+}
+
 /// Generate fields for a tagged strategy oneof
-pub fn generate_tagged_fields(oneof: &OneofInfo) -> Vec<TokenStream> {
+///
+/// The discriminator column is text, or the `discriminator` enum when given.
+pub fn generate_tagged_fields(
+    oneof: &OneofInfo,
+    discriminator: Option<&Discriminator>,
+) -> Vec<TokenStream> {
     let base_name = oneof.name.to_snake_case();
 
     // Discriminator column name
@@ -193,6 +411,13 @@ pub fn generate_tagged_fields(oneof: &OneofInfo) -> Vec<TokenStream> {
         oneof.discriminator_column.clone()
     };
     let disc_ident = format_ident!("{}", disc_col.to_snake_case());
+    let disc_type = match discriminator {
+        Some(disc) => {
+            let ident = format_ident!("{}", disc.rust_name);
+            quote! { #ident }
+        }
+        None => quote! { String },
+    };
 
     // Value column name
     let value_col = format!("{}_value", base_name);
@@ -201,7 +426,7 @@ pub fn generate_tagged_fields(oneof: &OneofInfo) -> Vec<TokenStream> {
     vec![
         quote! {
             #[sea_orm(column_name = #disc_col)]
-            pub #disc_ident: Option<String>
+            pub #disc_ident: Option<#disc_type>
         },
         quote! {
             #[sea_orm(column_name = #value_col, column_type = "Text")]
@@ -4,7 +4,9 @@
 //!
 //! SeaORM 2.0 uses the `DeriveRelation` macro with enum variants to define relations.
 
+use crate::codegen::Context;
 use crate::options::seaorm::{FieldOptions, RelationDef, RelationType};
+use crate::GeneratorError;
 use heck::{ToSnakeCase, ToUpperCamelCase};
 
 /// Represents a generated relation
@@ -49,6 +51,53 @@ impl SeaOrmRelationType {
     }
 }
 
+/// Field and `Relation` variant names of an entity's relations
+///
+/// Variants are the PascalCase relation name, behind the model's `relation_enum_prefix`
+/// when set. A relation named like one of the entity's columns would clash with the
+/// column's field and `Column` variant, so its field and variant take the prefix or,
+/// without one, a `Rel` suffix (`author` becomes `author_rel` / `AuthorRel`).
+#[derive(Debug, Clone, Default)]
+pub struct RelationNaming {
+    /// `relation_enum_prefix` of the model
+    pub prefix: String,
+    /// Field names of the entity's columns
+    pub columns: Vec<String>,
+}
+
+impl RelationNaming {
+    fn collides(&self, rel_def: &RelationDef) -> bool {
+        let field = rel_def.name.to_snake_case();
+        self.columns.contains(&field)
+    }
+
+    /// `Relation` variant of a relation
+    pub fn enum_name(&self, rel_def: &RelationDef) -> String {
+        let base = rel_def.name.to_upper_camel_case();
+        if !self.prefix.is_empty() {
+            format!("{}{}", self.prefix, base)
+        } else if self.collides(rel_def) {
+            format!("{}Rel", base)
+        } else {
+            base
+        }
+    }
+
+    /// Model field of a relation
+    pub fn field_name(&self, rel_def: &RelationDef) -> String {
+        if self.collides(rel_def) {
+            self.enum_name(rel_def).to_snake_case()
+        } else {
+            rel_def.name.to_snake_case()
+        }
+    }
+
+    /// Whether the variant differs from the one derived from the field name
+    fn renamed(&self, rel_def: &RelationDef) -> bool {
+        self.enum_name(rel_def) != self.field_name(rel_def).to_upper_camel_case()
+    }
+}
+
 /// Generate a relation from field options
 ///
 /// Returns None if the field doesn't define a relation
@@ -209,6 +258,47 @@ pub fn generate_relation_from_def(rel_def: &RelationDef) -> Option<GeneratedRela
     })
 }
 
+/// Add the complementary side of self-referential relations marked `auto_reverse`
+///
+/// A `belongs_to` gains a `has_many` named `children`, and a `has_one`/`has_many`
+/// gains a `belongs_to` named `parent` (either overridable with `reverse_name`). The
+/// synthesized side shares the foreign key, so [`find_self_ref_reverse`] pairs the two.
+/// Relations whose reverse is already declared are left alone.
+pub fn with_auto_reverse(relations: &[RelationDef], current_entity: &str) -> Vec<RelationDef> {
+    let mut result = relations.to_vec();
+
+    for rel in relations.iter().filter(|r| r.auto_reverse) {
+        if find_self_ref_reverse(relations, rel, current_entity).is_some()
+            || rel.related.to_snake_case() != current_entity.to_snake_case()
+        {
+            continue;
+        }
+
+        let (reverse_type, default_name) =
+            match RelationType::try_from(rel.r#type).unwrap_or(RelationType::Unspecified) {
+                RelationType::BelongsTo => (RelationType::HasMany, "children"),
+                RelationType::HasOne | RelationType::HasMany => (RelationType::BelongsTo, "parent"),
+                _ => continue,
+            };
+        let name = if rel.reverse_name.is_empty() {
+            default_name.to_string()
+        } else {
+            rel.reverse_name.clone()
+        };
+
+        result.push(RelationDef {
+            name,
+            r#type: reverse_type as i32,
+            related: rel.related.clone(),
+            foreign_key: rel.foreign_key.clone(),
+            references: rel.references.clone(),
+            ..Default::default()
+        });
+    }
+
+    result
+}
+
 /// Find the reverse relation name for a self-referential relation
 ///
 /// Given a list of relations and a self-referential relation, find its reverse pair.
@@ -219,6 +309,16 @@ fn find_self_ref_reverse(
     current_rel: &RelationDef,
     current_entity: &str,
 ) -> Option<String> {
+    find_self_ref_reverse_def(relations, current_rel, current_entity)
+        .map(|rel| rel.name.to_upper_camel_case())
+}
+
+/// The reverse pair of a self-referential relation, see [`find_self_ref_reverse`]
+fn find_self_ref_reverse_def<'a>(
+    relations: &'a [RelationDef],
+    current_rel: &RelationDef,
+    current_entity: &str,
+) -> Option<&'a RelationDef> {
     let current_type =
         RelationType::try_from(current_rel.r#type).unwrap_or(RelationType::Unspecified);
     let is_self_ref = current_rel.related.to_snake_case() == current_entity.to_snake_case();
@@ -263,7 +363,7 @@ fn find_self_ref_reverse(
             };
 
             if same_fk {
-                return Some(rel.name.to_upper_camel_case());
+                return Some(rel);
             }
         }
     }
@@ -271,18 +371,78 @@ fn find_self_ref_reverse(
     None
 }
 
+/// Module path of a related entity as seen from an entity in `package`
+///
+/// Entities in the same package are siblings (`super::post`). When the `module_root`
+/// parameter is set, entities resolved to another package are addressed from that
+/// root instead (`crate::entities::auth::v1::account`).
+pub fn related_module_path(ctx: &Context, package: Option<&str>, related: &str) -> String {
+    let target = ctx.resolver.resolve_message(related, package);
+
+    if let (Some(root), Some(target)) = (ctx.config.module_root.as_deref(), target) {
+        let target_package = target.file.package.as_deref().unwrap_or("");
+        if target_package != package.unwrap_or("") {
+            let module = target.message.name.as_deref().unwrap_or("").to_snake_case();
+            return std::iter::once(root.to_string())
+                .chain(crate::generator::package_modules(
+                    &ctx.config,
+                    target_package,
+                ))
+                .chain(std::iter::once(module))
+                .collect::<Vec<_>>()
+                .join("::");
+        }
+    }
+
+    format!("super::{}", related.to_snake_case())
+}
+
 /// Generate all relation fields for a message, properly handling self-referential pairs
+///
+/// Fields and variants are named per `naming`; relations still clashing with a column
+/// or with each other afterwards are rejected.
 pub fn generate_relation_fields(
+    ctx: &Context,
+    package: Option<&str>,
     relations: &[RelationDef],
     current_entity: &str,
-) -> Vec<proc_macro2::TokenStream> {
-    relations
+    naming: &RelationNaming,
+) -> Result<Vec<proc_macro2::TokenStream>, GeneratorError> {
+    let mut fields: Vec<String> = naming.columns.clone();
+    let mut variants: Vec<String> = naming
+        .columns
+        .iter()
+        .map(|c| c.to_upper_camel_case())
+        .collect();
+    for rel in relations.iter().filter(|r| !r.name.is_empty()) {
+        let field = naming.field_name(rel);
+        let variant = naming.enum_name(rel);
+        if fields.contains(&field) || variants.contains(&variant) {
+            return Err(GeneratorError::InvalidConfig(format!(
+                "Relation {} on {} collides with another field or relation as {} / {}; \
+                 rename it or set relation_enum_prefix",
+                rel.name, current_entity, field, variant
+            )));
+        }
+        fields.push(field);
+        variants.push(variant);
+    }
+
+    Ok(relations
         .iter()
         .filter_map(|rel| {
-            let reverse = find_self_ref_reverse(relations, rel, current_entity);
-            generate_relation_field_with_reverse(rel, current_entity, reverse.as_deref())
+            let reverse = find_self_ref_reverse_def(relations, rel, current_entity)
+                .map(|reverse| naming.enum_name(reverse));
+            let module = related_module_path(ctx, package, &rel.related);
+            generate_relation_field_with_reverse(
+                rel,
+                current_entity,
+                reverse.as_deref(),
+                &module,
+                naming,
+            )
         })
-        .collect()
+        .collect())
 }
 
 /// Generate a relation field for the SeaORM 2.0 dense format
@@ -298,14 +458,25 @@ pub fn generate_relation_field(
     rel_def: &RelationDef,
     current_entity: &str,
 ) -> Option<proc_macro2::TokenStream> {
-    generate_relation_field_with_reverse(rel_def, current_entity, None)
+    let module = format!("super::{}", rel_def.related.to_snake_case());
+    generate_relation_field_with_reverse(
+        rel_def,
+        current_entity,
+        None,
+        &module,
+        &RelationNaming::default(),
+    )
 }
 
 /// Generate a relation field with optional relation_reverse for self-referential relations
+///
+/// `target_module` is the module path of the related entity (e.g. `super::post`).
 fn generate_relation_field_with_reverse(
     rel_def: &RelationDef,
     current_entity: &str,
     relation_reverse: Option<&str>,
+    target_module: &str,
+    naming: &RelationNaming,
 ) -> Option<proc_macro2::TokenStream> {
     use quote::{format_ident, quote};
 
@@ -315,21 +486,24 @@ fn generate_relation_field_with_reverse(
 
     let rel_type = RelationType::try_from(rel_def.r#type).unwrap_or(RelationType::Unspecified);
 
-    let field_name = format_ident!("{}", rel_def.name.to_snake_case());
-    let relation_enum_name = rel_def.name.to_upper_camel_case();
+    let field_name = format_ident!("{}", naming.field_name(rel_def));
+    let relation_enum_name = naming.enum_name(rel_def);
+    // Other relations only spell out their variant when it isn't the field's
+    let relation_enum = if naming.renamed(rel_def) {
+        quote! { , relation_enum = #relation_enum_name }
+    } else {
+        quote! {}
+    };
 
     // Check if this is a self-referential relation
     let is_self_ref = rel_def.related.to_snake_case() == current_entity.to_snake_case();
 
-    // For self-ref, use Entity directly; otherwise use super::module::Entity
+    // For self-ref, use Entity directly; otherwise use the related module's Entity
     let target_entity: syn::Type = if is_self_ref {
         syn::parse_quote!(Entity)
     } else {
-        syn::parse_str(&format!(
-            "super::{}::Entity",
-            rel_def.related.to_snake_case()
-        ))
-        .unwrap_or_else(|_| syn::parse_quote!(Entity))
+        syn::parse_str(&format!("{}::Entity", target_module))
+            .unwrap_or_else(|_| syn::parse_quote!(Entity))
     };
 
     match rel_type {
@@ -348,7 +522,7 @@ fn generate_relation_field_with_reverse(
                 }
             } else {
                 Some(quote! {
-                    #[sea_orm(has_one)]
+                    #[sea_orm(has_one #relation_enum)]
                     pub #field_name: HasOne<#target_entity>
                 })
             }
@@ -372,7 +546,7 @@ fn generate_relation_field_with_reverse(
                     }
                 } else {
                     Some(quote! {
-                        #[sea_orm(has_many, via = #via_module)]
+                        #[sea_orm(has_many, via = #via_module #relation_enum)]
                         pub #field_name: HasMany<#target_entity>
                     })
                 }
@@ -390,7 +564,7 @@ fn generate_relation_field_with_reverse(
                 }
             } else {
                 Some(quote! {
-                    #[sea_orm(has_many)]
+                    #[sea_orm(has_many #relation_enum)]
                     pub #field_name: HasMany<#target_entity>
                 })
             }
@@ -422,7 +596,7 @@ fn generate_relation_field_with_reverse(
                 }
             } else {
                 Some(quote! {
-                    #[sea_orm(belongs_to, from = #from_col, to = #to_col)]
+                    #[sea_orm(belongs_to, from = #from_col, to = #to_col #relation_enum)]
                     pub #field_name: HasOne<#target_entity>
                 })
             }
@@ -438,7 +612,7 @@ fn generate_relation_field_with_reverse(
                     })
                 } else {
                     Some(quote! {
-                        #[sea_orm(has_many, via = #via_module)]
+                        #[sea_orm(has_many, via = #via_module #relation_enum)]
                         pub #field_name: HasMany<#target_entity>
                     })
                 }
@@ -449,7 +623,7 @@ fn generate_relation_field_with_reverse(
                 })
             } else {
                 Some(quote! {
-                    #[sea_orm(has_many)]
+                    #[sea_orm(has_many #relation_enum)]
                     pub #field_name: HasMany<#target_entity>
                 })
             }
@@ -458,6 +632,288 @@ fn generate_relation_field_with_reverse(
     }
 }
 
+/// Generate the expanded `Relation` enum and its impls for `manual_entity`
+///
+/// Produces what `DeriveEntityModel` derives from relation fields: a `Relation` enum,
+/// `RelationTrait` building each `RelationDef`, and `Related` for every target entity
+/// reached by exactly one relation. Variants are named per `naming`, as in the derive.
+/// A self-referential `has_one`/`has_many` is the reverse of its paired `belongs_to`.
+/// Relations through a junction table can't be expanded without the junction's own
+/// relations and are rejected.
+pub fn generate_manual_relations(
+    ctx: &Context,
+    package: Option<&str>,
+    relations: &[RelationDef],
+    current_entity: &str,
+    naming: &RelationNaming,
+) -> Result<proc_macro2::TokenStream, GeneratorError> {
+    use quote::{format_ident, quote};
+
+    let mut variants = Vec::new();
+    let mut defs = Vec::new();
+    let mut related: Vec<(String, proc_macro2::Ident)> = Vec::new();
+
+    for rel in relations {
+        let Some(generated) = generate_relation_from_def(rel) else {
+            continue;
+        };
+        let unsupported = |reason: &str| {
+            GeneratorError::InvalidConfig(format!(
+                "manual_entity cannot expand relation {} on {}: {}",
+                rel.name, current_entity, reason
+            ))
+        };
+        if generated.via_table.is_some()
+            || generated.relation_type == SeaOrmRelationType::ManyToMany
+        {
+            return Err(unsupported(
+                "relations through a junction table need the derive",
+            ));
+        }
+
+        let variant = format_ident!("{}", naming.enum_name(rel));
+        let is_self_ref = rel.related.to_snake_case() == current_entity.to_snake_case();
+        let module = related_module_path(ctx, package, &rel.related);
+        let target: syn::Path = if is_self_ref {
+            syn::parse_quote!(Entity)
+        } else {
+            syn::parse_str(&format!("{}::Entity", module))
+                .map_err(|_| unsupported("invalid related entity path"))?
+        };
+
+        let def = match generated.relation_type {
+            SeaOrmRelationType::BelongsTo => {
+                let from = format_ident!(
+                    "{}",
+                    generated
+                        .from_column
+                        .unwrap_or_default()
+                        .to_upper_camel_case()
+                );
+                let to = format_ident!(
+                    "{}",
+                    generated
+                        .to_column
+                        .unwrap_or_default()
+                        .to_upper_camel_case()
+                );
+                let to_column: syn::Path = if is_self_ref {
+                    syn::parse_quote!(Column::#to)
+                } else {
+                    syn::parse_str(&format!("{}::Column::{}", module, to))
+                        .map_err(|_| unsupported("invalid related column path"))?
+                };
+                quote! { Entity::belongs_to(#target).from(Column::#from).to(#to_column).into() }
+            }
+            _ if is_self_ref => {
+                let reverse = find_self_ref_reverse_def(relations, rel, current_entity)
+                    .ok_or_else(|| {
+                        unsupported("self-referential relations need a belongs_to pair")
+                    })?;
+                let reverse = format_ident!("{}", naming.enum_name(reverse));
+                quote! { Relation::#reverse.def().rev() }
+            }
+            SeaOrmRelationType::HasOne => quote! { Entity::has_one(#target).into() },
+            _ => quote! { Entity::has_many(#target).into() },
+        };
+
+        if !is_self_ref {
+            related.push((module, variant.clone()));
+        }
+        defs.push(quote! { Self::#variant => #def, });
+        variants.push(variant);
+    }
+
+    // `Related` can only be implemented once per target
+    let related_impls = related
+        .iter()
+        .filter(|(module, _)| related.iter().filter(|(m, _)| m == module).count() == 1)
+        .map(|(module, variant)| {
+            let target: syn::Path = syn::parse_str(&format!("{}::Entity", module))
+                .unwrap_or_else(|_| syn::parse_quote!(Entity));
+            quote! {
+                impl Related<#target> for Entity {
+                    fn to() -> RelationDef {
+                        Relation::#variant.def()
+                    }
+                }
+            }
+        });
+
+    let relation_def = if defs.is_empty() {
+        quote! { panic!("No RelationDef") }
+    } else {
+        quote! {
+            match self {
+                #(#defs)*
+            }
+        }
+    };
+
+    Ok(quote! {
+        #[derive(Copy, Clone, Debug, EnumIter)]
+        pub enum Relation {
+            #(#variants,)*
+        }
+
+        impl RelationTrait for Relation {
+            fn def(&self) -> RelationDef {
+                #relation_def
+            }
+        }
+
+        #(#related_impls)*
+    })
+}
+
+/// Generate eager-loading helper methods (`find_with_<relation>`) for an entity
+///
+/// Each helper looks up a model by its primary key and loads the related models in
+/// the same query. To-many relations use `find_with_related`, to-one relations use
+/// `find_also_related`. Both go through the single `Related` impl for the target, so
+/// self-referential relations and targets reached through more than one relation
+/// (which have no such impl) are skipped; join them through their `Relation` variant
+/// instead.
+///
+/// `primary_key` is the primary key column name and Rust type.
+pub fn generate_relation_helpers(
+    ctx: &Context,
+    package: Option<&str>,
+    relations: &[RelationDef],
+    current_entity: &str,
+    primary_key: (&str, &str),
+) -> Vec<proc_macro2::TokenStream> {
+    use quote::{format_ident, quote};
+
+    let (pk_name, pk_type) = primary_key;
+    let pk_ident = format_ident!("{}", pk_name);
+    let pk_type: syn::Type = syn::parse_str(pk_type).unwrap_or_else(|_| syn::parse_quote!(i64));
+
+    relations
+        .iter()
+        .filter(|rel| !rel.name.is_empty() && !rel.related.is_empty())
+        .filter(|rel| rel.related.to_snake_case() != current_entity.to_snake_case())
+        .filter(|rel| {
+            relations
+                .iter()
+                .filter(|other| other.related == rel.related)
+                .count()
+                == 1
+        })
+        .filter_map(|rel| {
+            let rel_type = RelationType::try_from(rel.r#type).unwrap_or(RelationType::Unspecified);
+            let method = format_ident!("find_with_{}", rel.name.to_snake_case());
+            let related: syn::Path =
+                syn::parse_str(&related_module_path(ctx, package, &rel.related)).ok()?;
+            let doc = format!(
+                "Find a model by primary key together with its related `{}`",
+                rel.name.to_snake_case()
+            );
+
+            match rel_type {
+                RelationType::HasMany | RelationType::ManyToMany => Some(quote! {
+                    #[doc = #doc]
+                    pub async fn #method<C>(
+                        db: &C,
+                        #pk_ident: #pk_type,
+                    ) -> Result<Option<(Model, Vec<#related::Model>)>, DbErr>
+                    where
+                        C: ConnectionTrait,
+                    {
+                        Ok(Self::find_by_id(#pk_ident)
+                            .find_with_related(#related::Entity)
+                            .all(db)
+                            .await?
+                            .into_iter()
+                            .next())
+                    }
+                }),
+                RelationType::HasOne | RelationType::BelongsTo => Some(quote! {
+                    #[doc = #doc]
+                    pub async fn #method<C>(
+                        db: &C,
+                        #pk_ident: #pk_type,
+                    ) -> Result<Option<(Model, Option<#related::Model>)>, DbErr>
+                    where
+                        C: ConnectionTrait,
+                    {
+                        Self::find_by_id(#pk_ident)
+                            .find_also_related(#related::Entity)
+                            .one(db)
+                            .await
+                    }
+                }),
+                RelationType::Unspecified => None,
+            }
+        })
+        .collect()
+}
+
+/// Generate the `RelatedEntity` enum (`DeriveRelatedEntity`) for loader-based eager loading
+///
+/// Each relation becomes a variant named like its `Relation` variant, pointing at the
+/// related entity. Self-referential relations and targets reached through more than one
+/// relation name their relation explicitly with `def`, since there's no unambiguous
+/// `Related` impl to fall back on.
+pub fn generate_related_entity(
+    ctx: &Context,
+    package: Option<&str>,
+    relations: &[RelationDef],
+    current_entity: &str,
+    naming: &RelationNaming,
+) -> proc_macro2::TokenStream {
+    use quote::{format_ident, quote};
+
+    let relations: Vec<&RelationDef> = relations
+        .iter()
+        .filter(|rel| !rel.name.is_empty() && !rel.related.is_empty())
+        .filter(|rel| {
+            RelationType::try_from(rel.r#type).unwrap_or(RelationType::Unspecified)
+                != RelationType::Unspecified
+        })
+        .collect();
+
+    let variants = relations.iter().map(|rel| {
+        let variant = naming.enum_name(rel);
+        let ident = format_ident!("{}", variant);
+        let self_ref = rel.related.to_snake_case() == current_entity.to_snake_case();
+        let entity = if self_ref {
+            "Entity".to_string()
+        } else {
+            format!(
+                "{}::Entity",
+                related_module_path(ctx, package, &rel.related)
+            )
+        };
+        let shared = relations
+            .iter()
+            .filter(|other| other.related == rel.related)
+            .count()
+            > 1;
+
+        if self_ref || shared {
+            let def = format!("Relation::{}.def()", variant);
+            quote! {
+                #[sea_orm(entity = #entity, def = #def)]
+                #ident
+            }
+        } else {
+            quote! {
+                #[sea_orm(entity = #entity)]
+                #ident
+            }
+        }
+    });
+
+    quote! {
+        /// Related entities, for loader-based eager loading
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelatedEntity)]
+        pub enum RelatedEntity {
+            #(#variants,)*
+        }
+    }
+}
+
 /// Generate the #[sea_orm(...)] attribute for a relation
 pub fn generate_relation_attribute(relation: &GeneratedRelation) -> String {
     match relation.relation_type {
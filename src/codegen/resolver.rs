@@ -0,0 +1,156 @@
+//! Type resolution across the files of a CodeGeneratorRequest
+//!
+//! Options like `include` and `entity` reference other messages by name. The resolver
+//! indexes every message in the request (including imports) so those references can be
+//! followed regardless of which file declares the target.
+
+use prost_types::{DescriptorProto, EnumDescriptorProto, FileDescriptorProto};
+use std::collections::HashMap;
+
+/// A message descriptor together with the file that declares it
+#[derive(Debug, Clone, Copy)]
+pub struct ResolvedMessage<'a> {
+    /// The file declaring the message
+    pub file: &'a FileDescriptorProto,
+    /// The message descriptor
+    pub message: &'a DescriptorProto,
+}
+
+/// An enum descriptor together with the file that declares it
+#[derive(Debug, Clone, Copy)]
+pub struct ResolvedEnum<'a> {
+    /// The file declaring the enum
+    pub file: &'a FileDescriptorProto,
+    /// The enum descriptor
+    pub enum_desc: &'a EnumDescriptorProto,
+}
+
+/// Index of top-level messages and enums by fully-qualified name (without the leading dot)
+#[derive(Debug, Default)]
+pub struct TypeResolver<'a> {
+    messages: HashMap<String, ResolvedMessage<'a>>,
+    enums: HashMap<String, ResolvedEnum<'a>>,
+}
+
+impl<'a> TypeResolver<'a> {
+    /// Build a resolver over all files in a request
+    pub fn new(files: &'a [FileDescriptorProto]) -> Self {
+        let mut messages = HashMap::new();
+        let mut enums = HashMap::new();
+
+        for file in files {
+            for message in &file.message_type {
+                let name = message.name.as_deref().unwrap_or("");
+                messages.insert(
+                    qualified_name(file.package.as_deref(), name),
+                    ResolvedMessage { file, message },
+                );
+            }
+            for enum_desc in &file.enum_type {
+                let name = enum_desc.name.as_deref().unwrap_or("");
+                enums.insert(
+                    qualified_name(file.package.as_deref(), name),
+                    ResolvedEnum { file, enum_desc },
+                );
+            }
+        }
+
+        Self { messages, enums }
+    }
+
+    /// Resolve a message reference as written in an option
+    ///
+    /// Accepts fully-qualified names (`.pkg.Message` or `pkg.Message`) and bare names,
+    /// which are looked up in `package` first and then across all packages when unique.
+    pub fn resolve_message(
+        &self,
+        name: &str,
+        package: Option<&str>,
+    ) -> Option<ResolvedMessage<'a>> {
+        lookup(&self.messages, name, package)
+    }
+
+    /// Resolve an enum reference, such as a field's `type_name`
+    ///
+    /// Follows the same rules as [`TypeResolver::resolve_message`].
+    pub fn resolve_enum(&self, name: &str, package: Option<&str>) -> Option<ResolvedEnum<'a>> {
+        lookup(&self.enums, name, package)
+    }
+}
+
+/// Look up a type by fully-qualified, package-relative, or unique simple name
+fn lookup<T: Copy>(index: &HashMap<String, T>, name: &str, package: Option<&str>) -> Option<T> {
+    let name = name.trim_start_matches('.');
+
+    if let Some(found) = index.get(name) {
+        return Some(*found);
+    }
+
+    if let Some(found) = index.get(&qualified_name(package, name)) {
+        return Some(*found);
+    }
+
+    // Fall back to a unique match on the simple name
+    let suffix = format!(".{}", name);
+    let mut candidates = index
+        .iter()
+        .filter(|(qualified, _)| qualified.ends_with(&suffix));
+    match (candidates.next(), candidates.next()) {
+        (Some((_, found)), None) => Some(*found),
+        _ => None,
+    }
+}
+
+/// Join a package and a message name into a fully-qualified name
+fn qualified_name(package: Option<&str>, name: &str) -> String {
+    match package {
+        Some(pkg) if !pkg.is_empty() => format!("{}.{}", pkg, name),
+        _ => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, package: &str, messages: &[&str]) -> FileDescriptorProto {
+        FileDescriptorProto {
+            name: Some(name.to_string()),
+            package: Some(package.to_string()),
+            message_type: messages
+                .iter()
+                .map(|m| DescriptorProto {
+                    name: Some(m.to_string()),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_message() {
+        let files = vec![
+            file("common.proto", "common", &["BaseColumns", "Audit"]),
+            file("app.proto", "app", &["User", "Audit"]),
+        ];
+        let resolver = TypeResolver::new(&files);
+
+        let found = resolver.resolve_message(".common.Audit", None).unwrap();
+        assert_eq!(found.file.name.as_deref(), Some("common.proto"));
+
+        // Bare names prefer the current package
+        let found = resolver.resolve_message("Audit", Some("app")).unwrap();
+        assert_eq!(found.file.name.as_deref(), Some("app.proto"));
+
+        // ...and otherwise fall back to a unique match anywhere
+        let found = resolver
+            .resolve_message("BaseColumns", Some("app"))
+            .unwrap();
+        assert_eq!(found.file.name.as_deref(), Some("common.proto"));
+
+        // Ambiguous or unknown names don't resolve
+        assert!(resolver.resolve_message("Audit", Some("other")).is_none());
+        assert!(resolver.resolve_message("Missing", Some("app")).is_none());
+    }
+}
@@ -4,19 +4,25 @@
 //! The generated traits mirror service RPCs and can be implemented by
 //! database storage layers or mocked for testing.
 
+use crate::codegen::entity::resolve_message_options;
+use crate::codegen::relation::related_module_path;
+use crate::codegen::{non_exhaustive_attribute, Context};
+use crate::options::seaorm::RpcMethodOptions;
 use crate::options::{
     get_cached_input_message_options, get_cached_rpc_method_options, get_cached_service_options,
-    parse_service_options,
+    parse_input_message_options, parse_rpc_method_options, parse_service_options,
 };
 use crate::GeneratorError;
 use heck::{ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::TokenStream;
 use prost_types::compiler::code_generator_response::File;
+use prost_types::field_descriptor_proto::{Label, Type};
 use prost_types::{FileDescriptorProto, MethodDescriptorProto, ServiceDescriptorProto};
 use quote::{format_ident, quote};
 
 /// Generate a Storage trait from a protobuf service
 pub fn generate(
+    ctx: &Context,
     file: &FileDescriptorProto,
     service: &ServiceDescriptorProto,
 ) -> Result<Option<File>, GeneratorError> {
@@ -44,16 +50,40 @@ pub fn generate(
         service_options.trait_name.clone()
     };
 
+    // Determine the error type wrapped by StorageError::Database
+    let db_error: syn::Type = if service_options.db_error.is_empty() {
+        syn::parse_quote!(sea_orm::DbErr)
+    } else {
+        syn::parse_str(&service_options.db_error).map_err(|e| {
+            GeneratorError::InvalidConfig(format!(
+                "Invalid db_error type \"{}\" on {}: {}",
+                service_options.db_error, service_name, e
+            ))
+        })?
+    };
+
     // Generate the output filename
     let module_name = trait_name.to_snake_case();
     let output_filename = format!(
         "{}/{}.rs",
-        file.package.as_deref().unwrap_or("").replace('.', "/"),
+        crate::generator::package_dir(&ctx.config, file.package.as_deref().unwrap_or("")),
         module_name
     );
 
     // Generate trait methods
-    let methods = generate_trait_methods(file_name, service_name, &service.method)?;
+    let methods = generate_trait_methods(
+        ctx,
+        file,
+        service_name,
+        &service.method,
+        service_options.by_ref,
+        ctx.config.emit_connection_trait_bound,
+        service_options.blocking,
+    )?;
+
+    // Generate FieldMask-driven update helpers and list response conversions
+    let masked_updates = generate_masked_updates(ctx, file, service_name, &service.method)?;
+    let list_conversions = generate_list_conversions(ctx, file, service_name, &service.method)?;
 
     // Build the trait
     let trait_ident = format_ident!("{}", trait_name);
@@ -62,6 +92,26 @@ pub fn generate(
     let module_doc = format!("Storage trait for {}", service_name);
     let trait_doc = format!("Storage trait mirroring {} RPCs", service_name);
 
+    // Blocking traits are plain traits carrying the runtime their impls block on
+    let (trait_attr, runtime_method) = if service_options.blocking {
+        (
+            quote! {
+                ///
+                /// Methods are blocking: implementations run SeaORM's async calls to
+                /// completion with `self.runtime().block_on(...)`. They must not be called
+                /// from within that runtime's async context, where `block_on` panics.
+            },
+            quote! {
+                /// Handle of the Tokio runtime the blocking methods drive SeaORM calls on
+                fn runtime(&self) -> &tokio::runtime::Handle;
+            },
+        )
+    } else {
+        (quote! { #[async_trait::async_trait] }, quote! {})
+    };
+
+    let non_exhaustive = non_exhaustive_attribute(ctx.config.non_exhaustive);
+
     let code = quote! {
         #![doc = #module_doc]
         //!
@@ -73,10 +123,11 @@ pub fn generate(
 
         /// Storage error type
         #[derive(Debug, thiserror::Error)]
+        #non_exhaustive
         pub enum StorageError {
             /// Database error
             #[error("database error: {0}")]
-            Database(#[from] sea_orm::DbErr),
+            Database(#[from] #db_error),
             /// Resource not found
             #[error("not found: {0}")]
             NotFound(String),
@@ -86,10 +137,16 @@ pub fn generate(
         }
 
         #[doc = #trait_doc]
-        #[async_trait::async_trait]
+        #trait_attr
         pub trait #trait_ident: Send + Sync {
+            #runtime_method
+
             #(#methods)*
         }
+
+        #(#masked_updates)*
+
+        #(#list_conversions)*
     };
 
     // Format the generated code
@@ -109,18 +166,28 @@ pub fn generate(
 }
 
 /// Generate trait method signatures from service methods
+///
+/// With `by_ref` unary requests are borrowed (`request: &CreateUser`); streamed
+/// requests are always owned. With `generic_conn` every method is generic over the
+/// connection it runs on (`conn: &C` where `C: ConnectionTrait`). With `blocking`
+/// methods are synchronous and streams become boxed iterators.
 fn generate_trait_methods(
-    file_name: &str,
+    ctx: &Context,
+    file: &FileDescriptorProto,
     service_name: &str,
     methods: &[MethodDescriptorProto],
+    by_ref: bool,
+    generic_conn: bool,
+    blocking: bool,
 ) -> Result<Vec<TokenStream>, GeneratorError> {
+    let file_name = file.name.as_deref().unwrap_or("");
     let mut result = Vec::new();
 
     for method in methods {
         let method_name = method.name.as_deref().unwrap_or("");
 
         // Check for method-level options
-        let method_options = get_cached_rpc_method_options(file_name, service_name, method_name);
+        let method_options = rpc_method_options(file_name, service_name, method);
 
         // Skip if marked
         if method_options.as_ref().map(|o| o.skip).unwrap_or(false) {
@@ -136,15 +203,59 @@ fn generate_trait_methods(
 
         // Extract input/output types - check for domain type first
         let raw_input_type = extract_type_name(method.input_type.as_deref());
-        let input_type = resolve_domain_type(file_name, &raw_input_type);
+        let input_type =
+            resolve_domain_type(ctx, file, method.input_type.as_deref(), &raw_input_type);
         let output_type = extract_type_name(method.output_type.as_deref());
 
         let method_ident = format_ident!("{}", rust_method_name);
         let input_ident = format_ident!("{}", input_type);
-        let output_ident = format_ident!("{}", output_type);
 
-        let method_token = quote! {
-            async fn #method_ident(&self, request: #input_ident) -> Result<#output_ident, StorageError>;
+        // google.protobuf.Empty carries no data: drop the request parameter and return ()
+        let output_tokens = if is_empty_type(method.output_type.as_deref()) {
+            quote! { () }
+        } else {
+            let output_ident = format_ident!("{}", output_type);
+            quote! { #output_ident }
+        };
+
+        // Streaming sides become boxed streams: inputs are consumed as the client sends
+        // them, outputs are produced lazily, so stream-returning methods are not async
+        let request_param = if method.client_streaming() && blocking {
+            quote! { , request: Box<dyn Iterator<Item = #input_ident> + Send> }
+        } else if method.client_streaming() {
+            quote! { , request: futures::stream::BoxStream<'static, #input_ident> }
+        } else if is_empty_type(method.input_type.as_deref()) {
+            quote! {}
+        } else if by_ref {
+            quote! { , request: &#input_ident }
+        } else {
+            quote! { , request: #input_ident }
+        };
+
+        let method_token = if blocking {
+            blocking_method(
+                &method_ident,
+                &request_param,
+                &output_tokens,
+                method.server_streaming(),
+                generic_conn,
+            )
+        } else {
+            match (method.server_streaming(), generic_conn) {
+                (true, false) => quote! {
+                    fn #method_ident(&self #request_param) -> futures::stream::BoxStream<'_, Result<#output_tokens, StorageError>>;
+                },
+                // The stream may borrow both the storage and the connection
+                (true, true) => quote! {
+                    fn #method_ident<'a, C: sea_orm::ConnectionTrait + Send + Sync>(&'a self, conn: &'a C #request_param) -> futures::stream::BoxStream<'a, Result<#output_tokens, StorageError>>;
+                },
+                (false, false) => quote! {
+                    async fn #method_ident(&self #request_param) -> Result<#output_tokens, StorageError>;
+                },
+                (false, true) => quote! {
+                    async fn #method_ident<C: sea_orm::ConnectionTrait + Send + Sync>(&self, conn: &C #request_param) -> Result<#output_tokens, StorageError>;
+                },
+            }
         };
 
         result.push(method_token);
@@ -153,21 +264,321 @@ fn generate_trait_methods(
     Ok(result)
 }
 
+/// Generate a blocking trait method signature
+///
+/// Server streams are returned as boxed iterators, which may borrow the storage and,
+/// with `generic_conn`, the connection.
+fn blocking_method(
+    method_ident: &proc_macro2::Ident,
+    request_param: &TokenStream,
+    output_tokens: &TokenStream,
+    server_streaming: bool,
+    generic_conn: bool,
+) -> TokenStream {
+    match (server_streaming, generic_conn) {
+        (true, false) => quote! {
+            fn #method_ident(&self #request_param) -> Box<dyn Iterator<Item = Result<#output_tokens, StorageError>> + Send + '_>;
+        },
+        (true, true) => quote! {
+            fn #method_ident<'a, C: sea_orm::ConnectionTrait + Send + Sync>(&'a self, conn: &'a C #request_param) -> Box<dyn Iterator<Item = Result<#output_tokens, StorageError>> + Send + 'a>;
+        },
+        (false, false) => quote! {
+            fn #method_ident(&self #request_param) -> Result<#output_tokens, StorageError>;
+        },
+        (false, true) => quote! {
+            fn #method_ident<C: sea_orm::ConnectionTrait + Send + Sync>(&self, conn: &C #request_param) -> Result<#output_tokens, StorageError>;
+        },
+    }
+}
+
+/// Look up a method's `seaorm.rpc` options, falling back to uninterpreted options
+fn rpc_method_options(
+    file_name: &str,
+    service_name: &str,
+    method: &MethodDescriptorProto,
+) -> Option<RpcMethodOptions> {
+    let method_name = method.name.as_deref().unwrap_or("");
+    get_cached_rpc_method_options(file_name, service_name, method_name)
+        .or_else(|| parse_rpc_method_options(method))
+}
+
+/// Generate `<method>_masked` helpers for methods marked `masked_update`
+///
+/// The request must carry a `google.protobuf.FieldMask` and a message field holding an
+/// entity generated with `field_mask: true`. The helper turns that entity's model into
+/// an ActiveModel where only the masked columns are `Set`; everything else stays
+/// `Unchanged`, so saving it writes exactly the masked fields.
+fn generate_masked_updates(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    service_name: &str,
+    methods: &[MethodDescriptorProto],
+) -> Result<Vec<TokenStream>, GeneratorError> {
+    let file_name = file.name.as_deref().unwrap_or("");
+    let package = file.package.as_deref();
+    let mut result = Vec::new();
+
+    for method in methods {
+        let method_name = method.name.as_deref().unwrap_or("");
+        let Some(method_options) = rpc_method_options(file_name, service_name, method)
+            .filter(|o| o.masked_update && !o.skip)
+        else {
+            continue;
+        };
+
+        let request = method
+            .input_type
+            .as_deref()
+            .and_then(|name| ctx.resolver.resolve_message(name, package))
+            .ok_or_else(|| {
+                GeneratorError::InvalidConfig(format!(
+                    "{}.{} sets masked_update, but its request message could not be resolved",
+                    service_name, method_name
+                ))
+            })?;
+
+        let mask_field = request
+            .message
+            .field
+            .iter()
+            .find(|f| f.type_name.as_deref() == Some(".google.protobuf.FieldMask"))
+            .and_then(|f| f.name.as_deref())
+            .ok_or_else(|| {
+                GeneratorError::InvalidConfig(format!(
+                    "{}.{} sets masked_update, but its request has no google.protobuf.FieldMask field",
+                    service_name, method_name
+                ))
+            })?;
+
+        // The first message field holding an entity is the one being updated
+        let entity = request
+            .message
+            .field
+            .iter()
+            .filter_map(|f| f.type_name.as_deref())
+            .filter_map(|name| ctx.resolver.resolve_message(name, package))
+            .find_map(|resolved| {
+                resolve_message_options(resolved.file, resolved.message)
+                    .filter(|o| !o.skip)
+                    .map(|o| (resolved, o))
+            });
+        let (entity, entity_options) = entity.ok_or_else(|| {
+            GeneratorError::InvalidConfig(format!(
+                "{}.{} sets masked_update, but its request has no entity field to update",
+                service_name, method_name
+            ))
+        })?;
+        let entity_name = entity.message.name.as_deref().unwrap_or("");
+        if !entity_options.field_mask {
+            return Err(GeneratorError::InvalidConfig(format!(
+                "{}.{} sets masked_update, but {} is not generated with field_mask: true",
+                service_name, method_name, entity_name
+            )));
+        }
+
+        let rust_method_name = Some(method_options.method_name)
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| method_name.to_snake_case());
+        let helper_ident = format_ident!("{}_masked", rust_method_name);
+        let mask_ident = format_ident!("{}", mask_field.to_snake_case());
+        let entity_module: syn::Path =
+            syn::parse_str(&related_module_path(ctx, package, entity_name)).map_err(|e| {
+                GeneratorError::CodeGenError(format!(
+                    "Invalid module path for {}: {}",
+                    entity_name, e
+                ))
+            })?;
+        let unknown_path = format!("unknown field in {}: {{}}", mask_field);
+        let doc = format!(
+            "Build the ActiveModel for {}, setting only the columns named in `{}`",
+            method_name, mask_field
+        );
+
+        result.push(quote! {
+            #[doc = #doc]
+            ///
+            /// Unmasked columns stay `Unchanged`, so the update writes exactly the masked
+            /// fields. Unknown paths are rejected as invalid arguments.
+            pub fn #helper_ident(
+                model: #entity_module::Model,
+                #mask_ident: &[String],
+            ) -> Result<#entity_module::ActiveModel, StorageError> {
+                use sea_orm::{ActiveModelTrait, IntoActiveModel};
+
+                let mut active = model.into_active_model();
+                for path in #mask_ident {
+                    let column = #entity_module::column_for_field_path(path).ok_or_else(|| {
+                        StorageError::InvalidArgument(format!(#unknown_path, path))
+                    })?;
+                    active.reset(column);
+                }
+                Ok(active)
+            }
+        });
+    }
+
+    Ok(result)
+}
+
+/// Generate conversions for responses of methods marked `list_response`
+///
+/// The response must hold one repeated entity field (the page of items) and one integer
+/// field named `total...` (the total count). It converts from `(Vec<Model>, i64)`, as
+/// returned by a paginator, and back; items go through the `From` conversions between
+/// the entity's `Model` and its proto message, which the caller provides. A response
+/// shared by several methods gets one pair of conversions.
+fn generate_list_conversions(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    service_name: &str,
+    methods: &[MethodDescriptorProto],
+) -> Result<Vec<TokenStream>, GeneratorError> {
+    let file_name = file.name.as_deref().unwrap_or("");
+    let package = file.package.as_deref();
+    let mut seen = Vec::new();
+    let mut result = Vec::new();
+
+    for method in methods {
+        let method_name = method.name.as_deref().unwrap_or("");
+        if !rpc_method_options(file_name, service_name, method)
+            .map(|o| o.list_response && !o.skip)
+            .unwrap_or(false)
+        {
+            continue;
+        }
+        let invalid = |reason: &str| {
+            GeneratorError::InvalidConfig(format!(
+                "{}.{} sets list_response, but {}",
+                service_name, method_name, reason
+            ))
+        };
+
+        let output_type = method.output_type.as_deref().unwrap_or("");
+        if seen.contains(&output_type) {
+            continue;
+        }
+        seen.push(output_type);
+        let response = ctx
+            .resolver
+            .resolve_message(output_type, package)
+            .ok_or_else(|| invalid("its response message could not be resolved"))?;
+
+        // The page of items: a repeated field holding an entity
+        let items = response
+            .message
+            .field
+            .iter()
+            .filter(|f| f.label() == Label::Repeated && f.r#type() == Type::Message)
+            .find(|f| {
+                f.type_name
+                    .as_deref()
+                    .and_then(|name| ctx.resolver.resolve_message(name, package))
+                    .and_then(|item| resolve_message_options(item.file, item.message))
+                    .map(|o| !o.skip)
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| invalid("its response has no repeated entity field"))?;
+        let total = response
+            .message
+            .field
+            .iter()
+            .filter(|f| f.label() != Label::Repeated)
+            .filter(|f| f.name.as_deref().unwrap_or("").starts_with("total"))
+            .find_map(|f| {
+                let rust_type = match f.r#type() {
+                    Type::Int32 | Type::Sint32 | Type::Sfixed32 => "i32",
+                    Type::Int64 | Type::Sint64 | Type::Sfixed64 => "i64",
+                    Type::Uint32 | Type::Fixed32 => "u32",
+                    Type::Uint64 | Type::Fixed64 => "u64",
+                    _ => return None,
+                };
+                Some((f.name.as_deref().unwrap_or(""), rust_type))
+            })
+            .ok_or_else(|| invalid("its response has no integer total field"))?;
+
+        let item_type_name = items.type_name.as_deref().unwrap_or("");
+        let entity_name = extract_type_name(Some(item_type_name));
+        let entity_module: syn::Path = syn::parse_str(&related_module_path(
+            ctx,
+            package,
+            item_type_name.rsplit('.').next().unwrap_or(item_type_name),
+        ))
+        .map_err(|_| invalid("its item entity has no valid module path"))?;
+        let response_ident = format_ident!("{}", extract_type_name(Some(output_type)));
+        let items_ident = format_ident!("{}", items.name.as_deref().unwrap_or("").to_snake_case());
+        let total_ident = format_ident!("{}", total.0.to_snake_case());
+        let total_type = format_ident!("{}", total.1);
+        let to_doc = format!(
+            "Build a {} from a page of {} models and the total count",
+            response_ident, entity_name
+        );
+        let from_doc = format!(
+            "Split a {} into its {} models and the total count",
+            response_ident, entity_name
+        );
+
+        result.push(quote! {
+            #[doc = #to_doc]
+            impl From<(Vec<#entity_module::Model>, i64)> for #response_ident {
+                fn from((models, total): (Vec<#entity_module::Model>, i64)) -> Self {
+                    Self {
+                        #items_ident: models.into_iter().map(Into::into).collect(),
+                        #total_ident: #total_type::try_from(total).unwrap_or(#total_type::MAX),
+                        ..Default::default()
+                    }
+                }
+            }
+
+            #[doc = #from_doc]
+            impl From<#response_ident> for (Vec<#entity_module::Model>, i64) {
+                fn from(response: #response_ident) -> Self {
+                    (
+                        response.#items_ident.into_iter().map(Into::into).collect(),
+                        i64::try_from(response.#total_ident).unwrap_or(i64::MAX),
+                    )
+                }
+            }
+        });
+    }
+
+    Ok(result)
+}
+
 /// Resolve a message type to its domain type if one exists
 ///
 /// If the message has `seaorm.input_message` options with a `domain_type`,
 /// returns the domain type name. Otherwise returns the original type.
-fn resolve_domain_type(file_name: &str, message_name: &str) -> String {
+fn resolve_domain_type(
+    ctx: &Context,
+    file: &FileDescriptorProto,
+    type_name: Option<&str>,
+    message_name: &str,
+) -> String {
     // Look up input_message options for this message
+    let file_name = file.name.as_deref().unwrap_or("");
     if let Some(opts) = get_cached_input_message_options(file_name, message_name) {
         if !opts.domain_type.is_empty() {
             return opts.domain_type;
         }
     }
+    // Fall back to uninterpreted options on the declaring message
+    let declared = type_name
+        .and_then(|name| ctx.resolver.resolve_message(name, file.package.as_deref()))
+        .and_then(|resolved| parse_input_message_options(resolved.message));
+    if let Some(opts) = declared {
+        if !opts.domain_type.is_empty() {
+            return opts.domain_type;
+        }
+    }
     // No domain type specified, use original
     message_name.to_string()
 }
 
+/// Whether a method input/output type is `google.protobuf.Empty`
+fn is_empty_type(type_name: Option<&str>) -> bool {
+    type_name == Some(".google.protobuf.Empty")
+}
+
 /// Extract a Rust type name from a protobuf type path
 ///
 /// Converts ".package.name.TypeName" to "TypeName"
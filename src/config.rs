@@ -0,0 +1,372 @@
+//! Plugin parameter parsing
+//!
+//! Parameters are passed to the plugin via `--seaorm_opt=key=value` (protoc) or
+//! `opt: key=value` (buf) and arrive as a single comma-separated string in
+//! `CodeGeneratorRequest::parameter`.
+
+use crate::GeneratorError;
+
+/// Target database backend, selected with the `backend` parameter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// PostgreSQL (the default)
+    #[default]
+    Postgres,
+    /// MySQL / MariaDB
+    Mysql,
+    /// SQLite
+    Sqlite,
+}
+
+impl Backend {
+    /// Column type for values stored as JSON: `JsonBinary` (JSONB) on Postgres,
+    /// plain `Json` elsewhere
+    pub fn json_column_type(self) -> &'static str {
+        match self {
+            Backend::Postgres => "JsonBinary",
+            Backend::Mysql | Backend::Sqlite => "Json",
+        }
+    }
+}
+
+/// Treatment of message fields with neither `embed` nor a relation, selected with the
+/// `default_message_handling` parameter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MessageHandling {
+    /// Store the message as a JSON column (the default)
+    #[default]
+    Json,
+    /// Generate a `has_one` relation to the message's entity instead of a column
+    Relation,
+    /// Reject the field
+    Error,
+}
+
+/// Where defaulted column names come from, selected with the `column_from` parameter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColumnSource {
+    /// The snake_case field name (the default)
+    #[default]
+    Name,
+    /// The field's `json_name` (e.g. `createdAt`), falling back to the field name
+    JsonName,
+}
+
+/// How proto packages map to Rust modules, selected with the `package_modules` parameter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PackageModules {
+    /// One module per package segment, `acme.billing.v1` as `acme::billing::v1` (the
+    /// default)
+    #[default]
+    Nested,
+    /// Nested, without a trailing version segment: `acme::billing`
+    StripVersion,
+    /// A single module named after the whole package: `acme_billing_v1`
+    Flatten,
+}
+
+/// Global generator configuration parsed from plugin parameters
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    /// Predicate for a `#![cfg(...)]` attribute gating every generated file
+    /// (e.g. `feature = "db"`)
+    pub cfg: Option<String>,
+    /// Emit a Graphviz DOT file per package describing entities and their relations
+    pub emit_schema_graph: bool,
+    /// Emit a `sea_orm_migration` migration per entity
+    pub emit_migrations: bool,
+    /// Name of a single file to assemble all generated Rust code into, with nested
+    /// modules mirroring packages
+    pub single_file: Option<String>,
+    /// Module path under which package modules live (e.g. `crate::entities`), used to
+    /// address entities in other packages instead of `super::`
+    pub module_root: Option<String>,
+    /// Prefix prepended to every generated table name (e.g. `app_`)
+    pub table_prefix: Option<String>,
+    /// Template for defaulted column names, with `{field}` standing for the field's
+    /// column name (e.g. `c_{field}`)
+    pub column_name_template: Option<String>,
+    /// Fail when extension data and uninterpreted options disagree for the same element
+    pub strict_options: bool,
+    /// Replace glob imports in generated modules with explicit named imports
+    pub no_prelude_glob: bool,
+    /// Drop SeaORM's prelude glob and write its names as fully-qualified paths
+    pub no_prelude: bool,
+    /// Acronyms kept as a single word when deriving column names (e.g. `ID`, `URL`)
+    pub acronyms: Vec<String>,
+    /// Database backend the generated entities target
+    pub backend: Backend,
+    /// Make storage trait methods generic over a per-call `ConnectionTrait` argument
+    pub emit_connection_trait_bound: bool,
+    /// Store 32-bit integer primary keys as `i64` columns
+    pub widen_pk: bool,
+    /// Mark generated enums and error types `#[non_exhaustive]`
+    pub non_exhaustive: bool,
+    /// Emit expanded `Entity`/`Column`/`PrimaryKey`/`Relation` impls instead of `DeriveEntityModel`
+    pub manual_entity: bool,
+    /// Derive `specta::Type` on generated models, enums and domain types behind a `specta` feature
+    pub derive_specta: bool,
+    /// Implement `arbitrary::Arbitrary` for generated models, enums and domain types behind an
+    /// `arbitrary` feature
+    pub derive_arbitrary: bool,
+    /// Derive serde's `Serialize` and `Deserialize` on generated models
+    pub derive_serde: bool,
+    /// Suppress every generated `#[cfg(test)]` module, set with `emit_tests=false`
+    pub no_tests: bool,
+    /// Longest table or column name the database accepts (e.g. 63 on Postgres); longer
+    /// names are shortened with a hash suffix
+    pub max_identifier_length: Option<usize>,
+    /// What to do with message fields that set neither `embed` nor a relation
+    pub default_message_handling: MessageHandling,
+    /// Source of column names for fields without an explicit `column_name`
+    pub column_from: ColumnSource,
+    /// Module layout of the generated packages
+    pub package_modules: PackageModules,
+}
+
+impl Config {
+    /// Parse the plugin parameter string
+    ///
+    /// Entries are separated by top-level commas; commas nested inside parentheses
+    /// or quotes (as in `cfg=any(feature = "a", feature = "b")`) are preserved.
+    pub fn parse(parameter: Option<&str>) -> Result<Self, GeneratorError> {
+        let mut config = Config::default();
+
+        for entry in split_parameters(parameter.unwrap_or("")) {
+            let (key, value) = match entry.split_once('=') {
+                Some((k, v)) => (k.trim(), v.trim()),
+                None => (entry, "true"),
+            };
+
+            match key {
+                "cfg" => {
+                    if value.is_empty() {
+                        return Err(GeneratorError::InvalidConfig(
+                            "cfg parameter requires a predicate, e.g. cfg=feature=\"db\""
+                                .to_string(),
+                        ));
+                    }
+                    config.cfg = Some(value.to_string());
+                }
+                "module_root" => {
+                    if value.is_empty() {
+                        return Err(GeneratorError::InvalidConfig(
+                            "module_root parameter requires a path, e.g. module_root=crate::entities"
+                                .to_string(),
+                        ));
+                    }
+                    config.module_root = Some(value.trim_end_matches("::").to_string());
+                }
+                "acronyms" => {
+                    config.acronyms = value
+                        .split(':')
+                        .map(str::trim)
+                        .filter(|a| !a.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                }
+                "backend" => {
+                    config.backend = match value {
+                        "postgres" | "postgresql" => Backend::Postgres,
+                        "mysql" => Backend::Mysql,
+                        "sqlite" => Backend::Sqlite,
+                        other => {
+                            return Err(GeneratorError::InvalidConfig(format!(
+                                "backend expects postgres, mysql or sqlite, got \"{}\"",
+                                other
+                            )))
+                        }
+                    }
+                }
+                "default_message_handling" => {
+                    config.default_message_handling = match value {
+                        "json" => MessageHandling::Json,
+                        "relation" => MessageHandling::Relation,
+                        "error" => MessageHandling::Error,
+                        other => {
+                            return Err(GeneratorError::InvalidConfig(format!(
+                            "default_message_handling expects json, relation or error, got \"{}\"",
+                            other
+                        )))
+                        }
+                    }
+                }
+                "column_from" => {
+                    config.column_from = match value {
+                        "name" => ColumnSource::Name,
+                        "json_name" => ColumnSource::JsonName,
+                        other => {
+                            return Err(GeneratorError::InvalidConfig(format!(
+                                "column_from expects name or json_name, got \"{}\"",
+                                other
+                            )))
+                        }
+                    }
+                }
+                "package_modules" => {
+                    config.package_modules = match value {
+                        "nested" => PackageModules::Nested,
+                        "strip_version" => PackageModules::StripVersion,
+                        "flatten" => PackageModules::Flatten,
+                        other => {
+                            return Err(GeneratorError::InvalidConfig(format!(
+                            "package_modules expects nested, strip_version or flatten, got \"{}\"",
+                            other
+                        )))
+                        }
+                    }
+                }
+                "single_file" => {
+                    if !value.ends_with(".rs") {
+                        return Err(GeneratorError::InvalidConfig(format!(
+                            "single_file must name a .rs file, got \"{}\"",
+                            value
+                        )));
+                    }
+                    config.single_file = Some(value.to_string())
+                }
+                "table_prefix" => {
+                    config.table_prefix = Some(value.to_string()).filter(|p| !p.is_empty())
+                }
+                "column_name_template" => {
+                    if !value.contains("{field}") {
+                        return Err(GeneratorError::InvalidConfig(format!(
+                            "column_name_template must contain {{field}}, got \"{}\"",
+                            value
+                        )));
+                    }
+                    config.column_name_template = Some(value.to_string())
+                }
+                "max_identifier_length" => {
+                    // The shortened name keeps a 9-character hash suffix
+                    match value.parse::<usize>() {
+                        Ok(len) if len >= 16 => config.max_identifier_length = Some(len),
+                        _ => {
+                            return Err(GeneratorError::InvalidConfig(format!(
+                                "max_identifier_length expects a length of at least 16, got \"{}\"",
+                                value
+                            )))
+                        }
+                    }
+                }
+                "emit_schema_graph" => config.emit_schema_graph = parse_bool(key, value)?,
+                "emit_migrations" => config.emit_migrations = parse_bool(key, value)?,
+                "strict_options" => config.strict_options = parse_bool(key, value)?,
+                "no_prelude_glob" => config.no_prelude_glob = parse_bool(key, value)?,
+                "no_prelude" => config.no_prelude = parse_bool(key, value)?,
+                "emit_connection_trait_bound" => {
+                    config.emit_connection_trait_bound = parse_bool(key, value)?
+                }
+                "widen_pk" => config.widen_pk = parse_bool(key, value)?,
+                "non_exhaustive" => config.non_exhaustive = parse_bool(key, value)?,
+                "manual_entity" => config.manual_entity = parse_bool(key, value)?,
+                "derive_serde" => config.derive_serde = parse_bool(key, value)?,
+                "derive_specta" => config.derive_specta = parse_bool(key, value)?,
+                "derive_arbitrary" => config.derive_arbitrary = parse_bool(key, value)?,
+                "emit_tests" => config.no_tests = !parse_bool(key, value)?,
+                // Accepted for compatibility with buf configurations; options are always retained
+                "retain_options" => {}
+                other => {
+                    return Err(GeneratorError::InvalidConfig(format!(
+                        "Unknown plugin parameter: {}",
+                        other
+                    )))
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// Parse a boolean parameter value
+fn parse_bool(key: &str, value: &str) -> Result<bool, GeneratorError> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        other => Err(GeneratorError::InvalidConfig(format!(
+            "{} expects true or false, got \"{}\"",
+            key, other
+        ))),
+    }
+}
+
+/// Split a parameter string on top-level commas
+fn split_parameters(parameter: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut depth: i32 = 0;
+    let mut in_quotes = false;
+
+    for (i, c) in parameter.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '(' if !in_quotes => depth += 1,
+            ')' if !in_quotes => depth = depth.saturating_sub(1),
+            ',' if !in_quotes && depth == 0 => {
+                parts.push(&parameter[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&parameter[start..]);
+
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_empty_parameter() {
+        assert_eq!(Config::parse(None).unwrap(), Config::default());
+        assert_eq!(Config::parse(Some("")).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_parse_cfg_with_nested_commas() {
+        let config = Config::parse(Some(
+            "retain_options=true,cfg=any(feature=\"a\", feature=\"b\")",
+        ))
+        .unwrap();
+        assert_eq!(
+            config.cfg.as_deref(),
+            Some("any(feature=\"a\", feature=\"b\")")
+        );
+    }
+
+    #[test]
+    fn test_parse_bool_parameter() {
+        assert!(
+            Config::parse(Some("emit_schema_graph"))
+                .unwrap()
+                .emit_schema_graph
+        );
+        assert!(
+            !Config::parse(Some("emit_schema_graph=false"))
+                .unwrap()
+                .emit_schema_graph
+        );
+        assert!(Config::parse(Some("emit_schema_graph=yes")).is_err());
+    }
+
+    #[test]
+    fn test_parse_acronyms() {
+        let config = Config::parse(Some("acronyms=ID:URL:API")).unwrap();
+        assert_eq!(config.acronyms, vec!["ID", "URL", "API"]);
+    }
+
+    #[test]
+    fn test_parse_unknown_parameter() {
+        assert!(matches!(
+            Config::parse(Some("bogus=1")),
+            Err(GeneratorError::InvalidConfig(_))
+        ));
+    }
+}
@@ -3,13 +3,33 @@
 //! This module coordinates the overall code generation process,
 //! iterating through proto files and generating SeaORM entities, enums, and storage traits.
 
+use crate::codegen::relation::{generate_relation_from_def, with_auto_reverse};
+use crate::codegen::Context;
+use crate::config::{Config, PackageModules};
+use crate::options::seaorm::RelationType;
 use crate::GeneratorError;
+use heck::{ToSnakeCase, ToUpperCamelCase};
 use prost::Message;
+use prost_types::compiler::code_generator_response::File;
 use prost_types::compiler::{CodeGeneratorRequest, CodeGeneratorResponse};
+use prost_types::field_descriptor_proto::Type;
+use prost_types::uninterpreted_option::NamePart;
+use prost_types::{DescriptorProto, FileDescriptorProto, UninterpretedOption};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 /// Generate SeaORM entities and enums from a CodeGeneratorRequest
-pub fn generate(request: CodeGeneratorRequest) -> Result<CodeGeneratorResponse, GeneratorError> {
+pub fn generate(
+    mut request: CodeGeneratorRequest,
+) -> Result<CodeGeneratorResponse, GeneratorError> {
+    let config = Config::parse(request.parameter.as_deref())?;
+    apply_auto_entities(&mut request);
+    check_duplicate_table_names(&request)?;
+    check_package_modules(&config, &request)?;
+
+    let ctx = Context::new(config, &request.proto_file);
+
     let mut files = Vec::new();
+    let mut migrations = Vec::new();
 
     // Process each file that was requested for generation
     for file_name in &request.file_to_generate {
@@ -22,33 +42,93 @@ pub fn generate(request: CodeGeneratorRequest) -> Result<CodeGeneratorResponse,
                 GeneratorError::CodeGenError(format!("File descriptor not found: {}", file_name))
             })?;
 
+        if ctx.config.strict_options {
+            crate::options::check_option_divergence(file_descriptor)?;
+        }
+
         // Process each message in the file
         for message in &file_descriptor.message_type {
             // Generate entity if has model options
-            if let Some(generated) = crate::codegen::generate_entity(file_descriptor, message)? {
+            if let Some(generated) =
+                crate::codegen::generate_entity(&ctx, file_descriptor, message)?
+            {
                 files.push(generated);
             }
+            if ctx.config.emit_migrations {
+                if let Some(generated) =
+                    crate::codegen::generate_migration(&ctx, file_descriptor, message)?
+                {
+                    files.push(generated);
+                    migrations.push((file_descriptor, message));
+                }
+            }
             // Generate domain type if has input options
-            if let Some(generated) = crate::codegen::generate_domain(file_descriptor, message)? {
+            if let Some(generated) =
+                crate::codegen::generate_domain(&ctx, file_descriptor, message)?
+            {
                 files.push(generated);
             }
         }
 
         // Process each enum in the file
         for enum_desc in &file_descriptor.enum_type {
-            if let Some(generated) = crate::codegen::generate_enum(file_descriptor, enum_desc)? {
+            if let Some(generated) =
+                crate::codegen::generate_enum(&ctx, file_descriptor, enum_desc)?
+            {
                 files.push(generated);
             }
         }
 
         // Process each service in the file
         for service in &file_descriptor.service {
-            if let Some(generated) = crate::codegen::generate_service(file_descriptor, service)? {
+            if let Some(generated) =
+                crate::codegen::generate_service(&ctx, file_descriptor, service)?
+            {
                 files.push(generated);
             }
         }
     }
 
+    files.extend(support_modules(&ctx.config, &request));
+    files.extend(generate_migrators(&ctx, &migrations)?);
+
+    if ctx.config.no_prelude {
+        for file in &mut files {
+            if let Some(content) = file.content.take() {
+                file.content = Some(crate::codegen::imports::qualify_sea_orm_paths(&content));
+            }
+        }
+    }
+
+    if ctx.config.no_prelude_glob {
+        let prelude_names = package_prelude_names(&request);
+        for file in &mut files {
+            if let Some(content) = file.content.take() {
+                file.content = Some(crate::codegen::imports::expand_glob_imports(
+                    &content,
+                    &prelude_names,
+                ));
+            }
+        }
+    }
+
+    // Gate every generated module behind the configured cfg predicate
+    if let Some(ref cfg) = ctx.config.cfg {
+        for file in &mut files {
+            if let Some(content) = file.content.take() {
+                file.content = Some(format!("#![cfg({})]\n\n{}", cfg, content));
+            }
+        }
+    }
+
+    if let Some(ref name) = ctx.config.single_file {
+        files = assemble_single_file(name, files)?;
+    }
+
+    if ctx.config.emit_schema_graph {
+        files.extend(generate_schema_graphs(&ctx.config, &request));
+    }
+
     Ok(CodeGeneratorResponse {
         file: files,
         error: None,
@@ -56,6 +136,770 @@ pub fn generate(request: CodeGeneratorRequest) -> Result<CodeGeneratorResponse,
     })
 }
 
+/// Rust module segments of a proto package, per the `package_modules` parameter
+///
+/// `acme.billing.v1` is `acme::billing::v1` by default, `acme::billing` with
+/// `strip_version` and `acme_billing_v1` with `flatten`. The empty package has none.
+pub fn package_modules(config: &Config, package: &str) -> Vec<String> {
+    let mut segments: Vec<&str> = package.split('.').filter(|s| !s.is_empty()).collect();
+    match config.package_modules {
+        PackageModules::Nested => {}
+        PackageModules::StripVersion => {
+            if segments.len() > 1 && segments.last().is_some_and(|s| is_version_segment(s)) {
+                segments.pop();
+            }
+        }
+        PackageModules::Flatten => {
+            if segments.is_empty() {
+                return Vec::new();
+            }
+            return vec![segments.join("_")];
+        }
+    }
+    segments.into_iter().map(str::to_string).collect()
+}
+
+/// Directory of a package's generated files, such as `acme/billing/v1`
+pub fn package_dir(config: &Config, package: &str) -> String {
+    package_modules(config, package).join("/")
+}
+
+/// Whether a package segment is an API version such as `v1`, `v2beta` or `v1alpha2`
+fn is_version_segment(segment: &str) -> bool {
+    let Some(rest) = segment.strip_prefix('v') else {
+        return false;
+    };
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 {
+        return false;
+    }
+    let label = rest[digits..].trim_end_matches(|c: char| c.is_ascii_digit());
+    label.is_empty() || label.chars().all(|c| c.is_ascii_lowercase())
+}
+
+/// Reject packages that `package_modules` would write into the same module
+fn check_package_modules(
+    config: &Config,
+    request: &CodeGeneratorRequest,
+) -> Result<(), GeneratorError> {
+    let mut seen: BTreeMap<String, &str> = BTreeMap::new();
+    for file in files_to_generate(request) {
+        let package = file.package.as_deref().unwrap_or("");
+        let dir = package_dir(config, package);
+        match seen.get(&dir) {
+            Some(other) if *other != package => {
+                return Err(GeneratorError::InvalidConfig(format!(
+                    "packages {} and {} both map to module {}; use a different package_modules",
+                    other,
+                    package,
+                    dir.replace('/', "::")
+                )));
+            }
+            _ => {
+                seen.insert(dir, package);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run every semantic check over a request without producing any output
+///
+/// Unlike `generate`, which stops at the first error, this keeps going and returns
+/// every problem found: invalid parameters, duplicate table names, everything the
+/// generators (migrations included, under `emit_migrations`) reject, plus entities
+/// without a primary key and dangling relations.
+pub fn validate(request: &CodeGeneratorRequest) -> Result<(), Vec<GeneratorError>> {
+    let mut errors = Vec::new();
+
+    let config = Config::parse(request.parameter.as_deref()).unwrap_or_else(|e| {
+        errors.push(e);
+        Config::default()
+    });
+    let mut request = request.clone();
+    apply_auto_entities(&mut request);
+    let request = &request;
+    if let Err(e) = check_duplicate_table_names(request) {
+        errors.push(e);
+    }
+    if let Err(e) = check_package_modules(&config, request) {
+        errors.push(e);
+    }
+
+    let ctx = Context::new(config, &request.proto_file);
+    let mut migrations = Vec::new();
+
+    for file in files_to_generate(request) {
+        if ctx.config.strict_options {
+            if let Err(e) = crate::options::check_option_divergence(file) {
+                errors.push(e);
+            }
+        }
+
+        for message in &file.message_type {
+            if let Err(e) = crate::codegen::generate_entity(&ctx, file, message) {
+                errors.push(e);
+            }
+            if ctx.config.emit_migrations {
+                match crate::codegen::generate_migration(&ctx, file, message) {
+                    Ok(Some(_)) => migrations.push((file, message)),
+                    Ok(None) => {}
+                    Err(e) => errors.push(e),
+                }
+            }
+            if let Err(e) = crate::codegen::generate_domain(&ctx, file, message) {
+                errors.push(e);
+            }
+            errors.extend(crate::codegen::entity::check_entity(&ctx, file, message));
+        }
+
+        for enum_desc in &file.enum_type {
+            if let Err(e) = crate::codegen::generate_enum(&ctx, file, enum_desc) {
+                errors.push(e);
+            }
+        }
+
+        for service in &file.service {
+            if let Err(e) = crate::codegen::generate_service(&ctx, file, service) {
+                errors.push(e);
+            }
+        }
+    }
+
+    if let Err(e) = generate_migrators(&ctx, &migrations) {
+        errors.push(e);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// The files of a request that were requested for generation
+fn files_to_generate(
+    request: &CodeGeneratorRequest,
+) -> impl Iterator<Item = &prost_types::FileDescriptorProto> {
+    request.proto_file.iter().filter(|f| {
+        request
+            .file_to_generate
+            .iter()
+            .any(|n| f.name.as_ref() == Some(n))
+    })
+}
+
+/// Annotate the messages of `auto_entities` services as if they carried seaorm options
+///
+/// Response messages with an `id` field become entities keyed on `id`; responses
+/// without one contribute the messages they carry instead, so
+/// `ListUsersResponse { repeated User users }` yields a `User` entity. Request
+/// messages become domain inputs named after the request minus its suffix
+/// (`CreateUserRequest` becomes `CreateUser`). Messages that already carry seaorm
+/// options are left as written.
+fn apply_auto_entities(request: &mut CodeGeneratorRequest) {
+    // Index top-level messages by fully-qualified name
+    let mut index: HashMap<String, (usize, usize)> = HashMap::new();
+    for (file_idx, file) in request.proto_file.iter().enumerate() {
+        for (msg_idx, message) in file.message_type.iter().enumerate() {
+            let name = message.name.as_deref().unwrap_or("");
+            let full_name = match file.package.as_deref() {
+                Some(pkg) if !pkg.is_empty() => format!("{}.{}", pkg, name),
+                _ => name.to_string(),
+            };
+            index.insert(full_name, (file_idx, msg_idx));
+        }
+    }
+
+    let files = &request.proto_file;
+    let lookup = |type_name: Option<&str>| -> Option<(usize, usize)> {
+        index.get(type_name?.trim_start_matches('.')).copied()
+    };
+    let message_at = |(file_idx, msg_idx): (usize, usize)| &files[file_idx].message_type[msg_idx];
+    let has_id = |message: &DescriptorProto| {
+        message
+            .field
+            .iter()
+            .any(|f| f.name.as_deref() == Some("id"))
+    };
+
+    let mut entities = Vec::new();
+    let mut inputs = Vec::new();
+    for file in files_to_generate(request) {
+        let file_name = file.name.as_deref().unwrap_or("");
+        for service in &file.service {
+            let service_name = service.name.as_deref().unwrap_or("");
+            let auto = crate::options::get_cached_service_options(file_name, service_name)
+                .or_else(|| crate::options::parse_service_options(service))
+                .map(|o| o.auto_entities && !o.skip)
+                .unwrap_or(false);
+            if !auto {
+                continue;
+            }
+
+            for method in &service.method {
+                if let Some(output) = lookup(method.output_type.as_deref()) {
+                    if has_id(message_at(output)) {
+                        entities.push(output);
+                    } else {
+                        entities.extend(
+                            message_at(output)
+                                .field
+                                .iter()
+                                .filter(|f| f.r#type() == Type::Message)
+                                .filter_map(|f| lookup(f.type_name.as_deref()))
+                                .filter(|&carried| has_id(message_at(carried))),
+                        );
+                    }
+                }
+                inputs.extend(lookup(method.input_type.as_deref()));
+            }
+        }
+    }
+    inputs.retain(|input| !entities.contains(input));
+
+    for (file_idx, msg_idx) in entities {
+        let file = &mut request.proto_file[file_idx];
+        let file_name = file.name.clone().unwrap_or_default();
+        let message = &mut file.message_type[msg_idx];
+        let message_name = message.name.clone().unwrap_or_default();
+        let annotated = crate::options::get_cached_message_options(&file_name, &message_name)
+            .or_else(|| crate::options::parse_message_options(message))
+            .is_some();
+        if annotated {
+            continue;
+        }
+        let options = message.options.get_or_insert_with(Default::default);
+        options
+            .uninterpreted_option
+            .push(seaorm_option("model", ""));
+
+        if let Some(id) = message
+            .field
+            .iter_mut()
+            .find(|f| f.name.as_deref() == Some("id"))
+        {
+            let annotated = crate::options::get_cached_field_options(
+                &file_name,
+                &message_name,
+                id.number.unwrap_or(0),
+            )
+            .or_else(|| crate::options::parse_field_options(id))
+            .is_some();
+            if !annotated {
+                let options = id.options.get_or_insert_with(Default::default);
+                options
+                    .uninterpreted_option
+                    .push(seaorm_option("column", "primary_key: true"));
+            }
+        }
+    }
+
+    for (file_idx, msg_idx) in inputs {
+        let file = &mut request.proto_file[file_idx];
+        let file_name = file.name.clone().unwrap_or_default();
+        let message = &mut file.message_type[msg_idx];
+        let message_name = message.name.clone().unwrap_or_default();
+        let annotated = crate::options::get_cached_input_message_options(&file_name, &message_name)
+            .or_else(|| crate::options::parse_input_message_options(message))
+            .is_some();
+        if annotated {
+            continue;
+        }
+
+        let stripped = crate::codegen::domain::strip_request_suffix(&message_name);
+        let domain_type = if stripped == message_name {
+            format!("{}Input", message_name)
+        } else {
+            stripped
+        };
+        let options = message.options.get_or_insert_with(Default::default);
+        options.uninterpreted_option.push(seaorm_option(
+            "input_message",
+            &format!("domain_type: \"{}\"", domain_type),
+        ));
+    }
+}
+
+/// Build an uninterpreted `(seaorm.<extension>)` option holding an aggregate value
+fn seaorm_option(extension: &str, aggregate: &str) -> UninterpretedOption {
+    UninterpretedOption {
+        name: vec![NamePart {
+            name_part: format!("seaorm.{}", extension),
+            is_extension: true,
+        }],
+        aggregate_value: Some(aggregate.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Ensure no two generated entities resolve to the same table name
+fn check_duplicate_table_names(request: &CodeGeneratorRequest) -> Result<(), GeneratorError> {
+    let mut tables: HashMap<String, String> = HashMap::new();
+
+    for file in files_to_generate(request) {
+        for message in &file.message_type {
+            let Some(table_name) = crate::codegen::entity::entity_table_name(file, message) else {
+                continue;
+            };
+
+            let full_name = match file.package.as_deref() {
+                Some(pkg) if !pkg.is_empty() => {
+                    format!("{}.{}", pkg, message.name.as_deref().unwrap_or(""))
+                }
+                _ => message.name.clone().unwrap_or_default(),
+            };
+
+            if let Some(existing) = tables.get(&table_name) {
+                return Err(GeneratorError::InvalidConfig(format!(
+                    "Duplicate table name \"{}\": used by both {} and {}",
+                    table_name, existing, full_name
+                )));
+            }
+            tables.insert(table_name, full_name);
+        }
+    }
+
+    Ok(())
+}
+
+/// A module of the single-file output: a generated file, nested modules, or both
+#[derive(Default)]
+struct ModuleNode {
+    file: Option<syn::File>,
+    children: BTreeMap<String, ModuleNode>,
+}
+
+impl ModuleNode {
+    /// Render the node's contents, each child as a nested `pub mod`
+    ///
+    /// Package modules whose files import `super::prelude` declare `pub mod prelude;`,
+    /// which is loaded from disk like the prelude next to per-file output.
+    fn render(&self) -> proc_macro2::TokenStream {
+        use quote::{format_ident, quote};
+
+        let (attrs, items) = match &self.file {
+            Some(file) => {
+                // Imports are deduplicated per module, keeping the first occurrence
+                let mut seen = BTreeSet::new();
+                let items: Vec<&syn::Item> = file
+                    .items
+                    .iter()
+                    .filter(|item| match item {
+                        syn::Item::Use(use_item) => seen.insert(quote!(#use_item).to_string()),
+                        _ => true,
+                    })
+                    .collect();
+                (file.attrs.iter().collect(), items)
+            }
+            None => (Vec::new(), Vec::new()),
+        };
+
+        let needs_prelude = !self.children.contains_key("prelude")
+            && self.children.values().any(|child| {
+                child.file.as_ref().is_some_and(|file| {
+                    file.items.iter().any(|item| {
+                        matches!(item, syn::Item::Use(u) if quote!(#u).to_string().contains("super :: prelude"))
+                    })
+                })
+            });
+        let prelude = if needs_prelude {
+            quote! { pub mod prelude; }
+        } else {
+            quote! {}
+        };
+
+        let children = self.children.iter().map(|(name, child)| {
+            let ident = format_ident!("{}", name);
+            let body = child.render();
+            quote! {
+                pub mod #ident {
+                    #body
+                }
+            }
+        });
+
+        quote! {
+            #(#attrs)*
+            #prelude
+            #(#items)*
+            #(#children)*
+        }
+    }
+}
+
+/// Assemble every generated Rust file into one file named `name`
+///
+/// Each output path becomes nested modules (`test/v1/user.rs` is `pub mod test { pub
+/// mod v1 { pub mod user { ... } } }`). Files that aren't Rust are passed through.
+fn assemble_single_file(name: &str, files: Vec<File>) -> Result<Vec<File>, GeneratorError> {
+    let mut root = ModuleNode::default();
+    let mut others = Vec::new();
+
+    for file in files {
+        let path = file.name.clone().unwrap_or_default();
+        let Some(module_path) = path.strip_suffix(".rs") else {
+            others.push(file);
+            continue;
+        };
+
+        let content = file.content.unwrap_or_default();
+        let parsed = syn::parse_file(&content).map_err(|e| {
+            GeneratorError::CodeGenError(format!("single_file: {} is not valid Rust: {}", path, e))
+        })?;
+
+        let node = module_path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .fold(&mut root, |node, segment| {
+                node.children.entry(segment.to_string()).or_default()
+            });
+        node.file = Some(parsed);
+    }
+
+    let body = root.render();
+    let code = quote::quote! {
+        //! Generated by protoc-gen-seaorm from protobuf definitions.
+        //! @generated
+
+        #body
+    };
+    let content = match syn::parse_file(&code.to_string()) {
+        Ok(parsed) => prettyplease::unparse(&parsed),
+        Err(_) => code.to_string(),
+    };
+
+    others.insert(
+        0,
+        File {
+            name: Some(name.to_string()),
+            content: Some(content),
+            ..Default::default()
+        },
+    );
+    Ok(others)
+}
+
+/// Type names generated code may take from `super::prelude`
+///
+/// These are the Rust names of the request's messages and enums plus the types named
+/// in `rust_type`, `newtype_inner`, input `type`, `domain_type` and `error_type`
+/// options. `no_prelude_glob` imports only these from `super::prelude`.
+fn package_prelude_names(request: &CodeGeneratorRequest) -> BTreeSet<String> {
+    fn add_type_names(names: &mut BTreeSet<String>, rust_type: &str) {
+        let Ok(tokens) = rust_type.parse::<proc_macro2::TokenStream>() else {
+            return;
+        };
+        let mut after_colons = false;
+        let mut colon_count = 0;
+        for token in tokens {
+            match token {
+                proc_macro2::TokenTree::Ident(ident) => {
+                    if !after_colons {
+                        names.insert(ident.to_string());
+                    }
+                    after_colons = false;
+                    colon_count = 0;
+                }
+                proc_macro2::TokenTree::Punct(punct) => {
+                    colon_count = if punct.as_char() == ':' {
+                        colon_count + 1
+                    } else {
+                        0
+                    };
+                    after_colons = colon_count >= 2;
+                }
+                proc_macro2::TokenTree::Group(group) => {
+                    add_type_names(names, &group.stream().to_string());
+                    after_colons = false;
+                    colon_count = 0;
+                }
+                proc_macro2::TokenTree::Literal(_) => {
+                    after_colons = false;
+                    colon_count = 0;
+                }
+            }
+        }
+    }
+    fn add_messages(names: &mut BTreeSet<String>, messages: &[DescriptorProto]) {
+        for message in messages {
+            names.insert(message.name.as_deref().unwrap_or("").to_upper_camel_case());
+            add_messages(names, &message.nested_type);
+        }
+    }
+
+    let mut names = BTreeSet::new();
+    for file in &request.proto_file {
+        let file_name = file.name.as_deref().unwrap_or("");
+        add_messages(&mut names, &file.message_type);
+
+        for enum_desc in &file.enum_type {
+            let enum_name = enum_desc.name.as_deref().unwrap_or("");
+            let options =
+                crate::codegen::enum_gen::resolve_enum_options(file, enum_desc).unwrap_or_default();
+            names.insert(crate::codegen::enum_gen::rust_enum_name(
+                enum_name, &options,
+            ));
+        }
+
+        for message in &file.message_type {
+            let message_name = message.name.as_deref().unwrap_or("");
+            let input_message =
+                crate::options::get_cached_input_message_options(file_name, message_name)
+                    .or_else(|| crate::options::parse_input_message_options(message));
+            if let Some(options) = input_message {
+                add_type_names(&mut names, &options.domain_type);
+                add_type_names(&mut names, &options.error_type);
+            }
+
+            for field in &message.field {
+                let number = field.number.unwrap_or(0);
+                let column =
+                    crate::options::get_cached_field_options(file_name, message_name, number)
+                        .or_else(|| crate::options::parse_field_options(field));
+                if let Some(options) = column {
+                    add_type_names(&mut names, &options.rust_type);
+                    add_type_names(&mut names, &options.newtype_inner);
+                }
+                let input =
+                    crate::options::get_cached_input_options(file_name, message_name, number)
+                        .or_else(|| crate::options::parse_input_options(field));
+                if let Some(options) = input {
+                    add_type_names(&mut names, &options.r#type);
+                }
+            }
+        }
+    }
+    names
+}
+
+/// Generate the `support` module for each package with a `has_id_trait` entity
+fn support_modules(config: &Config, request: &CodeGeneratorRequest) -> Vec<File> {
+    let mut packages = BTreeSet::new();
+
+    for file in files_to_generate(request) {
+        let has_id = file.message_type.iter().any(|message| {
+            crate::codegen::entity::resolve_message_options(file, message)
+                .is_some_and(|options| options.has_id_trait)
+        });
+        if has_id {
+            packages.insert(file.package.clone().unwrap_or_default());
+        }
+    }
+
+    packages
+        .iter()
+        .map(|package| {
+            crate::codegen::entity::generate_support_module(&package_dir(config, package))
+        })
+        .collect()
+}
+
+/// Generate the aggregate `Migrator` of each package with generated migrations
+///
+/// Migrations are listed so that every table is created before the tables whose
+/// `belongs_to` foreign keys reference it; otherwise they keep declaration order.
+/// References to entities in other packages are left to those packages' migrators.
+fn generate_migrators(
+    ctx: &Context,
+    migrations: &[(&FileDescriptorProto, &DescriptorProto)],
+) -> Result<Vec<File>, GeneratorError> {
+    let mut packages: BTreeMap<String, Vec<(&FileDescriptorProto, &DescriptorProto)>> =
+        BTreeMap::new();
+    for &(file, message) in migrations {
+        packages
+            .entry(file.package.clone().unwrap_or_default())
+            .or_default()
+            .push((file, message));
+    }
+
+    packages
+        .into_iter()
+        .map(|(package, entities)| {
+            let order = migration_order(ctx, &entities)?;
+            let modules = order
+                .into_iter()
+                .filter_map(|i| {
+                    let (file, message) = entities[i];
+                    crate::codegen::entity::prefixed_table_name(ctx, file, message)
+                })
+                .map(|table_name| {
+                    format!(
+                        "super::{}::Migration",
+                        crate::codegen::migration::migration_module_name(&table_name)
+                    )
+                    .parse::<proc_macro2::TokenStream>()
+                    .map_err(|e| GeneratorError::CodeGenError(format!("{}: {}", table_name, e)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let code = quote::quote! {
+                //! Migrator running the generated migrations of the package
+                //!
+                //! Generated by protoc-gen-seaorm from protobuf definition.
+                //! @generated
+
+                use sea_orm_migration::prelude::*;
+
+                pub struct Migrator;
+
+                #[async_trait::async_trait]
+                impl MigratorTrait for Migrator {
+                    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+                        vec![#(Box::new(#modules),)*]
+                    }
+                }
+            };
+
+            let content = code.to_string();
+            let formatted = match syn::parse_file(&content) {
+                Ok(parsed) => prettyplease::unparse(&parsed),
+                Err(_) => content,
+            };
+
+            let dir = package_dir(&ctx.config, &package);
+            let name = if dir.is_empty() {
+                "migrator.rs".to_string()
+            } else {
+                format!("{}/migrator.rs", dir)
+            };
+
+            Ok(File {
+                name: Some(name),
+                content: Some(formatted),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Topologically sort a package's entities so referenced tables come first
+///
+/// Among the entities whose dependencies are all satisfied, the earliest declared
+/// one is taken next, keeping the order stable. Foreign keys that form a cycle
+/// can't be created table by table and are rejected.
+fn migration_order(
+    ctx: &Context,
+    entities: &[(&FileDescriptorProto, &DescriptorProto)],
+) -> Result<Vec<usize>, GeneratorError> {
+    let dependencies: Vec<BTreeSet<usize>> = entities
+        .iter()
+        .enumerate()
+        .map(|(index, &(file, message))| {
+            let Some(options) = crate::codegen::entity::resolve_message_options(file, message)
+            else {
+                return BTreeSet::new();
+            };
+            let message_name = message.name.as_deref().unwrap_or("");
+            with_auto_reverse(&options.relations, message_name)
+                .iter()
+                .filter(|rel| {
+                    rel.r#type == RelationType::BelongsTo as i32 && !rel.related.is_empty()
+                })
+                .filter_map(|rel| {
+                    let target = ctx
+                        .resolver
+                        .resolve_message(&rel.related, file.package.as_deref())?;
+                    entities
+                        .iter()
+                        .position(|&(_, m)| std::ptr::eq(m, target.message))
+                })
+                .filter(|&target| target != index)
+                .collect()
+        })
+        .collect();
+
+    let mut order = Vec::with_capacity(entities.len());
+    let mut done = vec![false; entities.len()];
+    while order.len() < entities.len() {
+        let next = (0..entities.len())
+            .find(|&i| !done[i] && dependencies[i].iter().all(|&d| done[d]))
+            .ok_or_else(|| {
+                let cycle: Vec<&str> = (0..entities.len())
+                    .filter(|&i| !done[i])
+                    .map(|i| entities[i].1.name.as_deref().unwrap_or(""))
+                    .collect();
+                GeneratorError::InvalidConfig(format!(
+                    "belongs_to foreign keys between {} form a cycle, so their migrations can't be ordered",
+                    cycle.join(", ")
+                ))
+            })?;
+        done[next] = true;
+        order.push(next);
+    }
+    Ok(order)
+}
+
+/// Generate a Graphviz DOT schema graph for each package
+///
+/// Nodes are the generated entities (labelled with their table names) and edges are
+/// the message-level relations declared on them.
+fn generate_schema_graphs(config: &Config, request: &CodeGeneratorRequest) -> Vec<File> {
+    let mut graphs: BTreeMap<String, (Vec<String>, Vec<String>)> = BTreeMap::new();
+
+    for file in files_to_generate(request) {
+        let package = file.package.clone().unwrap_or_default();
+
+        for message in &file.message_type {
+            let Some(table_name) = crate::codegen::entity::entity_table_name(file, message) else {
+                continue;
+            };
+            let Some(options) = crate::codegen::entity::resolve_message_options(file, message)
+            else {
+                continue;
+            };
+
+            let message_name = message.name.as_deref().unwrap_or("");
+            let node = message_name.to_snake_case();
+            let (nodes, edges) = graphs.entry(package.clone()).or_default();
+
+            nodes.push(format!(
+                "    \"{}\" [label=\"{}\\n({})\"];",
+                node, message_name, table_name
+            ));
+
+            for relation in &options.relations {
+                if let Some(generated) = generate_relation_from_def(relation) {
+                    edges.push(format!(
+                        "    \"{}\" -> \"{}\" [label=\"{} ({})\"];",
+                        node,
+                        relation.related.to_snake_case(),
+                        relation.name,
+                        generated.relation_type.attribute_name()
+                    ));
+                }
+            }
+        }
+    }
+
+    graphs
+        .into_iter()
+        .map(|(package, (nodes, edges))| {
+            let mut content = String::from("// Schema graph generated by protoc-gen-seaorm\n");
+            content.push_str("// @generated\n");
+            content.push_str("digraph schema {\n    rankdir=LR;\n    node [shape=box];\n\n");
+            for line in nodes.iter().chain(edges.iter()) {
+                content.push_str(line);
+                content.push('\n');
+            }
+            content.push_str("}\n");
+
+            let dir = package_dir(config, &package);
+            let name = if dir.is_empty() {
+                "schema.dot".to_string()
+            } else {
+                format!("{}/schema.dot", dir)
+            };
+
+            File {
+                name: Some(name),
+                content: Some(content),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
 /// Generate SeaORM entities from raw protobuf bytes
 ///
 /// This entry point preserves extension data by using prost-reflect for decoding.
@@ -7,6 +7,7 @@
 #![deny(missing_docs)]
 
 pub mod codegen;
+pub mod config;
 pub mod generator;
 pub mod options;
 pub mod types;
@@ -45,6 +46,13 @@ pub fn generate(request: CodeGeneratorRequest) -> Result<CodeGeneratorResponse,
     generator::generate(request)
 }
 
+/// Check a CodeGeneratorRequest for annotation problems without generating any files
+///
+/// Reports every problem at once, which suits linting protos in CI.
+pub fn validate(request: &CodeGeneratorRequest) -> Result<(), Vec<GeneratorError>> {
+    generator::validate(request)
+}
+
 /// Generate SeaORM entities from raw protobuf bytes
 ///
 /// This entry point preserves extension data by using prost-reflect for decoding.
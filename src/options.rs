@@ -13,7 +13,8 @@ use prost::Message;
 use prost_reflect::{DescriptorPool, DynamicMessage, Value};
 use prost_types::{
     DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
-    OneofDescriptorProto, ServiceDescriptorProto, UninterpretedOption,
+    FileDescriptorProto, MethodDescriptorProto, OneofDescriptorProto, ServiceDescriptorProto,
+    UninterpretedOption,
 };
 use std::collections::HashMap;
 use std::sync::RwLock;
@@ -530,6 +531,20 @@ pub fn get_cached_enum_options(file_name: &str, enum_name: &str) -> Option<seaor
     })
 }
 
+/// Look up cached enum value options for a given file, enum name, and value number
+pub fn get_cached_enum_value_options(
+    file_name: &str,
+    enum_name: &str,
+    value_number: i32,
+) -> Option<seaorm::EnumValueOptions> {
+    OPTIONS_CACHE.read().ok().and_then(|cache| {
+        cache
+            .enum_value_options
+            .get(&(file_name.to_string(), enum_name.to_string(), value_number))
+            .cloned()
+    })
+}
+
 /// Look up cached oneof options for a given file, message name, and oneof index
 pub fn get_cached_oneof_options(
     file_name: &str,
@@ -583,6 +598,16 @@ pub fn get_cached_rpc_method_options(
     })
 }
 
+/// Parse SeaORM RPC method options from a MethodDescriptorProto
+pub fn parse_rpc_method_options(
+    method: &MethodDescriptorProto,
+) -> Option<seaorm::RpcMethodOptions> {
+    let opts = method.options.as_ref()?;
+
+    // Fallback to uninterpreted_option (main path for unit tests)
+    parse_rpc_method_options_from_uninterpreted(&opts.uninterpreted_option)
+}
+
 /// Look up cached input field options for a given file, message name, and field number
 pub fn get_cached_input_options(
     file_name: &str,
@@ -875,6 +900,132 @@ fn convert_to_message_options(value: &Value) -> Option<seaorm::MessageOptions> {
         }
     }
 
+    if let Some(cow) = msg.get_field_by_name("projection") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.projection = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("relation_helpers") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.relation_helpers = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("update_struct") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.update_struct = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("include") {
+        if let Value::String(s) = cow.as_ref() {
+            result.include = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("embeddable") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.embeddable = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("version_column") {
+        if let Value::String(s) = cow.as_ref() {
+            result.version_column = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("derive_default") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.derive_default = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("finders") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.finders = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("table_statement") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.table_statement = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("changeset") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.changeset = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("field_mask") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.field_mask = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("has_id_trait") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.has_id_trait = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("filter_struct") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.filter_struct = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("generate_tests") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.generate_tests = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("relation_enum_prefix") {
+        if let Value::String(s) = cow.as_ref() {
+            result.relation_enum_prefix = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("empty_active_model") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.empty_active_model = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("related_entity") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.related_entity = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("timestamps") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.timestamps = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("soft_delete") {
+        if let Value::String(s) = cow.as_ref() {
+            result.soft_delete = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("identity_hash") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.identity_hash = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("proto_codec") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.proto_codec = *b;
+        }
+    }
+
     Some(result)
 }
 
@@ -919,6 +1070,18 @@ fn convert_to_relation_def(value: &Value) -> Option<seaorm::RelationDef> {
         }
     }
 
+    if let Some(cow) = msg.get_field_by_name("auto_reverse") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.auto_reverse = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("reverse_name") {
+        if let Value::String(s) = cow.as_ref() {
+            result.reverse_name = s.clone();
+        }
+    }
+
     Some(result)
 }
 
@@ -1017,6 +1180,112 @@ fn convert_to_field_options(value: &Value) -> Option<seaorm::FieldOptions> {
         }
     }
 
+    if let Some(cow) = msg.get_field_by_name("max_length") {
+        if let Value::I32(n) = cow.as_ref() {
+            result.max_length = *n;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("indexed") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.indexed = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("repeated_as") {
+        if let Value::String(s) = cow.as_ref() {
+            result.repeated_as = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("widen") {
+        if let Value::String(s) = cow.as_ref() {
+            result.widen = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("enum_as") {
+        if let Value::String(s) = cow.as_ref() {
+            result.enum_as = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("insert_default") {
+        if let Value::String(s) = cow.as_ref() {
+            result.insert_default = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("update_default") {
+        if let Value::String(s) = cow.as_ref() {
+            result.update_default = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("filter_ops") {
+        if let Value::List(list) = cow.as_ref() {
+            for item in list.iter() {
+                if let Value::String(s) = item {
+                    result.filter_ops.push(s.clone());
+                }
+            }
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("temporal") {
+        if let Value::String(s) = cow.as_ref() {
+            result.temporal = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("rust_type") {
+        if let Value::String(s) = cow.as_ref() {
+            result.rust_type = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("newtype_inner") {
+        if let Value::String(s) = cow.as_ref() {
+            result.newtype_inner = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("duration_as") {
+        if let Value::String(s) = cow.as_ref() {
+            result.duration_as = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("auto_create_time") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.auto_create_time = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("auto_update_time") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.auto_update_time = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("serde_skip") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.serde_skip = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("generated") {
+        if let Value::String(s) = cow.as_ref() {
+            result.generated = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("stored") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.stored = *b;
+        }
+    }
+
     Some(result)
 }
 
@@ -1043,6 +1312,21 @@ fn convert_to_enum_options(value: &Value) -> Option<seaorm::EnumOptions> {
         }
     }
 
+    if let Some(cow) = msg.get_field_by_name("value_conversions") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.value_conversions = *b;
+        }
+    }
+
+    // Unset means the default (derive), so only record an explicit value
+    if msg.has_field_by_name("derive_default") {
+        if let Some(cow) = msg.get_field_by_name("derive_default") {
+            if let Value::Bool(b) = cow.as_ref() {
+                result.derive_default = Some(*b);
+            }
+        }
+    }
+
     Some(result)
 }
 
@@ -1069,6 +1353,12 @@ fn convert_to_enum_value_options(value: &Value) -> Option<seaorm::EnumValueOptio
         }
     }
 
+    if let Some(cow) = msg.get_field_by_name("skip") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.skip = *b;
+        }
+    }
+
     Some(result)
 }
 
@@ -1095,6 +1385,12 @@ fn convert_to_oneof_options(value: &Value) -> Option<seaorm::OneofOptions> {
         }
     }
 
+    if let Some(cow) = msg.get_field_by_name("discriminator_enum") {
+        if let Value::String(s) = cow.as_ref() {
+            result.discriminator_enum = s.clone();
+        }
+    }
+
     Some(result)
 }
 
@@ -1121,6 +1417,30 @@ fn convert_to_service_options(value: &Value) -> Option<seaorm::ServiceOptions> {
         }
     }
 
+    if let Some(cow) = msg.get_field_by_name("db_error") {
+        if let Value::String(s) = cow.as_ref() {
+            result.db_error = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("by_ref") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.by_ref = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("auto_entities") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.auto_entities = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("blocking") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.blocking = *b;
+        }
+    }
+
     Some(result)
 }
 
@@ -1141,6 +1461,18 @@ fn convert_to_rpc_method_options(value: &Value) -> Option<seaorm::RpcMethodOptio
         }
     }
 
+    if let Some(cow) = msg.get_field_by_name("masked_update") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.masked_update = *b;
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("list_response") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.list_response = *b;
+        }
+    }
+
     Some(result)
 }
 
@@ -1305,6 +1637,24 @@ fn convert_to_range_rule(msg: &DynamicMessage) -> Option<seaorm::RangeRule> {
         }
     }
 
+    if msg.has_field_by_name("gt") {
+        if let Some(cow) = msg.get_field_by_name("gt") {
+            if let Value::I64(n) = cow.as_ref() {
+                result.gt = Some(*n);
+                has_value = true;
+            }
+        }
+    }
+
+    if msg.has_field_by_name("lt") {
+        if let Some(cow) = msg.get_field_by_name("lt") {
+            if let Value::I64(n) = cow.as_ref() {
+                result.lt = Some(*n);
+                has_value = true;
+            }
+        }
+    }
+
     if has_value {
         Some(result)
     } else {
@@ -1335,9 +1685,207 @@ fn convert_to_input_message_options(value: &Value) -> Option<seaorm::InputMessag
         }
     }
 
+    if let Some(cow) = msg.get_field_by_name("entity") {
+        if let Value::String(s) = cow.as_ref() {
+            result.entity = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("error_type") {
+        if let Value::String(s) = cow.as_ref() {
+            result.error_type = s.clone();
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("fields") {
+        if let Value::List(list) = cow.as_ref() {
+            for item in list.iter() {
+                if let Value::String(s) = item {
+                    result.fields.push(s.clone());
+                }
+            }
+        }
+    }
+
+    if let Some(cow) = msg.get_field_by_name("generate_builder") {
+        if let Value::Bool(b) = cow.as_ref() {
+            result.generate_builder = *b;
+        }
+    }
+
     Some(result)
 }
 
+// =============================================================================
+// Strict mode: divergence between extension data and uninterpreted options
+// =============================================================================
+
+/// Check that extension data and uninterpreted options agree for every seaorm option in a file
+///
+/// Normally only one source carries options; when both do, the extension data wins
+/// silently. With the `strict_options` plugin parameter this check runs first and turns
+/// any disagreement into an error naming the annotated element. Nested messages and
+/// enums are checked under their dotted names, as they are cached.
+pub fn check_option_divergence(file: &FileDescriptorProto) -> Result<(), crate::GeneratorError> {
+    let file_name = file.name.as_deref().unwrap_or("");
+
+    for message in &file.message_type {
+        check_message_divergence(file_name, message, "")?;
+    }
+
+    for enum_desc in &file.enum_type {
+        check_enum_divergence(file_name, enum_desc, "")?;
+    }
+
+    for service in &file.service {
+        let service_name = service.name.as_deref().unwrap_or("");
+        if let Some(opts) = &service.options {
+            check_sources(
+                &format!("{} ({})", service_name, SERVICE_EXTENSION_NAME),
+                get_cached_service_options(file_name, service_name),
+                parse_service_options_from_uninterpreted(&opts.uninterpreted_option),
+            )?;
+        }
+
+        for method in &service.method {
+            let Some(opts) = &method.options else {
+                continue;
+            };
+            let method_name = method.name.as_deref().unwrap_or("");
+            check_sources(
+                &format!("{}.{} ({})", service_name, method_name, RPC_EXTENSION_NAME),
+                get_cached_rpc_method_options(file_name, service_name, method_name),
+                parse_rpc_method_options_from_uninterpreted(&opts.uninterpreted_option),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a message's options, then those of its fields, oneofs and nested types
+fn check_message_divergence(
+    file_name: &str,
+    message: &DescriptorProto,
+    parent_prefix: &str,
+) -> Result<(), crate::GeneratorError> {
+    let name = message.name.as_deref().unwrap_or("");
+    let msg_name = if parent_prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent_prefix, name)
+    };
+
+    if let Some(opts) = &message.options {
+        check_sources(
+            &format!("{} ({})", msg_name, MODEL_EXTENSION_NAME),
+            get_cached_message_options(file_name, &msg_name),
+            parse_message_options_from_uninterpreted(&opts.uninterpreted_option),
+        )?;
+        check_sources(
+            &format!("{} ({})", msg_name, INPUT_MESSAGE_EXTENSION_NAME),
+            get_cached_input_message_options(file_name, &msg_name),
+            parse_input_message_options_from_uninterpreted(&opts.uninterpreted_option),
+        )?;
+    }
+
+    for field in &message.field {
+        let Some(opts) = &field.options else { continue };
+        let field_name = field.name.as_deref().unwrap_or("");
+        let field_number = field.number.unwrap_or(0);
+        check_sources(
+            &format!("{}.{} ({})", msg_name, field_name, COLUMN_EXTENSION_NAME),
+            get_cached_field_options(file_name, &msg_name, field_number),
+            parse_field_options_from_uninterpreted(&opts.uninterpreted_option),
+        )?;
+        check_sources(
+            &format!("{}.{} ({})", msg_name, field_name, INPUT_EXTENSION_NAME),
+            get_cached_input_options(file_name, &msg_name, field_number),
+            parse_input_options_from_uninterpreted(&opts.uninterpreted_option),
+        )?;
+    }
+
+    for (index, oneof) in message.oneof_decl.iter().enumerate() {
+        let Some(opts) = &oneof.options else { continue };
+        check_sources(
+            &format!(
+                "{}.{} ({})",
+                msg_name,
+                oneof.name.as_deref().unwrap_or(""),
+                ONEOF_EXTENSION_NAME
+            ),
+            get_cached_oneof_options(file_name, &msg_name, index as i32),
+            parse_oneof_options_from_uninterpreted(&opts.uninterpreted_option),
+        )?;
+    }
+
+    for nested in &message.nested_type {
+        check_message_divergence(file_name, nested, &msg_name)?;
+    }
+
+    for enum_desc in &message.enum_type {
+        check_enum_divergence(file_name, enum_desc, &msg_name)?;
+    }
+
+    Ok(())
+}
+
+/// Check an enum's options and those of its values
+fn check_enum_divergence(
+    file_name: &str,
+    enum_desc: &EnumDescriptorProto,
+    parent_prefix: &str,
+) -> Result<(), crate::GeneratorError> {
+    let name = enum_desc.name.as_deref().unwrap_or("");
+    let enum_name = if parent_prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent_prefix, name)
+    };
+
+    if let Some(opts) = &enum_desc.options {
+        check_sources(
+            &format!("{} ({})", enum_name, ENUM_EXTENSION_NAME),
+            get_cached_enum_options(file_name, &enum_name),
+            parse_enum_options_from_uninterpreted(&opts.uninterpreted_option),
+        )?;
+    }
+
+    for value in &enum_desc.value {
+        let Some(opts) = &value.options else { continue };
+        check_sources(
+            &format!(
+                "{}.{} ({})",
+                enum_name,
+                value.name.as_deref().unwrap_or(""),
+                ENUM_VALUE_EXTENSION_NAME
+            ),
+            get_cached_enum_value_options(file_name, &enum_name, value.number.unwrap_or(0)),
+            parse_enum_value_options_from_uninterpreted(&opts.uninterpreted_option),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Compare options decoded from extension data against the uninterpreted fallback
+fn check_sources<T: PartialEq + std::fmt::Debug>(
+    location: &str,
+    extension: Option<T>,
+    uninterpreted: Option<T>,
+) -> Result<(), crate::GeneratorError> {
+    match (extension, uninterpreted) {
+        (Some(ext), Some(fallback)) if ext != fallback => {
+            Err(crate::GeneratorError::OptionsParseError(format!(
+                "{}: extension data and uninterpreted options disagree \
+                 (extension: {:?}, uninterpreted: {:?})",
+                location, ext, fallback
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
 // =============================================================================
 // Fallback: Uninterpreted option parsing (for older protoc versions)
 // =============================================================================
@@ -1468,6 +2016,27 @@ fn parse_service_options_from_uninterpreted(
     }
 }
 
+/// Parse RpcMethodOptions from uninterpreted options
+fn parse_rpc_method_options_from_uninterpreted(
+    uninterpreted: &[UninterpretedOption],
+) -> Option<seaorm::RpcMethodOptions> {
+    let mut result = seaorm::RpcMethodOptions::default();
+    let mut found = false;
+
+    for opt in uninterpreted {
+        if is_extension_option(opt, RPC_EXTENSION_NAME) {
+            found = true;
+            apply_rpc_method_option(&mut result, opt);
+        }
+    }
+
+    if found {
+        Some(result)
+    } else {
+        None
+    }
+}
+
 /// Parse InputOptions from uninterpreted options
 fn parse_input_options_from_uninterpreted(
     uninterpreted: &[UninterpretedOption],
@@ -1555,6 +2124,27 @@ fn apply_message_option(result: &mut seaorm::MessageOptions, opt: &Uninterpreted
                     result.skip = v == "true";
                 }
             }
+            "projection" => result.projection = parse_bool_option(opt),
+            "relation_helpers" => result.relation_helpers = parse_bool_option(opt),
+            "update_struct" => result.update_struct = parse_bool_option(opt),
+            "include" => result.include = parse_string_option(opt),
+            "embeddable" => result.embeddable = parse_bool_option(opt),
+            "version_column" => result.version_column = parse_string_option(opt),
+            "derive_default" => result.derive_default = parse_bool_option(opt),
+            "finders" => result.finders = parse_bool_option(opt),
+            "table_statement" => result.table_statement = parse_bool_option(opt),
+            "changeset" => result.changeset = parse_bool_option(opt),
+            "field_mask" => result.field_mask = parse_bool_option(opt),
+            "has_id_trait" => result.has_id_trait = parse_bool_option(opt),
+            "filter_struct" => result.filter_struct = parse_bool_option(opt),
+            "generate_tests" => result.generate_tests = parse_bool_option(opt),
+            "relation_enum_prefix" => result.relation_enum_prefix = parse_string_option(opt),
+            "empty_active_model" => result.empty_active_model = parse_bool_option(opt),
+            "related_entity" => result.related_entity = parse_bool_option(opt),
+            "timestamps" => result.timestamps = parse_bool_option(opt),
+            "soft_delete" => result.soft_delete = parse_string_option(opt),
+            "identity_hash" => result.identity_hash = parse_bool_option(opt),
+            "proto_codec" => result.proto_codec = parse_bool_option(opt),
             _ => {}
         }
     }
@@ -1581,6 +2171,8 @@ fn apply_enum_option(result: &mut seaorm::EnumOptions, opt: &UninterpretedOption
             "name" => result.name = parse_string_option(opt),
             "db_type" => result.db_type = parse_string_option(opt),
             "skip" => result.skip = parse_bool_option(opt),
+            "value_conversions" => result.value_conversions = parse_bool_option(opt),
+            "derive_default" => result.derive_default = Some(parse_bool_option(opt)),
             _ => {}
         }
     }
@@ -1595,6 +2187,7 @@ fn apply_enum_value_option(result: &mut seaorm::EnumValueOptions, opt: &Uninterp
             "name" => result.name = parse_string_option(opt),
             "string_value" => result.string_value = parse_string_option(opt),
             "int_value" => result.int_value = parse_int_option(opt),
+            "skip" => result.skip = parse_bool_option(opt),
             _ => {}
         }
     }
@@ -1609,6 +2202,7 @@ fn apply_oneof_option(result: &mut seaorm::OneofOptions, opt: &UninterpretedOpti
             "strategy" => result.strategy = parse_string_option(opt),
             "column_prefix" => result.column_prefix = parse_string_option(opt),
             "discriminator_column" => result.discriminator_column = parse_string_option(opt),
+            "discriminator_enum" => result.discriminator_enum = parse_string_option(opt),
             _ => {}
         }
     }
@@ -1623,6 +2217,25 @@ fn apply_service_option(result: &mut seaorm::ServiceOptions, opt: &Uninterpreted
             "generate_storage" => result.generate_storage = parse_bool_option(opt),
             "trait_name" => result.trait_name = parse_string_option(opt),
             "skip" => result.skip = parse_bool_option(opt),
+            "db_error" => result.db_error = parse_string_option(opt),
+            "by_ref" => result.by_ref = parse_bool_option(opt),
+            "auto_entities" => result.auto_entities = parse_bool_option(opt),
+            "blocking" => result.blocking = parse_bool_option(opt),
+            _ => {}
+        }
+    }
+}
+
+/// Apply a single uninterpreted option to RpcMethodOptions
+fn apply_rpc_method_option(result: &mut seaorm::RpcMethodOptions, opt: &UninterpretedOption) {
+    if let Some(aggregate) = opt.aggregate_value.as_ref() {
+        parse_aggregate_into_rpc_method_options(result, aggregate);
+    } else if let Some(field_name) = get_subfield_name(opt) {
+        match field_name {
+            "skip" => result.skip = parse_bool_option(opt),
+            "method_name" => result.method_name = parse_string_option(opt),
+            "masked_update" => result.masked_update = parse_bool_option(opt),
+            "list_response" => result.list_response = parse_bool_option(opt),
             _ => {}
         }
     }
@@ -1651,6 +2264,9 @@ fn apply_input_message_option(result: &mut seaorm::InputMessageOptions, opt: &Un
             "domain_type" => result.domain_type = parse_string_option(opt),
             "skip" => result.skip = parse_bool_option(opt),
             "generate_try_from" => result.generate_try_from = parse_bool_option(opt),
+            "entity" => result.entity = parse_string_option(opt),
+            "error_type" => result.error_type = parse_string_option(opt),
+            "generate_builder" => result.generate_builder = parse_bool_option(opt),
             _ => {}
         }
     }
@@ -1678,6 +2294,22 @@ fn apply_single_field_option(
         "belongs_to_from" => result.belongs_to_from = parse_string_option(opt),
         "belongs_to_to" => result.belongs_to_to = parse_string_option(opt),
         "has_many_via" => result.has_many_via = parse_string_option(opt),
+        "max_length" => result.max_length = parse_int_option(opt),
+        "indexed" => result.indexed = parse_bool_option(opt),
+        "repeated_as" => result.repeated_as = parse_string_option(opt),
+        "widen" => result.widen = parse_string_option(opt),
+        "enum_as" => result.enum_as = parse_string_option(opt),
+        "insert_default" => result.insert_default = parse_string_option(opt),
+        "update_default" => result.update_default = parse_string_option(opt),
+        "temporal" => result.temporal = parse_string_option(opt),
+        "rust_type" => result.rust_type = parse_string_option(opt),
+        "newtype_inner" => result.newtype_inner = parse_string_option(opt),
+        "duration_as" => result.duration_as = parse_string_option(opt),
+        "auto_create_time" => result.auto_create_time = parse_bool_option(opt),
+        "auto_update_time" => result.auto_update_time = parse_bool_option(opt),
+        "serde_skip" => result.serde_skip = parse_bool_option(opt),
+        "generated" => result.generated = parse_string_option(opt),
+        "stored" => result.stored = parse_bool_option(opt),
         _ => {}
     }
 }
@@ -1743,9 +2375,31 @@ fn parse_aggregate_into_message_options(result: &mut seaorm::MessageOptions, agg
         match key {
             "table_name" => result.table_name = parse_quoted_string(value),
             "skip" => result.skip = value == "true",
+            "indexes" if value.starts_with('[') => result.indexes.extend(parse_string_list(value)),
             "indexes" => {
                 result.indexes.push(parse_quoted_string(value));
             }
+            "projection" => result.projection = value == "true",
+            "relation_helpers" => result.relation_helpers = value == "true",
+            "update_struct" => result.update_struct = value == "true",
+            "include" => result.include = parse_quoted_string(value),
+            "embeddable" => result.embeddable = value == "true",
+            "version_column" => result.version_column = parse_quoted_string(value),
+            "derive_default" => result.derive_default = value == "true",
+            "finders" => result.finders = value == "true",
+            "table_statement" => result.table_statement = value == "true",
+            "changeset" => result.changeset = value == "true",
+            "field_mask" => result.field_mask = value == "true",
+            "has_id_trait" => result.has_id_trait = value == "true",
+            "filter_struct" => result.filter_struct = value == "true",
+            "generate_tests" => result.generate_tests = value == "true",
+            "relation_enum_prefix" => result.relation_enum_prefix = parse_quoted_string(value),
+            "empty_active_model" => result.empty_active_model = value == "true",
+            "related_entity" => result.related_entity = value == "true",
+            "timestamps" => result.timestamps = value == "true",
+            "soft_delete" => result.soft_delete = parse_quoted_string(value),
+            "identity_hash" => result.identity_hash = value == "true",
+            "proto_codec" => result.proto_codec = value == "true",
             _ => {}
         }
     }
@@ -1777,6 +2431,23 @@ fn parse_aggregate_into_field_options(result: &mut seaorm::FieldOptions, aggrega
             "belongs_to_from" => result.belongs_to_from = parse_quoted_string(value),
             "belongs_to_to" => result.belongs_to_to = parse_quoted_string(value),
             "has_many_via" => result.has_many_via = parse_quoted_string(value),
+            "max_length" => result.max_length = value.parse().unwrap_or(0),
+            "indexed" => result.indexed = value == "true",
+            "repeated_as" => result.repeated_as = parse_quoted_string(value),
+            "widen" => result.widen = parse_quoted_string(value),
+            "enum_as" => result.enum_as = parse_quoted_string(value),
+            "insert_default" => result.insert_default = parse_quoted_string(value),
+            "update_default" => result.update_default = parse_quoted_string(value),
+            "filter_ops" => result.filter_ops = parse_string_list(value),
+            "temporal" => result.temporal = parse_quoted_string(value),
+            "rust_type" => result.rust_type = parse_quoted_string(value),
+            "newtype_inner" => result.newtype_inner = parse_quoted_string(value),
+            "duration_as" => result.duration_as = parse_quoted_string(value),
+            "auto_create_time" => result.auto_create_time = value == "true",
+            "auto_update_time" => result.auto_update_time = value == "true",
+            "serde_skip" => result.serde_skip = value == "true",
+            "generated" => result.generated = parse_quoted_string(value),
+            "stored" => result.stored = value == "true",
             _ => {}
         }
     }
@@ -1794,6 +2465,8 @@ fn parse_aggregate_into_enum_options(result: &mut seaorm::EnumOptions, aggregate
             "name" => result.name = parse_quoted_string(value),
             "db_type" => result.db_type = parse_quoted_string(value),
             "skip" => result.skip = value == "true",
+            "value_conversions" => result.value_conversions = value == "true",
+            "derive_default" => result.derive_default = Some(value == "true"),
             _ => {}
         }
     }
@@ -1815,6 +2488,7 @@ fn parse_aggregate_into_enum_value_options(result: &mut seaorm::EnumValueOptions
                     result.int_value = v;
                 }
             }
+            "skip" => result.skip = value == "true",
             _ => {}
         }
     }
@@ -1832,6 +2506,7 @@ fn parse_aggregate_into_oneof_options(result: &mut seaorm::OneofOptions, aggrega
             "strategy" => result.strategy = parse_quoted_string(value),
             "column_prefix" => result.column_prefix = parse_quoted_string(value),
             "discriminator_column" => result.discriminator_column = parse_quoted_string(value),
+            "discriminator_enum" => result.discriminator_enum = parse_quoted_string(value),
             _ => {}
         }
     }
@@ -1851,6 +2526,30 @@ fn parse_aggregate_into_service_options(result: &mut seaorm::ServiceOptions, agg
             "generate_storage" => result.generate_storage = value == "true",
             "trait_name" => result.trait_name = parse_quoted_string(value),
             "skip" => result.skip = value == "true",
+            "db_error" => result.db_error = parse_quoted_string(value),
+            "by_ref" => result.by_ref = value == "true",
+            "auto_entities" => result.auto_entities = value == "true",
+            "blocking" => result.blocking = value == "true",
+            _ => {}
+        }
+    }
+}
+
+/// Parse an aggregate value (text format) into RpcMethodOptions
+///
+/// Aggregate values look like: `method_name: "create", skip: false`
+fn parse_aggregate_into_rpc_method_options(result: &mut seaorm::RpcMethodOptions, aggregate: &str) {
+    for part in split_aggregate_parts(aggregate) {
+        let (key, value) = match part.split_once(':') {
+            Some((k, v)) => (k.trim(), v.trim()),
+            None => continue,
+        };
+
+        match key {
+            "skip" => result.skip = value == "true",
+            "method_name" => result.method_name = parse_quoted_string(value),
+            "masked_update" => result.masked_update = value == "true",
+            "list_response" => result.list_response = value == "true",
             _ => {}
         }
     }
@@ -1892,6 +2591,10 @@ fn parse_aggregate_into_input_message_options(
             "domain_type" => result.domain_type = parse_quoted_string(value),
             "skip" => result.skip = value == "true",
             "generate_try_from" => result.generate_try_from = value == "true",
+            "entity" => result.entity = parse_quoted_string(value),
+            "error_type" => result.error_type = parse_quoted_string(value),
+            "fields" => result.fields.extend(parse_string_list(value)),
+            "generate_builder" => result.generate_builder = value == "true",
             _ => {}
         }
     }
@@ -2010,6 +2713,18 @@ fn parse_range_rule(value: &str) -> Option<seaorm::RangeRule> {
                     has_value = true;
                 }
             }
+            "gt" => {
+                if let Ok(n) = val.parse::<i64>() {
+                    result.gt = Some(n);
+                    has_value = true;
+                }
+            }
+            "lt" => {
+                if let Ok(n) = val.parse::<i64>() {
+                    result.lt = Some(n);
+                    has_value = true;
+                }
+            }
             _ => {}
         }
     }
@@ -2021,15 +2736,29 @@ fn parse_range_rule(value: &str) -> Option<seaorm::RangeRule> {
     }
 }
 
-/// Split aggregate value into simple parts (only top-level commas, not inside braces)
+/// Split aggregate value into simple parts (only top-level commas, not inside braces
+/// or quoted strings)
 fn split_aggregate_parts_simple(aggregate: &str) -> Vec<&str> {
     let mut parts = Vec::new();
     let mut start = 0;
     let mut brace_depth: i32 = 0;
     let mut bracket_depth: i32 = 0;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
 
     for (i, c) in aggregate.char_indices() {
+        if let Some(q) = quote {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                _ if c == q => quote = None,
+                _ => {}
+            }
+            continue;
+        }
+
         match c {
+            '"' | '\'' => quote = Some(c),
             '{' => brace_depth += 1,
             '}' => brace_depth = brace_depth.saturating_sub(1),
             '[' => bracket_depth += 1,
@@ -2149,6 +2878,8 @@ fn parse_single_relation_def(s: &str) -> Option<seaorm::RelationDef> {
             "foreign_key" => rel.foreign_key = parse_quoted_string(value),
             "references" => rel.references = parse_quoted_string(value),
             "through" => rel.through = parse_quoted_string(value),
+            "auto_reverse" => rel.auto_reverse = value == "true",
+            "reverse_name" => rel.reverse_name = parse_quoted_string(value),
             _ => {}
         }
     }
@@ -2181,11 +2912,39 @@ fn split_aggregate_parts(aggregate: &str) -> Vec<&str> {
     split_aggregate_parts_simple(aggregate)
 }
 
-/// Parse a quoted string value, removing quotes
+/// Parse a repeated string value: a list (`["a", "b"]`) or a single string
+///
+/// Repeated fields may also be written by repeating the key, so callers extend
+/// rather than replace.
+fn parse_string_list(s: &str) -> Vec<String> {
+    match extract_balanced(s, '[', ']') {
+        Some(content) => split_aggregate_parts_simple(content)
+            .into_iter()
+            .map(parse_quoted_string)
+            .filter(|item| !item.is_empty())
+            .collect(),
+        None => vec![parse_quoted_string(s)],
+    }
+}
+
+/// Parse a quoted string value, removing quotes and unescaping `\"`, `\'` and `\\`
 fn parse_quoted_string(s: &str) -> String {
     let s = s.trim();
-    if (s.starts_with('"') && s.ends_with('"')) || (s.starts_with('\'') && s.ends_with('\'')) {
-        s[1..s.len() - 1].to_string()
+    if s.len() >= 2
+        && ((s.starts_with('"') && s.ends_with('"')) || (s.starts_with('\'') && s.ends_with('\'')))
+    {
+        let mut out = String::with_capacity(s.len() - 2);
+        let mut chars = s[1..s.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            match (c, chars.clone().next()) {
+                ('\\', Some(next @ ('"' | '\'' | '\\'))) => {
+                    out.push(next);
+                    chars.next();
+                }
+                _ => out.push(c),
+            }
+        }
+        out
     } else {
         s.to_string()
     }
@@ -2200,11 +2959,59 @@ mod tests {
         assert_eq!(parse_quoted_string("\"hello\""), "hello");
         assert_eq!(parse_quoted_string("'world'"), "world");
         assert_eq!(parse_quoted_string("unquoted"), "unquoted");
+        assert_eq!(
+            parse_quoted_string(r#""Custom(\"geography(Point,4326)\")""#),
+            r#"Custom("geography(Point,4326)")"#
+        );
     }
 
     #[test]
     fn test_split_aggregate_parts() {
         let parts = split_aggregate_parts("key1: value1, key2: value2");
         assert_eq!(parts.len(), 2);
+
+        // Commas inside quoted strings don't split
+        let parts = split_aggregate_parts(
+            r#"column_type: "Custom(\"geography(Point,4326)\")", nullable: true"#,
+        );
+        assert_eq!(parts.len(), 2);
+    }
+
+    #[test]
+    fn test_check_option_divergence() {
+        let file_name = "strict_divergence_test.proto";
+        OPTIONS_CACHE.write().unwrap().message_options.insert(
+            (file_name.to_string(), "User".to_string()),
+            seaorm::MessageOptions {
+                table_name: "users".to_string(),
+                ..Default::default()
+            },
+        );
+
+        let file_with = |table: &str| FileDescriptorProto {
+            name: Some(file_name.to_string()),
+            message_type: vec![DescriptorProto {
+                name: Some("User".to_string()),
+                options: Some(prost_types::MessageOptions {
+                    uninterpreted_option: vec![UninterpretedOption {
+                        name: vec![prost_types::uninterpreted_option::NamePart {
+                            name_part: MODEL_EXTENSION_NAME.to_string(),
+                            is_extension: true,
+                        }],
+                        aggregate_value: Some(format!("table_name: \"{}\"", table)),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert!(check_option_divergence(&file_with("users")).is_ok());
+
+        let err = check_option_divergence(&file_with("accounts")).unwrap_err();
+        assert!(matches!(err, crate::GeneratorError::OptionsParseError(_)));
+        assert!(err.to_string().contains("User (seaorm.model)"), "{}", err);
     }
 }
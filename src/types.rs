@@ -4,6 +4,7 @@
 //! corresponding Rust types for SeaORM entities.
 
 use prost_types::field_descriptor_proto::Type;
+use prost_types::DescriptorProto;
 
 /// Represents a mapped Rust type with optional SeaORM-specific attributes
 #[derive(Debug, Clone)]
@@ -62,7 +63,7 @@ pub fn map_proto_type(proto_type: Type, type_name: Option<&str>) -> MappedType {
         Type::Bytes => MappedType {
             rust_type: "Vec<u8>".to_string(),
             requires_import: None,
-            column_type: None,
+            column_type: Some("VarBinary(StringLen::None)".to_string()),
         },
         Type::Message => map_message_type(type_name),
         Type::Enum => map_enum_type(type_name),
@@ -74,6 +75,59 @@ pub fn map_proto_type(proto_type: Type, type_name: Option<&str>) -> MappedType {
     }
 }
 
+/// Map a `temporal` variant to its chrono type and SeaORM column type
+///
+/// `naive` is a `DateTime` without time zone, `utc` and `local` are time-zone aware
+/// timestamps, and `date`/`time` keep only that part.
+pub fn map_temporal_type(variant: &str) -> Option<MappedType> {
+    let (rust_type, column_type) = match variant {
+        "naive" => ("DateTime", "DateTime"),
+        "utc" => ("DateTimeUtc", "TimestampWithTimeZone"),
+        "local" => ("DateTimeLocal", "TimestampWithTimeZone"),
+        "date" => ("Date", "Date"),
+        "time" => ("Time", "Time"),
+        _ => return None,
+    };
+    Some(MappedType {
+        rust_type: rust_type.to_string(),
+        requires_import: Some(format!("sea_orm::prelude::{}", rust_type)),
+        column_type: Some(column_type.to_string()),
+    })
+}
+
+/// Whether a message type has a dedicated mapping (Timestamp, Duration, Date and the
+/// wrapper types) rather than being stored as JSON
+pub fn is_well_known_message(type_name: &str) -> bool {
+    matches!(
+        type_name,
+        ".google.protobuf.Timestamp" | ".google.protobuf.Duration" | ".google.type.Date"
+    ) || wrapped_type(type_name).is_some()
+}
+
+/// The `google.protobuf` wrapper types and the scalar each one wraps
+const WRAPPER_TYPES: &[(&str, Type)] = &[
+    (".google.protobuf.DoubleValue", Type::Double),
+    (".google.protobuf.FloatValue", Type::Float),
+    (".google.protobuf.Int64Value", Type::Int64),
+    (".google.protobuf.UInt64Value", Type::Uint64),
+    (".google.protobuf.Int32Value", Type::Int32),
+    (".google.protobuf.UInt32Value", Type::Uint32),
+    (".google.protobuf.BoolValue", Type::Bool),
+    (".google.protobuf.StringValue", Type::String),
+    (".google.protobuf.BytesValue", Type::Bytes),
+];
+
+/// The scalar a `google.protobuf` wrapper type wraps, or None for other messages
+///
+/// Wrappers are optional scalars, so they map to `Option` of the scalar's type and
+/// are stored in its column type.
+pub fn wrapped_type(type_name: &str) -> Option<Type> {
+    WRAPPER_TYPES
+        .iter()
+        .find(|(name, _)| *name == type_name)
+        .map(|(_, scalar)| *scalar)
+}
+
 /// Map a protobuf message type to a Rust type
 /// Handles well-known types like google.protobuf.Timestamp
 fn map_message_type(type_name: Option<&str>) -> MappedType {
@@ -83,36 +137,21 @@ fn map_message_type(type_name: Option<&str>) -> MappedType {
             requires_import: Some("sea_orm::prelude::DateTimeUtc".to_string()),
             column_type: Some("TimestampWithTimeZone".to_string()),
         },
-        Some(".google.protobuf.Duration") => MappedType {
-            rust_type: "TimeDelta".to_string(),
-            requires_import: Some("chrono::TimeDelta".to_string()),
-            column_type: Some("Interval".to_string()),
-        },
+        Some(".google.protobuf.Duration") => {
+            map_duration_type("nanos").expect("nanos is a duration representation")
+        }
         Some(".google.type.Date") => MappedType {
             rust_type: "Date".to_string(),
             requires_import: Some("sea_orm::prelude::Date".to_string()),
             column_type: Some("Date".to_string()),
         },
-        Some(".google.protobuf.StringValue") => MappedType {
-            rust_type: "Option<String>".to_string(),
-            requires_import: None,
-            column_type: None,
-        },
-        Some(".google.protobuf.Int32Value") => MappedType {
-            rust_type: "Option<i32>".to_string(),
-            requires_import: None,
-            column_type: None,
-        },
-        Some(".google.protobuf.Int64Value") => MappedType {
-            rust_type: "Option<i64>".to_string(),
-            requires_import: None,
-            column_type: None,
-        },
-        Some(".google.protobuf.BoolValue") => MappedType {
-            rust_type: "Option<bool>".to_string(),
-            requires_import: None,
-            column_type: None,
-        },
+        Some(name) if wrapped_type(name).is_some() => {
+            let inner = map_proto_type(wrapped_type(name).expect("checked above"), None);
+            MappedType {
+                rust_type: format!("Option<{}>", inner.rust_type),
+                ..inner
+            }
+        }
         Some(name) => {
             // For other message types, use the type directly
             // In SeaORM 2.0, the type should derive FromJsonQueryResult for JSON storage
@@ -131,6 +170,69 @@ fn map_message_type(type_name: Option<&str>) -> MappedType {
     }
 }
 
+/// A `map<K, V>` field stored whole in a JSON column
+#[derive(Debug, Clone)]
+pub struct MappedMap {
+    /// The column's type, a `Json` value
+    pub mapped: MappedType,
+    /// The `HashMap<K, V>` the value deserializes to, when there is one
+    pub hash_map: Option<String>,
+}
+
+/// Map a `map<K, V>` field given the synthetic map entry message protoc lowers it to
+///
+/// Returns None for messages that aren't map entries. A typed `HashMap` is only given
+/// for string or integer keys with scalar or string values, since enum and message
+/// values don't deserialize from their JSON form without their own types.
+pub fn map_map_entry(entry: &DescriptorProto) -> Option<MappedMap> {
+    let is_map_entry = entry
+        .options
+        .as_ref()
+        .and_then(|o| o.map_entry)
+        .unwrap_or(false);
+    if !is_map_entry {
+        return None;
+    }
+
+    let entry_type = |number: i32| {
+        entry
+            .field
+            .iter()
+            .find(|f| f.number == Some(number))
+            .map(|f| f.r#type())
+    };
+    let key = entry_type(1).filter(|t| {
+        !matches!(
+            t,
+            Type::Double
+                | Type::Float
+                | Type::Bool
+                | Type::Bytes
+                | Type::Message
+                | Type::Enum
+                | Type::Group
+        )
+    });
+    let value = entry_type(2)
+        .filter(|t| !matches!(t, Type::Bytes | Type::Message | Type::Enum | Type::Group));
+    let hash_map = key.zip(value).map(|(key, value)| {
+        format!(
+            "std::collections::HashMap<{}, {}>",
+            map_proto_type(key, None).rust_type,
+            map_proto_type(value, None).rust_type
+        )
+    });
+
+    Some(MappedMap {
+        mapped: MappedType {
+            rust_type: "Json".to_string(),
+            requires_import: Some("sea_orm::prelude::Json".to_string()),
+            column_type: None,
+        },
+        hash_map,
+    })
+}
+
 /// Map a protobuf enum type to a Rust type
 fn map_enum_type(type_name: Option<&str>) -> MappedType {
     match type_name {
@@ -150,6 +252,27 @@ fn map_enum_type(type_name: Option<&str>) -> MappedType {
     }
 }
 
+/// Map a `duration_as` representation to its Rust type and SeaORM column type
+///
+/// `nanos` stores the total nanoseconds as an `i64` in a `BigInteger` column, which
+/// every backend supports. `interval` uses a Postgres `INTERVAL` as a `chrono::TimeDelta`,
+/// written out in full since SeaORM's prelude doesn't export it.
+pub fn map_duration_type(representation: &str) -> Option<MappedType> {
+    match representation {
+        "nanos" => Some(MappedType {
+            rust_type: "i64".to_string(),
+            requires_import: None,
+            column_type: Some("BigInteger".to_string()),
+        }),
+        "interval" => Some(MappedType {
+            rust_type: "chrono::TimeDelta".to_string(),
+            requires_import: None,
+            column_type: Some("Interval(None, None)".to_string()),
+        }),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,10 +285,95 @@ mod tests {
         assert_eq!(map_proto_type(Type::Bool, None).rust_type, "bool");
     }
 
+    #[test]
+    fn test_temporal_mapping() {
+        let cases = [
+            ("naive", "DateTime", "DateTime"),
+            ("utc", "DateTimeUtc", "TimestampWithTimeZone"),
+            ("local", "DateTimeLocal", "TimestampWithTimeZone"),
+            ("date", "Date", "Date"),
+            ("time", "Time", "Time"),
+        ];
+        for (variant, rust_type, column_type) in cases {
+            let mapped = map_temporal_type(variant).unwrap();
+            assert_eq!(mapped.rust_type, rust_type);
+            assert_eq!(mapped.column_type.as_deref(), Some(column_type));
+        }
+        assert!(map_temporal_type("zoned").is_none());
+    }
+
+    #[test]
+    fn test_duration_mapping() {
+        let mapped = map_proto_type(Type::Message, Some(".google.protobuf.Duration"));
+        assert_eq!(mapped.rust_type, "i64");
+        assert_eq!(mapped.column_type.as_deref(), Some("BigInteger"));
+
+        let mapped = map_duration_type("interval").unwrap();
+        assert_eq!(mapped.rust_type, "chrono::TimeDelta");
+        assert_eq!(mapped.column_type.as_deref(), Some("Interval(None, None)"));
+        assert!(map_duration_type("seconds").is_none());
+    }
+
     #[test]
     fn test_timestamp_mapping() {
         let mapped = map_proto_type(Type::Message, Some(".google.protobuf.Timestamp"));
         assert_eq!(mapped.rust_type, "DateTimeUtc");
         assert!(mapped.requires_import.is_some());
     }
+
+    #[test]
+    fn test_map_entry_mapping() {
+        use prost_types::{FieldDescriptorProto, MessageOptions};
+
+        let entry = |value: Type, map_entry: bool| DescriptorProto {
+            name: Some("LabelsEntry".to_string()),
+            field: [(1, Type::String), (2, value)]
+                .into_iter()
+                .map(|(number, r#type)| FieldDescriptorProto {
+                    number: Some(number),
+                    r#type: Some(r#type as i32),
+                    ..Default::default()
+                })
+                .collect(),
+            options: Some(MessageOptions {
+                map_entry: Some(map_entry),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let map = map_map_entry(&entry(Type::Int64, true)).unwrap();
+        assert_eq!(map.mapped.rust_type, "Json");
+        assert_eq!(
+            map.hash_map.as_deref(),
+            Some("std::collections::HashMap<String, i64>")
+        );
+
+        // Message values stay untyped JSON
+        let map = map_map_entry(&entry(Type::Message, true)).unwrap();
+        assert!(map.hash_map.is_none());
+
+        assert!(map_map_entry(&entry(Type::Int64, false)).is_none());
+    }
+
+    #[test]
+    fn test_wrapper_mapping() {
+        let cases = [
+            ("StringValue", "Option<String>"),
+            ("Int64Value", "Option<i64>"),
+            ("UInt32Value", "Option<u32>"),
+            ("BoolValue", "Option<bool>"),
+            ("DoubleValue", "Option<f64>"),
+            ("BytesValue", "Option<Vec<u8>>"),
+        ];
+        for (wrapper, rust_type) in cases {
+            let name = format!(".google.protobuf.{}", wrapper);
+            assert_eq!(
+                map_proto_type(Type::Message, Some(&name)).rust_type,
+                rust_type
+            );
+            assert!(is_well_known_message(&name));
+        }
+        assert!(wrapped_type(".google.protobuf.Any").is_none());
+    }
 }
@@ -6,10 +6,80 @@ use prost_types::uninterpreted_option::NamePart;
 use prost_types::{
     compiler::CodeGeneratorRequest, field_descriptor_proto::Type, DescriptorProto,
     EnumDescriptorProto, EnumOptions, EnumValueDescriptorProto, FieldDescriptorProto,
-    FileDescriptorProto, MessageOptions, MethodDescriptorProto, OneofDescriptorProto, OneofOptions,
+    FileDescriptorProto, MessageOptions, MethodDescriptorProto,
+    MethodOptions as ProtoMethodOptions, OneofDescriptorProto, OneofOptions,
     ServiceDescriptorProto, ServiceOptions, UninterpretedOption,
 };
 
+/// Build a `(seaorm.<extension>)` option carrying an aggregate value
+fn seaorm_option(extension: &str, aggregate: &str) -> UninterpretedOption {
+    UninterpretedOption {
+        name: vec![NamePart {
+            name_part: format!("seaorm.{}", extension),
+            is_extension: true,
+        }],
+        aggregate_value: Some(aggregate.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Build a scalar field, optionally annotated with `(seaorm.column)`
+fn column_field(name: &str, number: i32, ty: Type, column: Option<&str>) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        r#type: Some(ty.into()),
+        options: column.map(|aggregate| prost_types::FieldOptions {
+            uninterpreted_option: vec![seaorm_option("column", aggregate)],
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Build a message annotated with `(seaorm.model)`
+fn model_message(name: &str, model: &str, field: Vec<FieldDescriptorProto>) -> DescriptorProto {
+    DescriptorProto {
+        name: Some(name.to_string()),
+        field,
+        options: Some(MessageOptions {
+            uninterpreted_option: vec![seaorm_option("model", model)],
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Wrap messages in a single proto3 file under the `test` package
+fn single_file_request(message_type: Vec<DescriptorProto>) -> CodeGeneratorRequest {
+    let file_descriptor = FileDescriptorProto {
+        name: Some("test/models.proto".to_string()),
+        package: Some("test".to_string()),
+        message_type,
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    };
+
+    CodeGeneratorRequest {
+        file_to_generate: vec!["test/models.proto".to_string()],
+        proto_file: vec![file_descriptor],
+        ..Default::default()
+    }
+}
+
+/// Find a generated file by suffix and return its content
+fn generated_content<'a>(
+    response: &'a prost_types::compiler::CodeGeneratorResponse,
+    suffix: &str,
+) -> &'a str {
+    response
+        .file
+        .iter()
+        .find(|f| f.name.as_deref().unwrap_or("").ends_with(suffix))
+        .and_then(|f| f.content.as_deref())
+        .unwrap_or_else(|| panic!("should generate a file ending in {}", suffix))
+}
+
 /// Create a test CodeGeneratorRequest with a simple User message
 fn create_test_request() -> CodeGeneratorRequest {
     // Create the seaorm.model option
@@ -1528,6 +1598,84 @@ fn test_generate_domain_type_with_range_validation() {
     );
 }
 
+#[test]
+fn test_generate_domain_type_with_exclusive_range() {
+    let input_field = |name: &str, number: i32, ty: Type, aggregate: &str| FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        r#type: Some(ty.into()),
+        options: Some(prost_types::FieldOptions {
+            uninterpreted_option: vec![seaorm_option("input", aggregate)],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let message = DescriptorProto {
+        name: Some("ListItemsRequest".to_string()),
+        field: vec![
+            input_field(
+                "limit",
+                1,
+                Type::Int32,
+                "validate: { range: { gt: 0, lt: 100 } }",
+            ),
+            // The stricter of an inclusive and an exclusive bound wins
+            input_field(
+                "offset",
+                2,
+                Type::Int64,
+                "validate: { range: { min: 5, gt: 0, lt: 1000 } }",
+            ),
+        ],
+        options: Some(MessageOptions {
+            uninterpreted_option: vec![seaorm_option(
+                "input_message",
+                "domain_type: \"ListItems\", generate_try_from: true",
+            )],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![message]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "list_items.rs");
+
+    assert!(
+        content.contains("range(min = 1i32, max = 99i32)"),
+        "exclusive bounds should become inclusive integer bounds. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("range(min = 5i64, max = 999i64)"),
+        "should keep the stricter lower bound. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_domain_error_exposes_field_errors() {
+    let request = create_domain_type_test_request();
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "create_user.rs");
+
+    assert!(
+        content.contains("pub fn field_errors(&self) -> Vec<(String, String)>"),
+        "DomainError should expose field_errors. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains(".map(|(path, error)| (path.to_string(), error.to_string()))"),
+        "validation errors should be keyed by their field path. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("Some((field, error)) => vec![(field.to_string(), error.to_string())]"),
+        "conversion errors should be keyed by the failing field. Got:\n{}",
+        content
+    );
+}
+
 #[test]
 fn test_skip_domain_type_without_input_options() {
     // Create a message without input_message options
@@ -1755,3 +1903,5767 @@ fn test_generate_domain_type_without_try_from() {
         "should not have DomainError when generate_try_from is false"
     );
 }
+
+// =============================================================================
+// Projection Tests
+// =============================================================================
+
+#[test]
+fn test_generate_projection_struct() {
+    let summary = model_message(
+        "UserSummary",
+        "projection: true",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field(
+                "email",
+                2,
+                Type::String,
+                Some("column_name: \"email_address\""),
+            ),
+            column_field("post_count", 3, Type::Int64, None),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![summary]))
+        .expect("generation should succeed");
+    assert!(response.error.is_none(), "should have no error");
+
+    let content = generated_content(&response, "user_summary.rs");
+
+    assert!(
+        content.contains("#[derive(Clone, Debug, PartialEq, FromQueryResult)]"),
+        "should derive FromQueryResult. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub struct UserSummary"),
+        "should name the struct after the message"
+    );
+    assert!(content.contains("pub id: i64"), "should have id field");
+    assert!(
+        content.contains("pub email: String"),
+        "should have email field"
+    );
+    assert!(
+        content.contains("pub post_count: i64"),
+        "should have post_count field"
+    );
+    assert!(
+        !content.contains("DeriveEntityModel"),
+        "projection should not derive DeriveEntityModel"
+    );
+    assert!(
+        !content.contains("sea_orm::model"),
+        "projection should not use the dense model macro"
+    );
+    assert!(
+        !content.contains("ActiveModelBehavior"),
+        "projection should not implement ActiveModelBehavior"
+    );
+    assert!(
+        !content.contains("#[sea_orm("),
+        "projection should not carry column attributes"
+    );
+}
+
+// =============================================================================
+// Validation Tests
+// =============================================================================
+
+#[test]
+fn test_duplicate_table_names_are_rejected() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+    // Defaults to `users` via snake_case of the message name
+    let users = model_message(
+        "Users",
+        "skip: false",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![user, users]))
+        .expect_err("duplicate table names should fail");
+
+    assert!(
+        matches!(err, protoc_gen_seaorm::GeneratorError::InvalidConfig(_)),
+        "should be an InvalidConfig error, got {:?}",
+        err
+    );
+    let message = err.to_string();
+    assert!(
+        message.contains("\"users\""),
+        "should name the table: {}",
+        message
+    );
+    assert!(
+        message.contains("test.User") && message.contains("test.Users"),
+        "should name both messages: {}",
+        message
+    );
+}
+
+// =============================================================================
+// Column Default Tests
+// =============================================================================
+
+#[test]
+fn test_proto2_field_default_becomes_column_default() {
+    let mut retries = column_field("retries", 2, Type::Int32, None);
+    retries.default_value = Some("3".to_string());
+
+    // An explicit seaorm default wins over the proto default
+    let mut status = column_field("status", 3, Type::String, Some("default_value: \"active\""));
+    status.default_value = Some("pending".to_string());
+
+    let job = model_message(
+        "Job",
+        "table_name: \"jobs\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            retries,
+            status,
+        ],
+    );
+
+    let mut request = single_file_request(vec![job]);
+    request.proto_file[0].syntax = Some("proto2".to_string());
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "job.rs");
+
+    assert!(
+        content.contains("#[sea_orm(default_value = \"3\")]"),
+        "proto2 default should become the column default. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("default_value = \"active\""),
+        "seaorm default should be kept"
+    );
+    assert!(
+        !content.contains("pending"),
+        "proto default should not override the seaorm default"
+    );
+}
+
+#[test]
+fn test_column_attribute_values_are_escaped() {
+    let path = column_field(
+        "path",
+        2,
+        Type::String,
+        Some(r#"default_value: "C:\\path \"quoted\"", column_name: "the\"path""#),
+    );
+    let file = model_message(
+        "File",
+        "table_name: \"files\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            path,
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![file]))
+        .expect("quotes and backslashes in option values should not break generation");
+    let content = generated_content(&response, "file.rs");
+
+    assert!(
+        content.contains(r#"default_value = "C:\\path \"quoted\"""#),
+        "the default should be an escaped string literal. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains(r#"column_name = "the\"path""#),
+        "the column name should be an escaped string literal. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_proto2_enum_default_uses_stored_value() {
+    let mut status = column_field("status", 2, Type::Enum, None);
+    status.type_name = Some(".test.Status".to_string());
+    status.default_value = Some("STATUS_ACTIVE".to_string());
+
+    // A file of its own, so enum value options cached by other tests don't apply
+    let mut request = create_enum_test_request();
+    request.file_to_generate = vec!["test/jobs.proto".to_string()];
+    let file = &mut request.proto_file[0];
+    file.name = Some("test/jobs.proto".to_string());
+    file.syntax = Some("proto2".to_string());
+    file.message_type.push(model_message(
+        "Job",
+        "table_name: \"jobs\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            status,
+        ],
+    ));
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "job.rs");
+
+    assert!(
+        content.contains("#[sea_orm(default_value = \"status_active\")]"),
+        "enum defaults should be the value's stored string. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_proto2_bytes_default_is_rejected() {
+    let mut payload = column_field("payload", 2, Type::Bytes, None);
+    payload.default_value = Some("\\001abc".to_string());
+    let blob = model_message(
+        "Blob",
+        "table_name: \"blobs\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            payload,
+        ],
+    );
+
+    let mut request = single_file_request(vec![blob]);
+    request.proto_file[0].syntax = Some("proto2".to_string());
+    let err = protoc_gen_seaorm::generate(request).expect_err("bytes defaults should fail");
+    assert!(
+        err.to_string().contains("Blob.payload has a proto default"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+// =============================================================================
+// Plugin Parameter Tests
+// =============================================================================
+
+#[test]
+fn test_cfg_parameter_gates_generated_files() {
+    let mut request = create_test_request();
+    request.parameter = Some("cfg=feature=\"db\"".to_string());
+    request.proto_file[0].enum_type = create_enum_test_request().proto_file[0].enum_type.clone();
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    assert_eq!(response.file.len(), 2, "should generate entity and enum");
+
+    for file in &response.file {
+        let content = file.content.as_deref().unwrap();
+        assert!(
+            content.starts_with("#![cfg(feature=\"db\")]\n"),
+            "{} should start with the cfg attribute. Got:\n{}",
+            file.name.as_deref().unwrap(),
+            content
+        );
+    }
+}
+
+#[test]
+fn test_unknown_parameter_is_rejected() {
+    let mut request = create_test_request();
+    request.parameter = Some("not_a_real_option=true".to_string());
+
+    let err = protoc_gen_seaorm::generate(request).expect_err("unknown parameter should fail");
+    assert!(
+        matches!(err, protoc_gen_seaorm::GeneratorError::InvalidConfig(_)),
+        "should be an InvalidConfig error, got {:?}",
+        err
+    );
+}
+
+#[test]
+fn test_generate_domain_into_active_model() {
+    let mut request = create_domain_type_test_request();
+    let file = &mut request.proto_file[0];
+
+    // Point the domain type at a User entity living in the same file
+    file.message_type[0].options = Some(MessageOptions {
+        uninterpreted_option: vec![seaorm_option(
+            "input_message",
+            "domain_type: \"CreateUser\", entity: \"User\"",
+        )],
+        ..Default::default()
+    });
+    file.message_type.push(model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, Some("unique: true")),
+            column_field("name", 3, Type::String, None),
+        ],
+    ));
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "create_user.rs");
+
+    assert!(
+        content
+            .contains("impl sea_orm::IntoActiveModel<super::user::ActiveModel> for CreateUser {"),
+        "should implement IntoActiveModel for the entity. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("email: sea_orm::ActiveValue::Set(self.email),"),
+        "should set the matching email column"
+    );
+    assert!(
+        content.contains("name: sea_orm::ActiveValue::Set(self.name),"),
+        "should set the matching name column"
+    );
+    assert!(
+        content.contains("id: sea_orm::ActiveValue::NotSet,"),
+        "the unmapped primary key should be NotSet. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("..Default::default()"),
+        "unmatched columns should stay NotSet"
+    );
+    assert!(!content.contains("self.id"), "id is not on the domain type");
+}
+
+#[test]
+fn test_into_active_model_sets_required_timestamps() {
+    let mut request = create_domain_type_test_request();
+    let file = &mut request.proto_file[0];
+
+    file.message_type[0].options = Some(MessageOptions {
+        uninterpreted_option: vec![seaorm_option(
+            "input_message",
+            "domain_type: \"CreateUser\", entity: \"User\"",
+        )],
+        ..Default::default()
+    });
+    let timestamp = |name: &str, number: i32, column: Option<&str>| {
+        let mut field = column_field(name, number, Type::Message, column);
+        field.type_name = Some(".google.protobuf.Timestamp".to_string());
+        field
+    };
+    file.message_type.push(model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, None),
+            timestamp("created_at", 3, None),
+            timestamp("deleted_at", 4, Some("nullable: true")),
+            timestamp(
+                "synced_at",
+                5,
+                Some("default_expr: \"Expr::current_timestamp()\""),
+            ),
+            timestamp("seen_at", 6, Some("temporal: \"naive\"")),
+        ],
+    ));
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "create_user.rs");
+
+    assert!(
+        content.contains("created_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),"),
+        "required timestamps should be set to now. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("seen_at: sea_orm::ActiveValue::Set(chrono::Utc::now().naive_utc()),"),
+        "naive timestamps should be set in their own type. Got:\n{}",
+        content
+    );
+    assert!(
+        !content.contains("deleted_at:") && !content.contains("synced_at:"),
+        "nullable or defaulted timestamps should stay NotSet. Got:\n{}",
+        content
+    );
+}
+
+/// A domain request targeting `User` with the given entity columns
+fn domain_entity_request(columns: Vec<FieldDescriptorProto>) -> CodeGeneratorRequest {
+    let mut request = create_domain_type_test_request();
+    let file = &mut request.proto_file[0];
+    file.message_type[0].options = Some(MessageOptions {
+        uninterpreted_option: vec![seaorm_option(
+            "input_message",
+            "domain_type: \"CreateUser\", entity: \"User\"",
+        )],
+        ..Default::default()
+    });
+    file.message_type
+        .push(model_message("User", "table_name: \"users\"", columns));
+    request
+}
+
+#[test]
+fn test_into_active_model_wraps_values_for_nullable_columns() {
+    let request = domain_entity_request(vec![
+        column_field("id", 1, Type::Int64, Some("primary_key: true")),
+        column_field("email", 2, Type::String, None),
+        column_field("name", 3, Type::String, Some("nullable: true")),
+    ]);
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "create_user.rs");
+
+    assert!(
+        content.contains("name: sea_orm::ActiveValue::Set(Some(self.name)),"),
+        "a required domain value should fill a nullable column as Some. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_into_active_model_rejects_mismatched_column_types() {
+    let request = domain_entity_request(vec![
+        column_field("id", 1, Type::Int64, Some("primary_key: true")),
+        column_field("email", 2, Type::Int64, None),
+        column_field("name", 3, Type::String, None),
+    ]);
+
+    let err = protoc_gen_seaorm::generate(request).expect_err("mismatched types should fail");
+    assert!(
+        matches!(&err, protoc_gen_seaorm::GeneratorError::InvalidConfig(msg)
+            if msg.contains("CreateUser.email is String but column User.email is i64")),
+        "should name the field and both types, got {:?}",
+        err
+    );
+}
+
+// =============================================================================
+// Relation Helper Tests
+// =============================================================================
+
+#[test]
+fn test_generate_relation_helpers() {
+    let user = model_message(
+        "User",
+        r#"table_name: "users", relation_helpers: true, relations: [
+            {name: "posts", type: RELATION_TYPE_HAS_MANY, related: "post"},
+            {name: "profile", type: RELATION_TYPE_HAS_ONE, related: "profile"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("name", 2, Type::String, None),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains("impl Entity {"),
+        "should emit an Entity impl. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub async fn find_with_posts<C>(\n        db: &C,\n        id: i64,\n    ) -> Result<Option<(Model, Vec<super::post::Model>)>, DbErr>"),
+        "should generate a has_many helper. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains(".find_with_related(super::post::Entity)"),
+        "has_many helper should use find_with_related"
+    );
+    assert!(
+        content.contains("pub async fn find_with_profile<C>("),
+        "should generate a has_one helper"
+    );
+    assert!(
+        content.contains(".find_also_related(super::profile::Entity)"),
+        "has_one helper should use find_also_related"
+    );
+}
+
+#[test]
+fn test_relation_helpers_skip_targets_reached_twice() {
+    let post = model_message(
+        "Post",
+        r#"table_name: "posts", relation_helpers: true, relations: [
+            {name: "author", type: RELATION_TYPE_BELONGS_TO, related: "user", foreign_key: "author_id"},
+            {name: "editor", type: RELATION_TYPE_BELONGS_TO, related: "user", foreign_key: "editor_id"},
+            {name: "comments", type: RELATION_TYPE_HAS_MANY, related: "comment"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("author_id", 2, Type::Int64, None),
+            column_field("editor_id", 3, Type::Int64, None),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![post]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "post.rs");
+
+    assert!(
+        !content.contains("find_with_author") && !content.contains("find_with_editor"),
+        "user has no single Related impl to join through. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub async fn find_with_comments<C>("),
+        "other relations keep their helpers. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_generate_storage_with_custom_db_error() {
+    let mut request = create_service_test_request();
+    request.proto_file[0].service[0].options = Some(ServiceOptions {
+        uninterpreted_option: vec![seaorm_option(
+            "service",
+            "generate_storage: true, db_error: \"my_crate::DbError\"",
+        )],
+        ..Default::default()
+    });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user_service_storage.rs");
+
+    assert!(
+        content.contains("Database(#[from] my_crate::DbError)"),
+        "Database variant should wrap the custom error and derive From for it. Got:\n{}",
+        content
+    );
+    assert!(
+        !content.contains("sea_orm::DbErr"),
+        "default DbErr should not be used"
+    );
+}
+
+#[test]
+fn test_generate_storage_with_by_ref_arguments() {
+    let mut request = create_service_test_request();
+    request.proto_file[0].service[0].options = Some(ServiceOptions {
+        uninterpreted_option: vec![seaorm_option(
+            "service",
+            "generate_storage: true, by_ref: true",
+        )],
+        ..Default::default()
+    });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user_service_storage.rs");
+
+    assert!(
+        content.contains(
+            "async fn get_user(&self, request: &GetUserRequest) -> Result<User, StorageError>;"
+        ),
+        "requests should be taken by reference. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_generate_storage_generic_over_connection() {
+    let mut request = create_service_test_request();
+    request.parameter = Some("emit_connection_trait_bound".to_string());
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user_service_storage.rs");
+
+    assert!(
+        content.contains(
+            "async fn get_user<C: sea_orm::ConnectionTrait + Send + Sync>(\n        &self,\n        conn: &C,\n        request: GetUserRequest,\n    ) -> Result<User, StorageError>;"
+        ),
+        "methods should take a generic connection. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_repeated_primary_key_is_rejected() {
+    let mut tags = column_field("tags", 1, Type::String, Some("primary_key: true"));
+    tags.label = Some(prost_types::field_descriptor_proto::Label::Repeated.into());
+
+    let article = model_message("Article", "table_name: \"articles\"", vec![tags]);
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![article]))
+        .expect_err("repeated primary key should fail");
+
+    assert!(
+        matches!(err, protoc_gen_seaorm::GeneratorError::InvalidConfig(_)),
+        "should be an InvalidConfig error, got {:?}",
+        err
+    );
+    let message = err.to_string();
+    assert!(
+        message.contains("Article.tags")
+            && message.contains("repeated fields cannot be primary keys"),
+        "should explain which field is invalid and why: {}",
+        message
+    );
+}
+
+#[test]
+fn test_message_primary_key_is_rejected() {
+    let mut metadata = column_field("metadata", 1, Type::Message, Some("primary_key: true"));
+    metadata.type_name = Some(".test.Metadata".to_string());
+
+    let article = model_message("Article", "table_name: \"articles\"", vec![metadata]);
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![article]))
+        .expect_err("JSON primary key should fail");
+    assert!(
+        err.to_string()
+            .contains("stored as JSON cannot be primary keys"),
+        "should explain JSON columns can't be keys: {}",
+        err
+    );
+}
+
+// =============================================================================
+// Update Struct Tests
+// =============================================================================
+
+#[test]
+fn test_generate_update_struct() {
+    let mut bio = column_field("bio", 4, Type::String, None);
+    bio.proto3_optional = Some(true);
+
+    let user = model_message(
+        "User",
+        r#"table_name: "users", update_struct: true, version_column: "lock_version", soft_delete: "deleted_at""#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, None),
+            column_field("age", 3, Type::Int32, None),
+            bio,
+            column_field(
+                "email_domain",
+                5,
+                Type::String,
+                Some(r#"generated: "split_part(email, '@', 2)""#),
+            ),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains("#[derive(Clone, Debug, Default, PartialEq)]\npub struct UserUpdate {"),
+        "should generate the update struct. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub email: Option<String>,"),
+        "fields should be optional"
+    );
+    assert!(content.contains("pub age: Option<i32>,"));
+    assert!(
+        content.contains("pub bio: Option<Option<String>>,"),
+        "nullable columns should be clearable"
+    );
+    assert!(
+        !content.contains("pub id: Option<i64>"),
+        "primary key should not be updatable"
+    );
+    let update_struct = content
+        .split("pub struct UserUpdate {")
+        .nth(1)
+        .and_then(|rest| rest.split('}').next())
+        .unwrap_or("");
+    for managed in ["email_domain", "lock_version", "deleted_at"] {
+        assert!(
+            !update_struct.contains(managed),
+            "{} should not be updatable. Got:\n{}",
+            managed,
+            content
+        );
+    }
+    assert!(
+        content.contains("pub fn apply_to(&self, model: &mut ActiveModel)"),
+        "should generate apply_to"
+    );
+    assert!(
+        content.contains(
+            "if let Some(value) = &self.email {\n            model.email = sea_orm::ActiveValue::Set(value.clone());\n        }"
+        ),
+        "apply_to should only set Some fields. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_colliding_field_names_are_rejected() {
+    let account = model_message(
+        "Account",
+        "table_name: \"accounts\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("fooBar", 2, Type::String, None),
+            column_field("foo_bar", 3, Type::String, None),
+        ],
+    );
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![account]))
+        .expect_err("colliding field names should fail");
+
+    assert!(
+        matches!(err, protoc_gen_seaorm::GeneratorError::InvalidConfig(_)),
+        "should be an InvalidConfig error, got {:?}",
+        err
+    );
+    let message = err.to_string();
+    assert!(
+        message.contains("\"fooBar\"") && message.contains("\"foo_bar\""),
+        "should name both fields: {}",
+        message
+    );
+    assert!(
+        message.contains("Account"),
+        "should name the message: {}",
+        message
+    );
+}
+
+// =============================================================================
+// Base Column Include Tests
+// =============================================================================
+
+#[test]
+fn test_entities_include_shared_base_columns() {
+    let mut created_at = column_field("created_at", 2, Type::Message, None);
+    created_at.type_name = Some(".google.protobuf.Timestamp".to_string());
+
+    // Plain message without (seaorm.model): only used as a mixin
+    let base = DescriptorProto {
+        name: Some("BaseColumns".to_string()),
+        field: vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            created_at,
+        ],
+        ..Default::default()
+    };
+    let user = model_message(
+        "User",
+        "table_name: \"users\", include: \"BaseColumns\"",
+        vec![column_field("email", 1, Type::String, None)],
+    );
+    let tag = model_message(
+        "Tag",
+        "table_name: \"tags\", include: \".test.BaseColumns\"",
+        vec![column_field(
+            "slug",
+            1,
+            Type::String,
+            Some("primary_key: true, auto_increment: false"),
+        )],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![base, user, tag]))
+        .expect("generation should succeed");
+    assert_eq!(
+        response.file.len(),
+        2,
+        "the base message itself should not be generated"
+    );
+
+    let user = generated_content(&response, "user.rs");
+    assert!(
+        user.contains(
+            "    #[sea_orm(primary_key, auto_increment = false)]\n    pub id: i64,\n    #[sea_orm(column_type = \"TimestampWithTimeZone\")]\n    pub created_at: DateTimeUtc,\n    pub email: String,"
+        ),
+        "base columns should come first and keep the inherited primary key. Got:\n{}",
+        user
+    );
+
+    let tag = generated_content(&response, "tag.rs");
+    assert!(
+        tag.contains(
+            "    pub id: i64,\n    #[sea_orm(column_type = \"TimestampWithTimeZone\")]\n    pub created_at: DateTimeUtc,"
+        ),
+        "the entity's own primary key should replace the base key. Got:\n{}",
+        tag
+    );
+    assert!(
+        tag.contains("#[sea_orm(primary_key, auto_increment = false)]\n    pub slug: String"),
+        "own primary key should be kept"
+    );
+}
+
+#[test]
+fn test_unknown_include_is_rejected() {
+    let user = model_message(
+        "User",
+        "include: \"Missing\"",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect_err("unknown include should fail");
+    assert!(
+        err.to_string()
+            .contains("includes unknown message \"Missing\""),
+        "should name the missing message: {}",
+        err
+    );
+}
+
+#[test]
+fn test_emit_schema_graph() {
+    let user = model_message(
+        "User",
+        r#"table_name: "users", relations: [
+            {name: "posts", type: RELATION_TYPE_HAS_MANY, related: "post"}
+        ]"#,
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+    let post = model_message(
+        "Post",
+        r#"table_name: "posts", relations: [
+            {name: "author", type: RELATION_TYPE_BELONGS_TO, related: "user", foreign_key: "user_id"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("user_id", 2, Type::Int64, None),
+        ],
+    );
+
+    let mut request = single_file_request(vec![user, post]);
+    request.parameter = Some("emit_schema_graph=true".to_string());
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let graph = generated_content(&response, "test/schema.dot");
+
+    assert!(
+        graph.contains("digraph schema {"),
+        "should be a DOT digraph"
+    );
+    assert!(
+        graph.contains("\"user\" [label=\"User\\n(users)\"];"),
+        "should list the User entity. Got:\n{}",
+        graph
+    );
+    assert!(
+        graph.contains("\"post\" [label=\"Post\\n(posts)\"];"),
+        "should list the Post entity"
+    );
+    assert!(
+        graph.contains("\"user\" -> \"post\" [label=\"posts (has_many)\"];"),
+        "should have the has_many edge"
+    );
+    assert!(
+        graph.contains("\"post\" -> \"user\" [label=\"author (belongs_to)\"];"),
+        "should have the belongs_to edge"
+    );
+}
+
+// =============================================================================
+// Column Type Tests
+// =============================================================================
+
+#[test]
+fn test_max_length_produces_varchar() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, Some("max_length: 255")),
+            column_field("bio", 3, Type::String, None),
+            column_field(
+                "code",
+                4,
+                Type::String,
+                Some("max_length: 8, column_type: \"Char(Some(8))\""),
+            ),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains(
+            "#[sea_orm(column_type = \"String(StringLen::N(255))\")]\n    pub email: String,"
+        ),
+        "max_length should produce a VarChar column. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("    pub bio: String,") && !content.contains("StringLen::N(8)"),
+        "fields without max_length keep the default string type"
+    );
+    assert!(
+        content.contains("column_type = \"Char(Some(8))\""),
+        "explicit column_type should win over max_length"
+    );
+}
+
+#[test]
+fn test_generate_embeddable_struct() {
+    let address = model_message(
+        "Address",
+        "embeddable: true",
+        vec![
+            column_field("street", 1, Type::String, None),
+            column_field("city", 2, Type::String, None),
+        ],
+    );
+    let mut location = column_field("location", 2, Type::Message, Some("embed: true"));
+    location.type_name = Some(".test.Address".to_string());
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            location,
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![address, user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "address.rs");
+
+    assert!(
+        content.contains(
+            "#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]\npub struct Address {"
+        ),
+        "should derive FromJsonQueryResult and serde. Got:\n{}",
+        content
+    );
+    assert!(content.contains("use serde::{Deserialize, Serialize};"));
+    assert!(content.contains("pub street: String,"));
+    assert!(
+        !content.contains("DeriveEntityModel"),
+        "embeddable should not be an entity"
+    );
+
+    let user = generated_content(&response, "user.rs");
+    assert!(
+        user.contains("pub location: Address"),
+        "embedding entity should reference the struct"
+    );
+}
+
+#[test]
+fn test_embeddable_oneof_becomes_typed_enum() {
+    let mut card = column_field("card_token", 2, Type::String, None);
+    card.oneof_index = Some(0);
+    let mut account = column_field("account_number", 3, Type::Int64, None);
+    account.oneof_index = Some(0);
+    let mut payment = model_message(
+        "PaymentInfo",
+        "embeddable: true",
+        vec![column_field("holder", 1, Type::String, None), card, account],
+    );
+    payment.oneof_decl = vec![OneofDescriptorProto {
+        name: Some("method".to_string()),
+        ..Default::default()
+    }];
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![payment]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "payment_info.rs");
+
+    assert!(
+        content.contains("pub method: Option<PaymentInfoMethod>,"),
+        "oneof should be a typed field. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("#[serde(rename_all = \"snake_case\")]\npub enum PaymentInfoMethod {"),
+        "should generate a serde enum for the oneof. Got:\n{}",
+        content
+    );
+    assert!(content.contains("CardToken(String),"));
+    assert!(content.contains("AccountNumber(i64),"));
+    assert!(
+        !content.contains("pub card_token"),
+        "oneof variants should not become struct fields"
+    );
+}
+
+// =============================================================================
+// Optimistic Locking Tests
+// =============================================================================
+
+#[test]
+fn test_generate_version_column_with_locked_update() {
+    let document = model_message(
+        "Document",
+        "table_name: \"documents\", version_column: \"lock_version\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("title", 2, Type::String, None),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![document]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "document.rs");
+
+    assert!(
+        content.contains("#[sea_orm(default_value = \"0\")]\n    pub lock_version: i32,"),
+        "should add the version column. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub async fn update_with_lock<C>("),
+        "should generate update_with_lock. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("mut model: ActiveModel,\n    ) -> Result<Model, DbErr>"),
+        "update_with_lock should take the active model"
+    );
+    assert!(
+        content.contains("model.lock_version = sea_orm::ActiveValue::Set(expected + 1);"),
+        "should bump the version"
+    );
+    assert!(
+        content.contains(".filter(Column::LockVersion.eq(expected))"),
+        "should filter on the prior version"
+    );
+}
+
+// =============================================================================
+// Strict Option Parsing Tests
+// =============================================================================
+
+/// Encode a request whose first message carries `(seaorm.model)` as extension data
+///
+/// prost drops extension fields, so the options are spliced into the encoded
+/// `DescriptorProto` by hand. Repeating the non-repeated `options` field (7) makes
+/// decoders merge it with the uninterpreted options already present. With `nested`,
+/// the first message nested in it carries the options instead.
+fn encode_with_model_extension(
+    mut request: CodeGeneratorRequest,
+    extension: protoc_gen_seaorm::options::seaorm::MessageOptions,
+    nested: bool,
+) -> Vec<u8> {
+    use prost::encoding::{encode_key, encode_varint, WireType};
+    use prost::Message;
+
+    fn length_delimited(tag: u32, payload: &[u8], buf: &mut Vec<u8>) {
+        encode_key(tag, WireType::LengthDelimited, buf);
+        encode_varint(payload.len() as u64, buf);
+        buf.extend_from_slice(payload);
+    }
+
+    let mut file = request.proto_file.remove(0);
+    let mut message = file.message_type.remove(0);
+
+    let mut options = Vec::new();
+    length_delimited(50000, &extension.encode_to_vec(), &mut options);
+    let with_options = |message: DescriptorProto| {
+        let mut bytes = message.encode_to_vec();
+        length_delimited(7, &options, &mut bytes);
+        bytes
+    };
+    let message_bytes = if nested {
+        let inner = message.nested_type.remove(0);
+        let mut bytes = message.encode_to_vec();
+        length_delimited(3, &with_options(inner), &mut bytes);
+        bytes
+    } else {
+        with_options(message)
+    };
+
+    let mut file_bytes = file.encode_to_vec();
+    length_delimited(4, &message_bytes, &mut file_bytes);
+
+    let mut bytes = request.encode_to_vec();
+    length_delimited(15, &file_bytes, &mut bytes);
+    bytes
+}
+
+#[test]
+fn test_strict_options_rejects_divergent_sources() {
+    let request = |parameter: &str| CodeGeneratorRequest {
+        file_to_generate: vec!["test/strict.proto".to_string()],
+        parameter: Some(parameter.to_string()).filter(|p| !p.is_empty()),
+        proto_file: vec![FileDescriptorProto {
+            name: Some("test/strict.proto".to_string()),
+            package: Some("test".to_string()),
+            message_type: vec![model_message(
+                "Ledger",
+                "table_name: \"ledgers\"",
+                vec![column_field(
+                    "id",
+                    1,
+                    Type::Int64,
+                    Some("primary_key: true"),
+                )],
+            )],
+            syntax: Some("proto3".to_string()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let extension = protoc_gen_seaorm::options::seaorm::MessageOptions {
+        table_name: "ledger_entries".to_string(),
+        ..Default::default()
+    };
+
+    // By default the extension data wins
+    let bytes = encode_with_model_extension(request(""), extension.clone(), false);
+    let response =
+        protoc_gen_seaorm::generate_from_bytes(&bytes).expect("generation should succeed");
+    let content = generated_content(&response, "ledger.rs");
+    assert!(
+        content.contains("#[sea_orm(table_name = \"ledger_entries\")]"),
+        "extension data should take precedence. Got:\n{}",
+        content
+    );
+
+    // In strict mode the disagreement is an error
+    let bytes = encode_with_model_extension(request("strict_options=true"), extension, false);
+    let err = protoc_gen_seaorm::generate_from_bytes(&bytes)
+        .expect_err("divergent options should fail in strict mode");
+    assert!(
+        matches!(err, protoc_gen_seaorm::GeneratorError::OptionsParseError(_)),
+        "should be an options parse error, got {:?}",
+        err
+    );
+    assert!(
+        err.to_string().contains("Ledger (seaorm.model)"),
+        "error should name the message, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_strict_options_checks_nested_messages() {
+    let mut ledger = DescriptorProto {
+        name: Some("Ledger".to_string()),
+        ..Default::default()
+    };
+    ledger.nested_type.push(model_message(
+        "Entry",
+        "table_name: \"entries\"",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    ));
+    let request = CodeGeneratorRequest {
+        file_to_generate: vec!["test/strict_nested.proto".to_string()],
+        parameter: Some("strict_options=true".to_string()),
+        proto_file: vec![FileDescriptorProto {
+            name: Some("test/strict_nested.proto".to_string()),
+            package: Some("test".to_string()),
+            message_type: vec![ledger],
+            syntax: Some("proto3".to_string()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let extension = protoc_gen_seaorm::options::seaorm::MessageOptions {
+        table_name: "ledger_entries".to_string(),
+        ..Default::default()
+    };
+
+    let bytes = encode_with_model_extension(request, extension, true);
+    let err = protoc_gen_seaorm::generate_from_bytes(&bytes)
+        .expect_err("divergent nested options should fail in strict mode");
+    assert!(
+        err.to_string().contains("Ledger.Entry (seaorm.model)"),
+        "error should name the nested message, got: {}",
+        err
+    );
+}
+
+// =============================================================================
+// Domain Error Type Tests
+// =============================================================================
+
+#[test]
+fn test_domain_type_with_shared_error_type() {
+    let mut request = create_domain_type_test_request();
+    request.proto_file[0].message_type[0].options = Some(MessageOptions {
+        uninterpreted_option: vec![seaorm_option(
+            "input_message",
+            "domain_type: \"CreateUser\", generate_try_from: true, error_type: \"crate::errors::DomainError\"",
+        )],
+        ..Default::default()
+    });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "create_user.rs");
+
+    assert!(
+        content.contains("type Error = crate::errors::DomainError;"),
+        "TryFrom should use the shared error type. Got:\n{}",
+        content
+    );
+    assert!(
+        !content.contains("pub enum DomainError"),
+        "should not emit a local DomainError"
+    );
+}
+
+// =============================================================================
+// Model Default Tests
+// =============================================================================
+
+#[test]
+fn test_derive_default_uses_column_defaults() {
+    let mut nickname = column_field("nickname", 4, Type::String, None);
+    nickname.proto3_optional = Some(true);
+    let account = model_message(
+        "Account",
+        "table_name: \"accounts\", derive_default: true",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("status", 2, Type::String, Some("default_value: \"active\"")),
+            column_field("credits", 3, Type::Int32, Some("default_value: \"100\"")),
+            nickname,
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![account]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "account.rs");
+
+    assert!(
+        content.contains("impl Default for Model {"),
+        "should generate a Default impl. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("status: \"active\".to_string(),"),
+        "string default should be used. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("credits: 100,"),
+        "int default should be used"
+    );
+    assert!(content.contains("id: Default::default(),"));
+    assert!(content.contains("nickname: Default::default(),"));
+}
+
+#[test]
+fn test_derive_default_maps_enum_defaults_to_variants() {
+    let mut status = column_field(
+        "status",
+        2,
+        Type::Enum,
+        Some("default_value: \"status_active\""),
+    );
+    status.type_name = Some(".test.Status".to_string());
+    let mut previous = column_field("previous", 3, Type::Enum, None);
+    previous.type_name = Some(".test.Status".to_string());
+
+    // A file of its own, so enum options cached by other tests don't apply
+    let mut request = create_enum_test_request();
+    request.file_to_generate = vec!["test/defaults.proto".to_string()];
+    let file = &mut request.proto_file[0];
+    file.name = Some("test/defaults.proto".to_string());
+    file.message_type.push(model_message(
+        "Job",
+        "table_name: \"jobs\", derive_default: true",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            status,
+            previous,
+        ],
+    ));
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "job.rs");
+
+    assert!(
+        content.contains("status: Status::StatusActive,"),
+        "the stored default should map back to its variant. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("previous: Status::StatusUnknown,"),
+        "enums without a default_value should use their default variant. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_derive_default_rejects_enum_without_default() {
+    let mut status = column_field("status", 2, Type::Enum, None);
+    status.type_name = Some(".test.Status".to_string());
+
+    let mut request = create_enum_test_request();
+    request.file_to_generate = vec!["test/no_default.proto".to_string()];
+    let file = &mut request.proto_file[0];
+    file.name = Some("test/no_default.proto".to_string());
+    file.enum_type[0]
+        .options
+        .as_mut()
+        .unwrap()
+        .uninterpreted_option[0]
+        .aggregate_value = Some("db_type: \"string\", derive_default: false".to_string());
+    file.message_type.push(model_message(
+        "Job",
+        "table_name: \"jobs\", derive_default: true",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            status,
+        ],
+    ));
+
+    let err = protoc_gen_seaorm::generate(request)
+        .expect_err("an enum column without a default should fail");
+    assert!(
+        err.to_string()
+            .contains("derive_default on Job has no default for Job.status"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+// =============================================================================
+// Cross-Package Relation Tests
+// =============================================================================
+
+#[test]
+fn test_cross_package_relation_uses_module_root() {
+    let account = FileDescriptorProto {
+        name: Some("auth/v1/account.proto".to_string()),
+        package: Some("auth.v1".to_string()),
+        message_type: vec![model_message(
+            "Account",
+            "table_name: \"accounts\"",
+            vec![column_field(
+                "id",
+                1,
+                Type::Int64,
+                Some("primary_key: true"),
+            )],
+        )],
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    };
+    let post = FileDescriptorProto {
+        name: Some("blog/v1/post.proto".to_string()),
+        package: Some("blog.v1".to_string()),
+        dependency: vec!["auth/v1/account.proto".to_string()],
+        message_type: vec![
+            model_message(
+                "Post",
+                r#"table_name: "posts", relations: [
+                    {name: "author", type: RELATION_TYPE_BELONGS_TO, related: "Account", foreign_key: "author_id"},
+                    {name: "comments", type: RELATION_TYPE_HAS_MANY, related: "Comment"}
+                ]"#,
+                vec![
+                    column_field("id", 1, Type::Int64, Some("primary_key: true")),
+                    column_field("author_id", 2, Type::Int64, None),
+                ],
+            ),
+            model_message(
+                "Comment",
+                "table_name: \"comments\"",
+                vec![column_field(
+                    "id",
+                    1,
+                    Type::Int64,
+                    Some("primary_key: true"),
+                )],
+            ),
+        ],
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    };
+    let request = CodeGeneratorRequest {
+        file_to_generate: vec!["blog/v1/post.proto".to_string()],
+        parameter: Some("module_root=crate::entities".to_string()),
+        proto_file: vec![account, post],
+        ..Default::default()
+    };
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "blog/v1/post.rs");
+
+    assert!(
+        content.contains("pub author: HasOne<crate::entities::auth::v1::account::Entity>,"),
+        "out-of-package relation should be addressed from the module root. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub comments: HasMany<super::comment::Entity>,"),
+        "same-package relation should stay relative. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Streaming Storage Tests
+// =============================================================================
+
+#[test]
+fn test_server_streaming_method_returns_stream() {
+    let mut request = create_service_test_request();
+    request.proto_file[0].service[0]
+        .method
+        .push(MethodDescriptorProto {
+            name: Some("WatchUsers".to_string()),
+            input_type: Some(".test.ListUsersRequest".to_string()),
+            output_type: Some(".test.User".to_string()),
+            server_streaming: Some(true),
+            ..Default::default()
+        });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user_service_storage.rs");
+
+    assert!(
+        content.contains("fn watch_users(\n        &self,\n        request: ListUsersRequest,\n    ) -> futures::stream::BoxStream<'_, Result<User, StorageError>>;"),
+        "server-streaming method should return a stream. Got:\n{}",
+        content
+    );
+    assert!(
+        !content.contains("async fn watch_users"),
+        "stream-returning methods are not async"
+    );
+    assert!(
+        content.contains("async fn get_user("),
+        "unary methods keep their signature"
+    );
+}
+
+#[test]
+fn test_client_streaming_method_takes_stream() {
+    let mut request = create_service_test_request();
+    request.proto_file[0].service[0]
+        .method
+        .push(MethodDescriptorProto {
+            name: Some("ImportUsers".to_string()),
+            input_type: Some(".test.CreateUserRequest".to_string()),
+            output_type: Some(".test.ListUsersResponse".to_string()),
+            client_streaming: Some(true),
+            ..Default::default()
+        });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user_service_storage.rs");
+
+    assert!(
+        content.contains("async fn import_users(\n        &self,\n        request: futures::stream::BoxStream<'static, CreateUserRequest>,\n    ) -> Result<ListUsersResponse, StorageError>;"),
+        "client-streaming method should take a stream and return a single result. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_bidi_streaming_method_takes_and_returns_stream() {
+    let mut request = create_service_test_request();
+    request.proto_file[0].service[0]
+        .method
+        .push(MethodDescriptorProto {
+            name: Some("SyncUsers".to_string()),
+            input_type: Some(".test.GetUserRequest".to_string()),
+            output_type: Some(".test.User".to_string()),
+            client_streaming: Some(true),
+            server_streaming: Some(true),
+            ..Default::default()
+        });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user_service_storage.rs");
+
+    assert!(
+        content.contains("fn sync_users(\n        &self,\n        request: futures::stream::BoxStream<'static, GetUserRequest>,\n    ) -> futures::stream::BoxStream<'_, Result<User, StorageError>>;"),
+        "bidi method should take and return streams. Got:\n{}",
+        content
+    );
+    assert!(!content.contains("async fn sync_users"));
+}
+
+// =============================================================================
+// Column Index Tests
+// =============================================================================
+
+#[test]
+fn test_indexed_column_attribute() {
+    let order = model_message(
+        "Order",
+        "table_name: \"orders\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("customer_id", 2, Type::Int64, Some("indexed: true")),
+            column_field(
+                "reference",
+                3,
+                Type::String,
+                Some("unique: true, indexed: true"),
+            ),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![order]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "order.rs");
+
+    assert!(
+        content.contains("#[sea_orm(indexed)]\n    pub customer_id: i64,"),
+        "indexed column should carry the indexed attribute. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("#[sea_orm(unique)]\n    pub reference: String,"),
+        "unique already implies an index. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Table Prefix Tests
+// =============================================================================
+
+#[test]
+fn test_table_prefix_parameter() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+    let audit_log = model_message(
+        "AuditLog",
+        "",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+    let mut request = single_file_request(vec![user, audit_log]);
+    request.parameter = Some("table_prefix=app_".to_string());
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+
+    let user = generated_content(&response, "user.rs");
+    assert!(
+        user.contains("#[sea_orm(table_name = \"app_users\")]"),
+        "explicit table name should be prefixed. Got:\n{}",
+        user
+    );
+
+    let audit_log = generated_content(&response, "audit_log.rs");
+    assert!(
+        audit_log.contains("#[sea_orm(table_name = \"app_audit_log\")]"),
+        "default table name should be prefixed. Got:\n{}",
+        audit_log
+    );
+}
+
+// =============================================================================
+// JSON Oneof Enum Tests
+// =============================================================================
+
+#[test]
+fn test_json_oneof_enum_is_usable_as_column() {
+    let mut click = column_field("click", 2, Type::String, None);
+    click.oneof_index = Some(0);
+    let mut purchase = column_field("purchase", 3, Type::Int64, None);
+    purchase.oneof_index = Some(0);
+    let mut event = model_message(
+        "Event",
+        "table_name: \"events\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            click,
+            purchase,
+        ],
+    );
+    event.oneof_decl = vec![OneofDescriptorProto {
+        name: Some("payload".to_string()),
+        options: Some(OneofOptions {
+            uninterpreted_option: vec![seaorm_option("oneof", "strategy: \"json\"")],
+        }),
+    }];
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![event]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "event.rs");
+
+    assert!(
+        content.contains(
+            "#[sea_orm(column_name = \"payload\", column_type = \"Json\")]\n    pub payload: Option<EventPayload>,"
+        ),
+        "JSON oneof column should use the typed enum. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains(
+            "#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]\n#[serde(rename_all = \"snake_case\")]\npub enum EventPayload {"
+        ),
+        "enum should derive FromJsonQueryResult. Got:\n{}",
+        content
+    );
+    assert!(content.contains("use serde::{Deserialize, Serialize};"));
+    assert!(content.contains("Purchase(i64),"));
+}
+
+// =============================================================================
+// Validation Tests
+// =============================================================================
+
+#[test]
+fn test_validate_reports_every_problem() {
+    let user = model_message(
+        "User",
+        r#"table_name: "users", relations: [
+            {name: "posts", type: RELATION_TYPE_HAS_MANY, related: "Post"},
+            {name: "team", type: RELATION_TYPE_BELONGS_TO, related: "Team", foreign_key: "team_id"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("team_id", 2, Type::Int64, None),
+        ],
+    );
+    let post = model_message(
+        "Post",
+        "table_name: \"posts\"",
+        vec![column_field("title", 1, Type::String, None)],
+    );
+    let legacy_post = model_message(
+        "LegacyPost",
+        "table_name: \"posts\"",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+    let request = single_file_request(vec![user, post, legacy_post]);
+
+    let errors = protoc_gen_seaorm::validate(&request).expect_err("should find problems");
+    let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+
+    assert_eq!(
+        errors.len(),
+        3,
+        "should report each problem: {:?}",
+        messages
+    );
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("Duplicate table name \"posts\"")),
+        "should report the duplicate table: {:?}",
+        messages
+    );
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("Post has no primary key")),
+        "should report the missing primary key: {:?}",
+        messages
+    );
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("Relation \"team\" on User points to unknown entity \"Team\"")),
+        "should report the dangling relation: {:?}",
+        messages
+    );
+
+    // A clean request validates without producing output
+    let clean = single_file_request(vec![model_message(
+        "Team",
+        "table_name: \"teams\"",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    )]);
+    assert!(protoc_gen_seaorm::validate(&clean).is_ok());
+}
+
+#[test]
+fn test_validate_resolves_relations_by_package() {
+    let member = model_message(
+        "Member",
+        r#"table_name: "members", relations: [
+            {name: "team", type: RELATION_TYPE_BELONGS_TO, related: "billing.Team", foreign_key: "team_id"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("team_id", 2, Type::Int64, None),
+        ],
+    );
+    let team = model_message(
+        "Team",
+        "table_name: \"teams\"",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+    let request = single_file_request(vec![member, team]);
+
+    let errors = protoc_gen_seaorm::validate(&request).expect_err("should find problems");
+    assert!(
+        errors.iter().any(|e| e
+            .to_string()
+            .contains("Relation \"team\" on Member points to unknown entity \"billing.Team\"")),
+        "test.Team shouldn't satisfy a relation to billing.Team: {:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_empty_message_maps_to_unit() {
+    let mut request = create_service_test_request();
+    let methods = &mut request.proto_file[0].service[0].method;
+    methods.push(MethodDescriptorProto {
+        name: Some("Ping".to_string()),
+        input_type: Some(".google.protobuf.Empty".to_string()),
+        output_type: Some(".google.protobuf.Empty".to_string()),
+        ..Default::default()
+    });
+    methods.push(MethodDescriptorProto {
+        name: Some("DeleteUser".to_string()),
+        input_type: Some(".test.GetUserRequest".to_string()),
+        output_type: Some(".google.protobuf.Empty".to_string()),
+        ..Default::default()
+    });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user_service_storage.rs");
+
+    assert!(
+        content.contains("async fn ping(&self) -> Result<(), StorageError>;"),
+        "Empty input should drop the request parameter. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains(
+            "async fn delete_user(&self, request: GetUserRequest) -> Result<(), StorageError>;"
+        ),
+        "Empty output should become (). Got:\n{}",
+        content
+    );
+    assert!(
+        !content.contains("Empty"),
+        "should not reference an Empty type"
+    );
+}
+
+// =============================================================================
+// Single-Variant Oneof Tests
+// =============================================================================
+
+#[test]
+fn test_single_variant_oneof_is_plain_nullable_column() {
+    let mut nickname = column_field("nickname", 2, Type::String, None);
+    nickname.oneof_index = Some(0);
+    let mut score = column_field("score", 3, Type::Message, None);
+    score.type_name = Some(".google.protobuf.Int64Value".to_string());
+    score.oneof_index = Some(1);
+    let mut profile = model_message(
+        "Profile",
+        "table_name: \"profiles\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            nickname,
+            score,
+        ],
+    );
+    profile.oneof_decl = vec![
+        OneofDescriptorProto {
+            name: Some("display".to_string()),
+            ..Default::default()
+        },
+        OneofDescriptorProto {
+            name: Some("ranking".to_string()),
+            ..Default::default()
+        },
+    ];
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![profile]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "profile.rs");
+
+    assert!(
+        content.contains(
+            "#[sea_orm(column_name = \"nickname\", nullable)]\n    pub nickname: Option<String>,"
+        ),
+        "single-variant oneof should be a plain nullable column. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub score: Option<i64>,"),
+        "wrapper types should not be double-wrapped. Got:\n{}",
+        content
+    );
+    assert!(
+        !content.contains("display_type") && !content.contains("pub display:"),
+        "flattened oneofs have no discriminator or oneof column"
+    );
+}
+
+// =============================================================================
+// Enum Name Override Tests
+// =============================================================================
+
+#[test]
+fn test_enum_name_override_flows_into_columns() {
+    let status = EnumDescriptorProto {
+        name: Some("Status".to_string()),
+        value: vec![
+            EnumValueDescriptorProto {
+                name: Some("STATUS_PENDING".to_string()),
+                number: Some(0),
+                ..Default::default()
+            },
+            EnumValueDescriptorProto {
+                name: Some("STATUS_SHIPPED".to_string()),
+                number: Some(1),
+                ..Default::default()
+            },
+        ],
+        options: Some(EnumOptions {
+            uninterpreted_option: vec![seaorm_option(
+                "enum_opt",
+                "name: \"OrderState\", db_type: \"integer\"",
+            )],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut state = column_field("status", 2, Type::Enum, None);
+    state.type_name = Some(".test.Status".to_string());
+    let order = model_message(
+        "Order",
+        "table_name: \"orders\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            state,
+        ],
+    );
+
+    let mut request = single_file_request(vec![order]);
+    request.proto_file[0].enum_type = vec![status];
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+
+    let enum_content = generated_content(&response, "order_state.rs");
+    assert!(
+        enum_content.contains("pub enum OrderState"),
+        "enum should use the overridden name. Got:\n{}",
+        enum_content
+    );
+    assert!(
+        enum_content.contains("enum_name = \"order_state\""),
+        "overridden name should be recorded as the enum_name. Got:\n{}",
+        enum_content
+    );
+
+    let entity_content = generated_content(&response, "order.rs");
+    assert!(
+        entity_content.contains("pub status: OrderState,"),
+        "referencing column should use the overridden enum name. Got:\n{}",
+        entity_content
+    );
+}
+
+#[test]
+fn test_native_enum_uses_enum_db_type_and_name() {
+    let status = EnumDescriptorProto {
+        name: Some("Status".to_string()),
+        value: vec![
+            EnumValueDescriptorProto {
+                name: Some("STATUS_PENDING".to_string()),
+                number: Some(0),
+                ..Default::default()
+            },
+            EnumValueDescriptorProto {
+                name: Some("STATUS_SHIPPED".to_string()),
+                number: Some(1),
+                ..Default::default()
+            },
+        ],
+        options: Some(EnumOptions {
+            uninterpreted_option: vec![seaorm_option(
+                "enum_opt",
+                "name: \"OrderState\", db_type: \"native\"",
+            )],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let mut request = single_file_request(vec![]);
+    request.proto_file[0].enum_type = vec![status];
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "order_state.rs");
+
+    assert!(
+        content.contains(
+            "#[sea_orm(rs_type = \"String\", db_type = \"Enum\", enum_name = \"order_state\")]"
+        ),
+        "native enums should be stored as a database enum named after the enum. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub const TYPE_NAME: &'static str = \"order_state\";"),
+        "migrations should get the native type name. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Finder Tests
+// =============================================================================
+
+#[test]
+fn test_finders_generate_paginated_list_helper() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\", finders: true",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, None),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains(
+            "pub async fn list<C>(\n        db: &C,\n        page: u64,\n        page_size: u64,\n    ) -> Result<(Vec<Model>, u64), DbErr>"
+        ),
+        "should generate a list helper returning items and a total. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains(".order_by_asc(Column::Id)"),
+        "pages should be ordered by the primary key. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("let total = paginator.num_items().await?;")
+            && content.contains("let items = paginator.fetch_page(page).await?;")
+            && content.contains("Ok((items, total))"),
+        "should return the fetched page with the total count. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_finders_generate_find_by_ids_with_pk_type() {
+    let session = model_message(
+        "Session",
+        "table_name: \"sessions\", finders: true",
+        vec![
+            column_field("token", 1, Type::String, Some("primary_key: true")),
+            column_field("user_id", 2, Type::Int64, None),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![session]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "session.rs");
+
+    assert!(
+        content.contains(
+            "pub async fn find_by_ids<C>(db: &C, ids: &[String]) -> Result<Vec<Model>, DbErr>"
+        ),
+        "batch lookup should take the primary key type. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains(".filter(Column::Token.is_in(ids.iter().cloned()))"),
+        "batch lookup should filter on the primary key column. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Explicit Import Tests
+// =============================================================================
+
+#[test]
+fn test_no_prelude_glob_emits_explicit_imports() {
+    let requests = [
+        create_test_request(),
+        create_enum_test_request(),
+        create_service_test_request(),
+        create_domain_type_test_request(),
+    ];
+
+    for mut request in requests {
+        request.parameter = Some("no_prelude_glob".to_string());
+        let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+        assert!(!response.file.is_empty(), "should generate files");
+
+        for file in &response.file {
+            let content = file.content.as_deref().unwrap_or("");
+            assert!(
+                !content.contains("::*"),
+                "{} should not contain glob imports. Got:\n{}",
+                file.name.as_deref().unwrap_or(""),
+                content
+            );
+        }
+    }
+
+    let mut request = create_test_request();
+    request.parameter = Some("no_prelude_glob".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    assert!(
+        content.contains("use sea_orm::entity::prelude::{"),
+        "entity should import the SeaORM names it uses. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_no_prelude_glob_imports_sea_orm_names_from_sea_orm() {
+    let post = model_message(
+        "Post",
+        r#"table_name: "posts", soft_delete: "deleted_at""#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("title", 2, Type::String, None),
+        ],
+    );
+    let mut request = single_file_request(vec![post]);
+    request.parameter = Some("no_prelude_glob".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "post.rs");
+
+    assert!(
+        !content.contains("super::prelude::"),
+        "only the package's own types should come from super::prelude. Got:\n{}",
+        content
+    );
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+    let sea_orm_import = compact
+        .split("usesea_orm::entity::prelude::{")
+        .nth(1)
+        .and_then(|rest| rest.split('}').next())
+        .expect("entity should import from the SeaORM prelude");
+    assert!(
+        sea_orm_import.split(',').any(|name| name == "Select"),
+        "Select should be imported from SeaORM. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Repeated Field Storage Tests
+// =============================================================================
+
+#[test]
+fn test_repeated_as_blob_generates_codecs() {
+    let mut samples = column_field("samples", 2, Type::Int64, Some("repeated_as: \"blob\""));
+    samples.label = Some(prost_types::field_descriptor_proto::Label::Repeated.into());
+    let mut tags = column_field("tags", 3, Type::String, Some("repeated_as: \"jsonb\""));
+    tags.label = Some(prost_types::field_descriptor_proto::Label::Repeated.into());
+    let series = model_message(
+        "Series",
+        "table_name: \"series\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            samples,
+            tags,
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![series]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "series.rs");
+
+    assert!(
+        content.contains(
+            "#[sea_orm(column_type = \"VarBinary(StringLen::None)\")]\n    pub samples: Vec<u8>,"
+        ),
+        "blob storage should use a binary column. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub fn encode_samples(values: &[i64]) -> Result<Vec<u8>, DbErr>"),
+        "should generate an encoder. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub fn decode_samples(bytes: &[u8]) -> Result<Vec<i64>, DbErr>"),
+        "should generate a decoder. Got:\n{}",
+        content
+    );
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+    assert!(
+        compact.contains("letlen=u32::try_from(values.len())")
+            && compact.contains("iflen.checked_mul(WIDTH)!=Some(body.len())"),
+        "blob lengths should be checked. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("#[sea_orm(column_type = \"JsonBinary\")]\n    pub tags: Vec<String>,"),
+        "jsonb storage should use a JSON array column. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_repeated_as_blob_rejects_non_numeric_elements() {
+    let mut names = column_field("names", 2, Type::String, Some("repeated_as: \"blob\""));
+    names.label = Some(prost_types::field_descriptor_proto::Label::Repeated.into());
+    let series = model_message(
+        "Series",
+        "table_name: \"series\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            names,
+        ],
+    );
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![series]))
+        .expect_err("blob storage of strings should be rejected");
+    assert!(err.to_string().contains("Series.names"), "got: {}", err);
+}
+
+// =============================================================================
+// Acronym Naming Tests
+// =============================================================================
+
+#[test]
+fn test_acronyms_parameter_keeps_acronyms_whole_in_columns() {
+    let key = model_message(
+        "Key",
+        "table_name: \"keys\"",
+        vec![
+            column_field("UserID", 1, Type::Int64, Some("primary_key: true")),
+            column_field("APIKey", 2, Type::String, None),
+            column_field("APIURL", 3, Type::String, None),
+        ],
+    );
+
+    let mut request = single_file_request(vec![key]);
+    request.parameter = Some("acronyms=ID:URL:API".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "key.rs");
+
+    for column in [
+        "pub user_id: i64,",
+        "pub api_key: String,",
+        "pub api_url: String,",
+    ] {
+        assert!(
+            content.contains(column),
+            "should contain {}. Got:\n{}",
+            column,
+            content
+        );
+    }
+}
+
+// =============================================================================
+// Auto Reverse Relation Tests
+// =============================================================================
+
+#[test]
+fn test_auto_reverse_synthesizes_self_referential_children() {
+    let category = model_message(
+        "Category",
+        r#"table_name: "categories", relations: [
+            {name: "parent", type: RELATION_TYPE_BELONGS_TO, related: "category", foreign_key: "parent_id", auto_reverse: true}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("parent_id", 2, Type::Int64, Some("nullable: true")),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![category]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "category.rs");
+
+    assert!(
+        content.contains(
+            "relation_enum = \"Parent\",\n        relation_reverse = \"Children\",\n        from = \"parent_id\","
+        ),
+        "declared parent should be paired with the synthesized reverse. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub parent: HasOne<Entity>"),
+        "should keep the declared parent relation. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains(
+            "relation_enum = \"Children\",\n        relation_reverse = \"Parent\"\n    )]\n    pub children: HasMany<Entity>,"
+        ),
+        "should synthesize a children relation. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Backend Tests
+// =============================================================================
+
+#[test]
+fn test_embed_column_type_follows_backend() {
+    let mut metadata = column_field("metadata", 2, Type::Message, Some("embed: true"));
+    metadata.type_name = Some(".test.Metadata".to_string());
+    let article = model_message(
+        "Article",
+        "table_name: \"articles\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            metadata,
+        ],
+    );
+
+    for (parameter, column_type) in [
+        (None, "JsonBinary"),
+        (Some("backend=postgres"), "JsonBinary"),
+        (Some("backend=sqlite"), "Json"),
+        (Some("backend=mysql"), "Json"),
+    ] {
+        let mut request = single_file_request(vec![article.clone()]);
+        request.parameter = parameter.map(str::to_string);
+        let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+        let content = generated_content(&response, "article.rs");
+
+        let expected = format!(
+            "#[sea_orm(column_type = \"{}\")]\n    pub metadata: Metadata,",
+            column_type
+        );
+        assert!(
+            content.contains(&expected),
+            "{:?} should store embeds as {}. Got:\n{}",
+            parameter,
+            column_type,
+            content
+        );
+    }
+}
+
+#[test]
+fn test_unknown_backend_is_rejected() {
+    let mut request = single_file_request(vec![]);
+    request.parameter = Some("backend=oracle".to_string());
+
+    let err = protoc_gen_seaorm::generate(request).expect_err("unknown backend should fail");
+    assert!(err.to_string().contains("oracle"), "got: {}", err);
+}
+
+// =============================================================================
+// Reserved Name Tests
+// =============================================================================
+
+#[test]
+fn test_reserved_names_are_noted_in_entity() {
+    let mut user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+    user.reserved_name = vec!["legacy_email".to_string()];
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains("//! Reserved field names: legacy_email"),
+        "reserved names should be noted. Got:\n{}",
+        content
+    );
+    assert!(
+        !content.contains("pub legacy_email"),
+        "reserved names should not be generated"
+    );
+}
+
+#[test]
+fn test_generated_field_colliding_with_reserved_name_is_rejected() {
+    let mut category = model_message(
+        "Category",
+        r#"table_name: "categories", relations: [
+            {name: "parent", type: RELATION_TYPE_BELONGS_TO, related: "category", foreign_key: "parent_id", auto_reverse: true}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("parent_id", 2, Type::Int64, Some("nullable: true")),
+        ],
+    );
+    category.reserved_name = vec!["children".to_string()];
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![category]))
+        .expect_err("a relation reusing a reserved name should be rejected");
+    assert!(
+        err.to_string().contains("\"children\"") && err.to_string().contains("reserves"),
+        "got: {}",
+        err
+    );
+}
+
+// =============================================================================
+// Auto Entities Tests
+// =============================================================================
+
+#[test]
+fn test_auto_entities_service_generates_entities_and_domain_types() {
+    let message = |name: &str, fields: Vec<FieldDescriptorProto>| DescriptorProto {
+        name: Some(name.to_string()),
+        field: fields,
+        ..Default::default()
+    };
+    let users = FieldDescriptorProto {
+        name: Some("users".to_string()),
+        number: Some(1),
+        label: Some(prost_types::field_descriptor_proto::Label::Repeated.into()),
+        r#type: Some(Type::Message.into()),
+        type_name: Some(".autosvc.User".to_string()),
+        ..Default::default()
+    };
+    let method = |name: &str, input: &str, output: &str| MethodDescriptorProto {
+        name: Some(name.to_string()),
+        input_type: Some(format!(".autosvc.{}", input)),
+        output_type: Some(format!(".autosvc.{}", output)),
+        ..Default::default()
+    };
+
+    let file = FileDescriptorProto {
+        name: Some("autosvc/user_service.proto".to_string()),
+        package: Some("autosvc".to_string()),
+        message_type: vec![
+            message(
+                "User",
+                vec![
+                    column_field("id", 1, Type::Int64, None),
+                    column_field("email", 2, Type::String, None),
+                ],
+            ),
+            message(
+                "CreateUserRequest",
+                vec![column_field("email", 1, Type::String, None)],
+            ),
+            message(
+                "ListUsersRequest",
+                vec![column_field("page_size", 1, Type::Int32, None)],
+            ),
+            message("ListUsersResponse", vec![users]),
+        ],
+        service: vec![ServiceDescriptorProto {
+            name: Some("UserService".to_string()),
+            method: vec![
+                method("CreateUser", "CreateUserRequest", "User"),
+                method("ListUsers", "ListUsersRequest", "ListUsersResponse"),
+            ],
+            options: Some(ServiceOptions {
+                uninterpreted_option: vec![seaorm_option(
+                    "service",
+                    "generate_storage: true, auto_entities: true",
+                )],
+                ..Default::default()
+            }),
+        }],
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    };
+    let request = CodeGeneratorRequest {
+        file_to_generate: vec!["autosvc/user_service.proto".to_string()],
+        proto_file: vec![file],
+        ..Default::default()
+    };
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+
+    // The response message carrying users yields a User entity keyed on id
+    let entity = generated_content(&response, "autosvc/user.rs");
+    assert!(
+        entity.contains("#[sea_orm(table_name = \"user\")]"),
+        "User should become an entity. Got:\n{}",
+        entity
+    );
+    assert!(
+        entity.contains("#[sea_orm(primary_key"),
+        "id should become the primary key. Got:\n{}",
+        entity
+    );
+    assert!(
+        !response
+            .file
+            .iter()
+            .any(|f| f.name.as_deref() == Some("autosvc/list_users_response.rs")),
+        "wrapper responses should not become entities"
+    );
+
+    // Requests become domain types, which the storage trait takes
+    let domain = generated_content(&response, "autosvc/create_user.rs");
+    assert!(
+        domain.contains("pub struct CreateUser"),
+        "CreateUserRequest should become a domain type. Got:\n{}",
+        domain
+    );
+    generated_content(&response, "autosvc/list_users.rs");
+
+    let storage = generated_content(&response, "autosvc/user_service_storage.rs");
+    assert!(
+        storage.contains("request: CreateUser)"),
+        "storage trait should take the domain type. Got:\n{}",
+        storage
+    );
+}
+
+// =============================================================================
+// Widening Tests
+// =============================================================================
+
+#[test]
+fn test_widen_int32_primary_key_to_i64() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field(
+                "id",
+                1,
+                Type::Int32,
+                Some("primary_key: true, widen: \"i64\""),
+            ),
+            column_field("email", 2, Type::String, None),
+        ],
+    );
+    let update = DescriptorProto {
+        name: Some("UpdateUserRequest".to_string()),
+        field: vec![
+            column_field("id", 1, Type::Int32, None),
+            column_field("email", 2, Type::String, None),
+        ],
+        options: Some(MessageOptions {
+            uninterpreted_option: vec![seaorm_option(
+                "input_message",
+                "domain_type: \"UpdateUser\", entity: \"User\"",
+            )],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user, update]))
+        .expect("generation should succeed");
+
+    let entity = generated_content(&response, "test/user.rs");
+    assert!(
+        entity.contains("column_type = \"BigInteger\"") && entity.contains("pub id: i64,"),
+        "id should be stored as a bigint. Got:\n{}",
+        entity
+    );
+    assert!(
+        entity.contains("pub fn id_narrowed(&self) -> Result<i32, std::num::TryFromIntError>")
+            && entity.contains("i32::try_from(self.id)"),
+        "should convert the column back to the proto type. Got:\n{}",
+        entity
+    );
+
+    // The domain type keeps the proto's i32 and widens it into the active model
+    let domain = generated_content(&response, "test/update_user.rs");
+    assert!(
+        domain.contains("pub id: i32,"),
+        "domain type should keep the proto type. Got:\n{}",
+        domain
+    );
+    assert!(
+        domain.contains("id: sea_orm::ActiveValue::Set(self.id.into()),"),
+        "should widen the id into the active model. Got:\n{}",
+        domain
+    );
+}
+
+#[test]
+fn test_widen_pk_parameter_and_unsupported_widening() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int32,
+            Some("primary_key: true"),
+        )],
+    );
+    let mut request = single_file_request(vec![user]);
+    request.parameter = Some("widen_pk=true".to_string());
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let entity = generated_content(&response, "user.rs");
+    assert!(
+        entity.contains("pub id: i64,"),
+        "widen_pk should widen int32 primary keys. Got:\n{}",
+        entity
+    );
+
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![column_field(
+            "name",
+            1,
+            Type::String,
+            Some("widen: \"i64\""),
+        )],
+    );
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect_err("strings cannot be widened");
+    assert!(
+        err.to_string().contains("cannot be widened"),
+        "got: {}",
+        err
+    );
+}
+
+// =============================================================================
+// No Prelude Tests
+// =============================================================================
+
+#[test]
+fn test_no_prelude_writes_fully_qualified_paths() {
+    let mut created_at = column_field("created_at", 3, Type::Message, None);
+    created_at.type_name = Some(".google.protobuf.Timestamp".to_string());
+    let user = model_message(
+        "User",
+        r#"table_name: "users", finders: true, relations: [
+            {name: "posts", type: RELATION_TYPE_HAS_MANY, related: "post"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, None),
+            created_at,
+        ],
+    );
+    let mut request = single_file_request(vec![user]);
+    request.parameter = Some("no_prelude".to_string());
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        !content.contains("sea_orm::entity::prelude::*"),
+        "should not glob-import the SeaORM prelude. Got:\n{}",
+        content
+    );
+    for path in [
+        "sea_orm::entity::prelude::DeriveEntityModel",
+        "pub created_at: sea_orm::entity::prelude::DateTimeUtc,",
+        "pub posts: sea_orm::entity::prelude::HasMany<super::post::Entity>,",
+        "impl sea_orm::entity::prelude::ActiveModelBehavior for ActiveModel",
+        "use sea_orm::entity::prelude::EntityTrait as _;",
+    ] {
+        assert!(
+            content.contains(path),
+            "should contain `{}`. Got:\n{}",
+            path,
+            content
+        );
+    }
+    assert!(
+        !content.contains(": DateTimeUtc") && !content.contains(" HasMany<"),
+        "no prelude name should be left unqualified. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Tagged Oneof Tests
+// =============================================================================
+
+#[test]
+fn test_tagged_oneof_generates_db_conversions() {
+    let mut card = column_field("card", 2, Type::String, None);
+    card.oneof_index = Some(0);
+    let mut points = column_field("points", 3, Type::Int64, None);
+    points.oneof_index = Some(0);
+    let mut payment = model_message(
+        "Payment",
+        "table_name: \"payments\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            card,
+            points,
+        ],
+    );
+    payment.oneof_decl = vec![OneofDescriptorProto {
+        name: Some("method".to_string()),
+        options: Some(OneofOptions {
+            uninterpreted_option: vec![seaorm_option("oneof", "strategy: \"tagged\"")],
+        }),
+    }];
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![payment]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "payment.rs");
+
+    assert!(
+        content.contains("pub method_type: Option<String>,")
+            && content.contains("pub method_value: Option<String>,"),
+        "tagged oneof should keep its columns. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub enum PaymentMethod {\n    Card(String),\n    Points(i64),\n}"),
+        "should generate the oneof enum. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub fn from_db(disc: &str, value: &str) -> Result<Self, DbErr> {")
+            && content.contains("\"points\" => value.parse().map(Self::Points)"),
+        "should reconstruct the oneof from its columns. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub fn to_db(&self) -> (String, String) {")
+            && content.contains("Self::Card(value) => (\"card\".to_string(), value.to_string()),"),
+        "should decompose the oneof into its columns. Got:\n{}",
+        content
+    );
+    assert!(
+        !content.contains("use serde::"),
+        "tagged oneofs with scalar payloads don't need serde"
+    );
+}
+
+// =============================================================================
+// Custom Column Type Tests
+// =============================================================================
+
+#[test]
+fn test_custom_column_type_with_nested_quotes() {
+    let place = model_message(
+        "Place",
+        "table_name: \"places\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field(
+                "location",
+                2,
+                Type::String,
+                Some(r#"column_type: "Custom(\"geography(Point,4326)\")", nullable: true"#),
+            ),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![place]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "place.rs");
+
+    assert!(
+        content.contains(r#"column_type = "Custom(\"geography(Point,4326)\")""#),
+        "custom column type should survive with its quotes and commas. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub location: Option<String>,"),
+        "custom types keep the proto-mapped Rust type, and options after it still apply. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Table Statement Tests
+// =============================================================================
+
+#[test]
+fn test_table_statement_includes_every_column() {
+    let mut created_at = column_field("created_at", 4, Type::Message, None);
+    created_at.type_name = Some(".google.protobuf.Timestamp".to_string());
+    let user = model_message(
+        "User",
+        "table_name: \"users\", table_statement: true",
+        vec![
+            column_field(
+                "id",
+                1,
+                Type::Int64,
+                Some("primary_key: true, auto_increment: true"),
+            ),
+            column_field("email", 2, Type::String, Some("unique: true")),
+            column_field(
+                "nickname",
+                3,
+                Type::String,
+                Some("nullable: true, max_length: 32, indexed: true"),
+            ),
+            created_at,
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    // Compare ignoring prettyplease's line breaks and trailing commas
+    let compact = content
+        .split_whitespace()
+        .collect::<String>()
+        .replace(",)", ")");
+
+    assert!(
+        content.contains(
+            "pub fn create_table_statement() -> sea_orm::sea_query::TableCreateStatement {"
+        ),
+        "should generate the statement helper. Got:\n{}",
+        content
+    );
+    for column in [
+        ".col(ColumnDef::new(Column::Id).big_integer().not_null().primary_key().auto_increment())",
+        ".col(ColumnDef::new(Column::Email).string().not_null().unique_key())",
+        ".col(ColumnDef::new_with_type(Column::Nickname,Column::Nickname.def().get_column_type().clone()))",
+        ".col(ColumnDef::new_with_type(Column::CreatedAt,Column::CreatedAt.def().get_column_type().clone()).not_null())",
+    ] {
+        assert!(
+            compact.contains(column),
+            "should define `{}`. Got:\n{}",
+            column,
+            content
+        );
+    }
+    assert!(
+        content.contains(
+            "pub fn create_index_statements() -> Vec<sea_orm::sea_query::IndexCreateStatement> {"
+        ) && compact.contains(
+            "Index::create().name(\"idx_users_nickname\").table(Self).col(Column::Nickname)"
+        ),
+        "indexed columns should get index statements. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Domain Field Selection Tests
+// =============================================================================
+
+#[test]
+fn test_domain_fields_whitelist() {
+    let mut request = create_domain_type_test_request();
+    let message = &mut request.proto_file[0].message_type[0];
+    message.options = Some(MessageOptions {
+        uninterpreted_option: vec![seaorm_option(
+            "input_message",
+            r#"domain_type: "CreateUser", generate_try_from: true, fields: ["email", "name"]"#,
+        )],
+        ..Default::default()
+    });
+    message
+        .field
+        .push(column_field("page_token", 10, Type::String, None));
+    message
+        .field
+        .push(column_field("request_id", 11, Type::String, None));
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "create_user.rs");
+
+    assert!(
+        content.contains("pub email: String,") && content.contains("pub name: String,"),
+        "whitelisted fields should be kept. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("email: proto.email") && content.contains("name: proto.name"),
+        "TryFrom should convert whitelisted fields. Got:\n{}",
+        content
+    );
+    assert!(
+        !content.contains("page_token") && !content.contains("request_id"),
+        "other fields should be left out of the struct and TryFrom. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_domain_fields_whitelist_rejects_unknown_field() {
+    let mut request = create_domain_type_test_request();
+    request.proto_file[0].message_type[0].options = Some(MessageOptions {
+        uninterpreted_option: vec![seaorm_option(
+            "input_message",
+            r#"domain_type: "CreateUser", fields: ["email", "nickname"]"#,
+        )],
+        ..Default::default()
+    });
+
+    let err = protoc_gen_seaorm::generate(request).expect_err("unknown fields should fail");
+    assert!(err.to_string().contains("\"nickname\""), "got: {}", err);
+}
+
+// =============================================================================
+// Changeset Tests
+// =============================================================================
+
+#[test]
+fn test_changeset_reports_set_columns() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\", changeset: true",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, None),
+            column_field("display_name", 3, Type::String, None),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains("impl ActiveModel {")
+            && content.contains("pub fn changed_columns(&self) -> Vec<Column> {"),
+        "should generate the changeset helper. Got:\n{}",
+        content
+    );
+    for (field, column) in [
+        ("id", "Id"),
+        ("email", "Email"),
+        ("display_name", "DisplayName"),
+    ] {
+        assert!(
+            content.contains(&format!(
+                "if self.{}.is_set() {{\n            columns.push(Column::{});",
+                field, column
+            )),
+            "should check {}. Got:\n{}",
+            field,
+            content
+        );
+    }
+}
+
+// =============================================================================
+// Non-Exhaustive Tests
+// =============================================================================
+
+#[test]
+fn test_non_exhaustive_marks_storage_error_and_enums() {
+    let mut request = create_service_test_request();
+    request.parameter = Some("non_exhaustive=true".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user_service_storage.rs");
+    assert!(
+        content.contains("#[non_exhaustive]\npub enum StorageError"),
+        "StorageError should be non_exhaustive. Got:\n{}",
+        content
+    );
+
+    let mut request = create_enum_test_request();
+    request.parameter = Some("non_exhaustive=true".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "status.rs");
+    assert!(
+        content.contains("#[non_exhaustive]\npub enum Status"),
+        "enum should be non_exhaustive. Got:\n{}",
+        content
+    );
+
+    // Off by default
+    let response = protoc_gen_seaorm::generate(create_service_test_request())
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user_service_storage.rs");
+    assert!(!content.contains("#[non_exhaustive]"));
+}
+
+// =============================================================================
+// Field Mask Tests
+// =============================================================================
+
+#[test]
+fn test_field_mask_resolves_paths_to_columns() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\", field_mask: true",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, None),
+            column_field("display_name", 3, Type::String, None),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains("pub fn column_for_field_path(path: &str) -> Option<Column> {"),
+        "should generate the path lookup. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("\"email\" => Some(Column::Email),")
+            && content.contains("\"display_name\" => Some(Column::DisplayName),"),
+        "paths should resolve to columns. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub fn column_for_field_number(number: i32) -> Option<Column> {")
+            && content.contains("2 => Some(Column::Email),"),
+        "field numbers should resolve to columns. Got:\n{}",
+        content
+    );
+}
+
+/// A `UserService.UpdateUser` RPC marked `masked_update`, updating a `User` entity
+fn masked_update_request(user_model: &str) -> CodeGeneratorRequest {
+    let user = model_message(
+        "User",
+        user_model,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, None),
+        ],
+    );
+    let message_field = |name: &str, number: i32, type_name: &str| FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        r#type: Some(Type::Message as i32),
+        type_name: Some(type_name.to_string()),
+        ..Default::default()
+    };
+    let update_request = DescriptorProto {
+        name: Some("UpdateUserRequest".to_string()),
+        field: vec![
+            message_field("user", 1, ".test.User"),
+            message_field("update_mask", 2, ".google.protobuf.FieldMask"),
+        ],
+        ..Default::default()
+    };
+
+    let mut request = single_file_request(vec![user, update_request]);
+    request.proto_file[0].service.push(ServiceDescriptorProto {
+        name: Some("UserService".to_string()),
+        method: vec![MethodDescriptorProto {
+            name: Some("UpdateUser".to_string()),
+            input_type: Some(".test.UpdateUserRequest".to_string()),
+            output_type: Some(".test.User".to_string()),
+            options: Some(ProtoMethodOptions {
+                uninterpreted_option: vec![seaorm_option("rpc", "masked_update: true")],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }],
+        options: Some(ServiceOptions {
+            uninterpreted_option: vec![seaorm_option("service", "generate_storage: true")],
+            ..Default::default()
+        }),
+    });
+    request
+}
+
+#[test]
+fn test_masked_update_sets_only_masked_columns() {
+    let request = masked_update_request("table_name: \"users\", field_mask: true");
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user_service_storage.rs");
+    let compact: String = content
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .replace(",)", ")");
+
+    assert!(
+        compact.contains(
+            "pubfnupdate_user_masked(model:super::user::Model,update_mask:&[String])->Result<super::user::ActiveModel,StorageError>"
+        ),
+        "should generate the masked update helper. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains("letmutactive=model.into_active_model();forpathinupdate_mask{")
+            && compact.contains("super::user::column_for_field_path(path)")
+            && compact.contains("active.reset(column);"),
+        "only masked columns should be set. Got:\n{}",
+        content
+    );
+
+    // The entity must expose the path lookup
+    let request = masked_update_request("table_name: \"users\"");
+    let err = protoc_gen_seaorm::generate(request).expect_err("missing field_mask should fail");
+    assert!(err.to_string().contains("field_mask: true"), "{}", err);
+}
+
+// =============================================================================
+// Manual Entity Tests
+// =============================================================================
+
+#[test]
+fn test_manual_entity_expands_derive_entity_model() {
+    let post = model_message(
+        "Post",
+        r#"table_name: "posts", relations: [
+            {name: "author", type: RELATION_TYPE_BELONGS_TO, related: "user", foreign_key: "author_id"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field(
+                "title",
+                2,
+                Type::String,
+                Some("column_name: \"post_title\""),
+            ),
+            column_field("author_id", 3, Type::Int64, Some("indexed: true")),
+        ],
+    );
+    let mut request = single_file_request(vec![post]);
+    request.parameter = Some("manual_entity=true".to_string());
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "post.rs");
+    let compact: String = content
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .replace(",)", ")");
+
+    assert!(
+        !content.contains("DeriveEntityModel") && !content.contains("#[sea_orm::model]"),
+        "should not use the derive. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains("implEntityNameforEntity{fntable_name(&self)->&str{\"posts\"}}"),
+        "should name the table explicitly. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains("pubstructModel{pubid:i64,pubtitle:String,pubauthor_id:i64,}"),
+        "Model should carry no column attributes. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains("pubenumColumn{Id,#[sea_orm(column_name=\"post_title\")]Title,AuthorId,}"),
+        "should generate an explicit Column enum. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains(
+            "implColumnTraitforColumn{typeEntityName=Entity;fndef(&self)->ColumnDef{matchself{"
+        ) && compact.contains(
+            "Self::AuthorId=>{<i64assea_orm::sea_query::ValueType>::column_type().def().indexed()}"
+        ),
+        "should generate an explicit ColumnTrait impl. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains(
+            "implPrimaryKeyTraitforPrimaryKey{typeValueType=i64;fnauto_increment()->bool{false}}"
+        ),
+        "should generate the PrimaryKeyTrait impl. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains("Self::Author=>{Entity::belongs_to(super::user::Entity).from(Column::AuthorId).to(super::user::Column::Id).into()}")
+            && compact.contains("implRelated<super::user::Entity>forEntity{fnto()->RelationDef{Relation::Author.def()}}"),
+        "should expand relations. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Column Name Template Tests
+// =============================================================================
+
+#[test]
+fn test_column_name_template_applies_to_defaulted_columns() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, None),
+            column_field("name", 3, Type::String, Some("column_name: \"full_name\"")),
+        ],
+    );
+    let mut request = single_file_request(vec![user]);
+    request.parameter = Some("column_name_template=c_{field}".to_string());
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains("#[sea_orm(column_name = \"c_email\")]\n    pub email: String"),
+        "defaulted columns should follow the template. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("column_name = \"c_id\""),
+        "primary keys should follow the template too. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("#[sea_orm(column_name = \"full_name\")]\n    pub name: String")
+            && !content.contains("c_name"),
+        "explicit column names should win. Got:\n{}",
+        content
+    );
+
+    // The template must reference the field
+    let mut request = single_file_request(vec![]);
+    request.parameter = Some("column_name_template=legacy".to_string());
+    let err = protoc_gen_seaorm::generate(request).expect_err("template without {field}");
+    assert!(err.to_string().contains("{field}"), "{}", err);
+}
+
+// =============================================================================
+// List Response Tests
+// =============================================================================
+
+#[test]
+fn test_list_response_converts_to_and_from_pages() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+    let list_request = DescriptorProto {
+        name: Some("ListUsersRequest".to_string()),
+        ..Default::default()
+    };
+    let list_response = DescriptorProto {
+        name: Some("ListUsersResponse".to_string()),
+        field: vec![
+            FieldDescriptorProto {
+                name: Some("users".to_string()),
+                number: Some(1),
+                label: Some(prost_types::field_descriptor_proto::Label::Repeated as i32),
+                r#type: Some(Type::Message as i32),
+                type_name: Some(".test.User".to_string()),
+                ..Default::default()
+            },
+            column_field("total", 2, Type::Int32, None),
+            column_field("next_page_token", 3, Type::String, None),
+        ],
+        ..Default::default()
+    };
+
+    let mut request = single_file_request(vec![user, list_request, list_response]);
+    request.proto_file[0].service.push(ServiceDescriptorProto {
+        name: Some("UserService".to_string()),
+        method: vec![MethodDescriptorProto {
+            name: Some("ListUsers".to_string()),
+            input_type: Some(".test.ListUsersRequest".to_string()),
+            output_type: Some(".test.ListUsersResponse".to_string()),
+            options: Some(ProtoMethodOptions {
+                uninterpreted_option: vec![seaorm_option("rpc", "list_response: true")],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }],
+        options: Some(ServiceOptions {
+            uninterpreted_option: vec![seaorm_option("service", "generate_storage: true")],
+            ..Default::default()
+        }),
+    });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user_service_storage.rs");
+    let compact: String = content
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .replace(",)", ")");
+
+    assert!(
+        compact.contains("implFrom<(Vec<super::user::Model>,i64)>forListUsersResponse{")
+            && compact.contains("users:models.into_iter().map(Into::into).collect(),")
+            && compact.contains("total:i32::try_from(total).unwrap_or(i32::MAX),")
+            && compact.contains("..Default::default()"),
+        "should build the response from a page. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains("implFrom<ListUsersResponse>for(Vec<super::user::Model>,i64){"),
+        "should split the response back into a page. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Inline Enum Tests
+// =============================================================================
+
+#[test]
+fn test_enum_as_string_generates_inline_enum() {
+    let status_field = FieldDescriptorProto {
+        name: Some("status".to_string()),
+        number: Some(2),
+        r#type: Some(Type::Enum as i32),
+        type_name: Some(".test.Status".to_string()),
+        options: Some(prost_types::FieldOptions {
+            uninterpreted_option: vec![seaorm_option("column", "enum_as: \"string\"")],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            status_field,
+        ],
+    );
+    let mut request = single_file_request(vec![user]);
+    request.proto_file[0].enum_type.push(EnumDescriptorProto {
+        name: Some("Status".to_string()),
+        value: ["STATUS_UNSPECIFIED", "STATUS_ACTIVE"]
+            .iter()
+            .enumerate()
+            .map(|(number, name)| EnumValueDescriptorProto {
+                name: Some(name.to_string()),
+                number: Some(number as i32),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains("pub status: Status"),
+        "the column should use the inline enum. Got:\n{}",
+        content
+    );
+    assert!(
+        content
+            .contains("#[sea_orm(rs_type = \"String\", db_type = \"String(StringLen::N(64))\")]")
+            && content.contains("pub enum Status {")
+            && content.contains("#[sea_orm(string_value = \"status_active\")]"),
+        "should generate a string-backed active enum in the entity file. Got:\n{}",
+        content
+    );
+    assert!(
+        !response
+            .file
+            .iter()
+            .any(|f| f.name.as_deref().unwrap_or("").ends_with("status.rs")),
+        "un-annotated enums still get no file of their own"
+    );
+}
+
+// =============================================================================
+// Insert/Update Default Tests
+// =============================================================================
+
+#[test]
+fn test_insert_and_update_defaults() {
+    let timestamp_field = |name: &str, number: i32, column: &str| FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        r#type: Some(Type::Message as i32),
+        type_name: Some(".google.protobuf.Timestamp".to_string()),
+        options: Some(prost_types::FieldOptions {
+            uninterpreted_option: vec![seaorm_option("column", column)],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            timestamp_field("created_at", 2, "insert_default: \"now()\""),
+            timestamp_field(
+                "updated_at",
+                3,
+                "insert_default: \"now()\", update_default: \"now()\"",
+            ),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    let compact: String = content
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .replace(",)", ")");
+
+    assert_eq!(
+        content
+            .matches(r#"default_expr = "Expr::cust(\"now()\")""#)
+            .count(),
+        2,
+        "insert defaults should become default expressions. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains("pubfnupdate_default_exprs()->Vec<(Column,sea_orm::sea_query::SimpleExpr)>{vec![(Column::UpdatedAt,sea_orm::sea_query::Expr::cust(\"now()\"))]}"),
+        "update defaults should be listed for bulk updates. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains("asyncfnbefore_save<C>(mutself,_db:&C,insert:bool)->Result<Self,DbErr>")
+            && compact.contains(
+                "if!insert{self.updated_at=sea_orm::ActiveValue::Set(chrono::Utc::now().into());}"
+            ),
+        "single-model updates should stamp the column. Got:\n{}",
+        content
+    );
+    assert!(!compact.contains("self.created_at="));
+}
+
+// =============================================================================
+// Blocking Storage Tests
+// =============================================================================
+
+#[test]
+fn test_blocking_service_generates_sync_trait() {
+    let mut request = create_service_test_request();
+    request.proto_file[0].service[0].options = Some(ServiceOptions {
+        uninterpreted_option: vec![seaorm_option(
+            "service",
+            "generate_storage: true, blocking: true",
+        )],
+        ..Default::default()
+    });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user_service_storage.rs");
+
+    assert!(
+        !content.contains("async fn") && !content.contains("async_trait"),
+        "blocking traits should have no async methods. Got:\n{}",
+        content
+    );
+    assert!(
+        content
+            .contains("fn get_user(&self, request: GetUserRequest) -> Result<User, StorageError>;"),
+        "methods should return results directly. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("fn runtime(&self) -> &tokio::runtime::Handle;")
+            && content.contains("block_on"),
+        "should require and document the runtime handle. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_enum_as_string_on_integer_enum_casts_through_text() {
+    let status_field = FieldDescriptorProto {
+        name: Some("status".to_string()),
+        number: Some(2),
+        r#type: Some(Type::Enum as i32),
+        type_name: Some(".test.Status".to_string()),
+        options: Some(prost_types::FieldOptions {
+            uninterpreted_option: vec![seaorm_option("column", "enum_as: \"string\"")],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            status_field,
+        ],
+    );
+    let mut request = single_file_request(vec![user]);
+    request.proto_file[0].enum_type.push(EnumDescriptorProto {
+        name: Some("Status".to_string()),
+        value: ["STATUS_UNSPECIFIED", "STATUS_ACTIVE"]
+            .iter()
+            .enumerate()
+            .map(|(number, name)| EnumValueDescriptorProto {
+                name: Some(name.to_string()),
+                number: Some(number as i32),
+                ..Default::default()
+            })
+            .collect(),
+        options: Some(EnumOptions {
+            uninterpreted_option: vec![seaorm_option("enum_opt", "db_type: \"integer\"")],
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains(
+            "#[sea_orm(select_as = \"text\", save_as = \"text\")]\n    pub status: StatusText"
+        ),
+        "the column should cast through text. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub enum StatusText {")
+            && content.contains("#[sea_orm(string_value = \"status_active\")]"),
+        "should use a string-backed variant mapping. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Specta Derive Tests
+// =============================================================================
+
+#[test]
+fn test_derive_specta_on_models_enums_and_domain_types() {
+    const SPECTA: &str = "#[cfg_attr(feature = \"specta\", derive(specta::Type))]";
+
+    let mut request = create_test_request();
+    request.parameter = Some("derive_specta=true".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    assert!(
+        content.contains(&format!("DeriveEntityModel)]\n{}", SPECTA)),
+        "model should derive specta::Type. Got:\n{}",
+        content
+    );
+
+    let mut request = create_enum_test_request();
+    request.parameter = Some("derive_specta=true".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "status.rs");
+    assert!(
+        content.contains(&format!("{}\npub enum Status", SPECTA)),
+        "enum should derive specta::Type. Got:\n{}",
+        content
+    );
+
+    let mut request = create_domain_type_test_request();
+    request.parameter = Some("derive_specta=true".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "create_user.rs");
+    assert!(
+        content.contains(&format!("{}\npub struct CreateUser", SPECTA)),
+        "domain type should derive specta::Type. Got:\n{}",
+        content
+    );
+
+    // Off by default
+    let response =
+        protoc_gen_seaorm::generate(create_test_request()).expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    assert!(!content.contains("specta"));
+}
+
+// =============================================================================
+// HasId Trait Tests
+// =============================================================================
+
+#[test]
+fn test_has_id_trait_returns_primary_key() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\", has_id_trait: true",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, None),
+        ],
+    );
+    let membership = model_message(
+        "Membership",
+        "table_name: \"memberships\", has_id_trait: true",
+        vec![
+            column_field("org_slug", 1, Type::String, Some("primary_key: true")),
+            column_field("user_id", 2, Type::Int64, Some("primary_key: true")),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user, membership]))
+        .expect("generation should succeed");
+
+    let content = generated_content(&response, "user.rs");
+    assert!(
+        content.contains("impl super::support::HasId for Model {")
+            && content.contains("type Id = i64;")
+            && content.contains("fn id(&self) -> Self::Id {\n        self.id\n    }"),
+        "HasId should return the primary key. Got:\n{}",
+        content
+    );
+
+    let content = generated_content(&response, "membership.rs");
+    assert!(
+        content.contains("type Id = (String, i64);")
+            && content.contains("(self.org_slug.clone(), self.user_id)"),
+        "composite keys should be returned as a tuple. Got:\n{}",
+        content
+    );
+
+    let support = generated_content(&response, "test/support.rs");
+    assert!(
+        support.contains("pub trait HasId {")
+            && support.contains("type Id;")
+            && support.contains("fn id(&self) -> Self::Id;"),
+        "support module should define HasId. Got:\n{}",
+        support
+    );
+
+    // No support module without an opted-in entity
+    let plain = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![plain]))
+        .expect("generation should succeed");
+    assert!(response
+        .file
+        .iter()
+        .all(|f| !f.name.as_deref().unwrap_or("").ends_with("support.rs")));
+}
+
+// =============================================================================
+// Filter Struct Tests
+// =============================================================================
+
+#[test]
+fn test_filter_struct_generates_eq_and_contains_filters() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\", filter_struct: true",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, None),
+            column_field(
+                "name",
+                3,
+                Type::String,
+                Some("filter_ops: [\"eq\", \"contains\"]"),
+            ),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains("pub struct UserFilter {")
+            && content.contains("pub id: Option<i64>,")
+            && content.contains("pub email: Option<String>,")
+            && content.contains("pub name: Option<String>,")
+            && content.contains("pub name_contains: Option<String>,"),
+        "filter struct should have eq and contains fields. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub fn apply(self, mut query: Select<Entity>) -> Select<Entity> {"),
+        "filter struct should have an apply method. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("query = query.filter(Column::Email.eq(value));")
+            && content.contains("query = query.filter(Column::Name.contains(value));"),
+        "apply should add eq and contains filters. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_filter_ops_contains_requires_string_column() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\", filter_struct: true",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("age", 2, Type::Int32, Some("filter_ops: [\"contains\"]")),
+        ],
+    );
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect_err("contains on an integer column should fail");
+    assert!(err.to_string().contains("requires a string column"));
+}
+
+// =============================================================================
+// Default Message Handling Tests
+// =============================================================================
+
+/// A `User` with an unannotated optional `Profile` message field, plus the `Profile` entity
+fn unannotated_message_request(parameter: &str) -> CodeGeneratorRequest {
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            FieldDescriptorProto {
+                name: Some("profile".to_string()),
+                number: Some(2),
+                r#type: Some(Type::Message.into()),
+                type_name: Some(".test.Profile".to_string()),
+                proto3_optional: Some(true),
+                ..Default::default()
+            },
+        ],
+    );
+    let profile = model_message(
+        "Profile",
+        "table_name: \"profiles\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("user_id", 2, Type::Int64, None),
+        ],
+    );
+
+    let mut request = single_file_request(vec![user, profile]);
+    request.parameter = Some(parameter.to_string());
+    request
+}
+
+#[test]
+fn test_default_message_handling_applies_to_unannotated_message_fields() {
+    let response = protoc_gen_seaorm::generate(unannotated_message_request(
+        "default_message_handling=relation",
+    ))
+    .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    assert!(
+        content.contains("#[sea_orm(has_one)]\n    pub profile: HasOne<super::profile::Entity>"),
+        "the field should become a has_one relation. Got:\n{}",
+        content
+    );
+    assert!(
+        !content.contains("pub profile: Option<Profile>"),
+        "the field should not also be a column. Got:\n{}",
+        content
+    );
+
+    let response =
+        protoc_gen_seaorm::generate(unannotated_message_request("default_message_handling=json"))
+            .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    assert!(
+        content.contains("pub profile: Option<Profile>"),
+        "json should keep the field as a column. Got:\n{}",
+        content
+    );
+
+    let err = protoc_gen_seaorm::generate(unannotated_message_request(
+        "default_message_handling=error",
+    ))
+    .expect_err("error should reject the field");
+    assert!(err
+        .to_string()
+        .contains("User.profile is a message field without embed or a relation"));
+}
+
+// =============================================================================
+// Arbitrary Tests
+// =============================================================================
+
+#[test]
+fn test_derive_arbitrary_generates_validating_domain_values() {
+    const ARBITRARY: &str = "#[cfg_attr(feature = \"arbitrary\", derive(arbitrary::Arbitrary))]";
+
+    let mut request = create_domain_type_test_request();
+    request.parameter = Some("derive_arbitrary=true".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "create_user.rs");
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+
+    assert!(
+        compact.contains(
+            "#[cfg(feature=\"arbitrary\")]impl<'a>arbitrary::Arbitrary<'a>forCreateUser{"
+        ),
+        "domain type should implement Arbitrary. Got:\n{}",
+        content
+    );
+    // Emails are built around a non-empty alphanumeric local part
+    assert!(
+        compact.contains(
+            "email:format!(\"{}@example.com\",arbitrary_alphanumeric(u,1usize,17usize)?)"
+        ),
+        "email should be generated as a valid address. Got:\n{}",
+        content
+    );
+    // Lengths stay within `length: { min: 1, max: 100 }`
+    assert!(
+        compact.contains("name:arbitrary_alphanumeric(u,1usize,100usize)?"),
+        "name should respect its length bounds. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains("fnarbitrary_alphanumeric(u:&mutarbitrary::Unstructured<'_>,min:usize,max:usize,)->arbitrary::Result<String>"),
+        "the string helper should be emitted. Got:\n{}",
+        content
+    );
+
+    let mut request = create_test_request();
+    request.parameter = Some("derive_arbitrary=true".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    assert!(
+        content.contains(&format!("DeriveEntityModel)]\n{}", ARBITRARY)),
+        "model should derive Arbitrary. Got:\n{}",
+        content
+    );
+
+    let mut request = create_enum_test_request();
+    request.parameter = Some("derive_arbitrary=true".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "status.rs");
+    assert!(
+        content.contains(&format!("{}\npub enum Status", ARBITRARY)),
+        "enum should derive Arbitrary. Got:\n{}",
+        content
+    );
+
+    // Off by default
+    let response = protoc_gen_seaorm::generate(create_domain_type_test_request())
+        .expect("generation should succeed");
+    let content = generated_content(&response, "create_user.rs");
+    assert!(!content.contains("arbitrary"));
+}
+
+// =============================================================================
+// Migration Tests
+// =============================================================================
+
+#[test]
+fn test_emit_migrations_creates_indexes_and_foreign_keys() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, Some("indexed: true")),
+        ],
+    );
+    let post = model_message(
+        "Post",
+        r#"table_name: "posts", indexes: ["idx_posts_author_title:author_id,title"], relations: [
+            {name: "author", type: RELATION_TYPE_BELONGS_TO, related: "User", foreign_key: "author_id"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("author_id", 2, Type::Int64, None),
+            column_field("title", 3, Type::String, None),
+        ],
+    );
+
+    let mut request = single_file_request(vec![user, post]);
+    request.parameter = Some("emit_migrations=true".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+
+    let migration = generated_content(&response, "test/m_create_posts.rs");
+    let compact: String = migration.chars().filter(|c| !c.is_whitespace()).collect();
+    assert!(
+        compact.contains("implMigrationTraitforMigration{")
+            && compact.contains("manager.create_table(Table::create().table(super::post::Entity)"),
+        "migration should create the table. Got:\n{}",
+        migration
+    );
+    assert!(
+        compact.contains(
+            ".foreign_key(ForeignKey::create().name(\"fk_posts_author\").from(super::post::Entity,super::post::Column::AuthorId).to(super::user::Entity,super::user::Column::Id),)"
+        ),
+        "migration should add the belongs_to foreign key. Got:\n{}",
+        migration
+    );
+    assert!(
+        compact.contains(
+            "manager.create_index(Index::create().name(\"idx_posts_author_title\").table(super::post::Entity).col(super::post::Column::AuthorId).col(super::post::Column::Title).to_owned(),)"
+        ),
+        "migration should create the declared index. Got:\n{}",
+        migration
+    );
+    assert!(
+        compact.contains(".drop_table(Table::drop().table(super::post::Entity).to_owned())"),
+        "down should drop the table. Got:\n{}",
+        migration
+    );
+
+    let migration = generated_content(&response, "test/m_create_users.rs");
+    assert!(
+        migration.contains(".name(\"idx_users_email\")"),
+        "indexed columns should get an index. Got:\n{}",
+        migration
+    );
+}
+
+#[test]
+fn test_emit_migrations_migrator_orders_referenced_tables_first() {
+    let comment = model_message(
+        "Comment",
+        r#"table_name: "comments", relations: [
+            {name: "post", type: RELATION_TYPE_BELONGS_TO, related: "Post", foreign_key: "post_id"},
+            {name: "author", type: RELATION_TYPE_BELONGS_TO, related: "User", foreign_key: "author_id"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("post_id", 2, Type::Int64, None),
+            column_field("author_id", 3, Type::Int64, None),
+        ],
+    );
+    let post = model_message(
+        "Post",
+        r#"table_name: "posts", relations: [
+            {name: "author", type: RELATION_TYPE_BELONGS_TO, related: "User", foreign_key: "author_id"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("author_id", 2, Type::Int64, None),
+        ],
+    );
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+
+    let mut request = single_file_request(vec![comment, post, user]);
+    request.parameter = Some("emit_migrations=true".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+
+    let migrator = generated_content(&response, "test/migrator.rs");
+    let compact: String = migrator.chars().filter(|c| !c.is_whitespace()).collect();
+    assert!(
+        compact.contains("implMigratorTraitforMigrator{")
+            && compact.contains(
+                "vec![Box::new(super::m_create_users::Migration),Box::new(super::m_create_posts::Migration),Box::new(super::m_create_comments::Migration),]"
+            ),
+        "migrator should list referenced tables before referencing ones. Got:\n{}",
+        migrator
+    );
+}
+
+#[test]
+fn test_emit_migrations_rejects_foreign_key_cycles() {
+    let a = model_message(
+        "Alpha",
+        r#"table_name: "alphas", relations: [
+            {name: "beta", type: RELATION_TYPE_BELONGS_TO, related: "Beta", foreign_key: "beta_id"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("beta_id", 2, Type::Int64, None),
+        ],
+    );
+    let b = model_message(
+        "Beta",
+        r#"table_name: "betas", relations: [
+            {name: "alpha", type: RELATION_TYPE_BELONGS_TO, related: "Alpha", foreign_key: "alpha_id"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("alpha_id", 2, Type::Int64, None),
+        ],
+    );
+
+    let mut request = single_file_request(vec![a, b]);
+    request.parameter = Some("emit_migrations=true".to_string());
+    let errors = protoc_gen_seaorm::validate(&request).expect_err("validate should see the cycle");
+    assert!(
+        errors
+            .iter()
+            .any(|e| e.to_string().contains("Alpha, Beta form a cycle")),
+        "unexpected errors: {:?}",
+        errors
+    );
+
+    let err = protoc_gen_seaorm::generate(request).expect_err("cyclic foreign keys should fail");
+    assert!(
+        err.to_string().contains("Alpha, Beta form a cycle"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+// =============================================================================
+// Single File Tests
+// =============================================================================
+
+#[test]
+fn test_single_file_nests_all_modules() {
+    let mut request = create_test_request();
+    for other in [
+        create_enum_test_request(),
+        create_domain_type_test_request(),
+    ] {
+        request.file_to_generate.extend(other.file_to_generate);
+        request.proto_file.extend(other.proto_file);
+    }
+    request.parameter = Some("single_file=generated.rs".to_string());
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let names: Vec<&str> = response
+        .file
+        .iter()
+        .filter_map(|f| f.name.as_deref())
+        .collect();
+    assert_eq!(names, vec!["generated.rs"]);
+
+    let content = generated_content(&response, "generated.rs");
+    assert!(
+        content.contains("pub mod test {")
+            && content.contains("pub mod user {")
+            && content.contains("pub mod create_user {")
+            && content.contains("pub mod status {"),
+        "every module should be nested under its package. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub mod prelude;"),
+        "the package should declare its prelude. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub struct Model {")
+            && content.contains("pub enum Status {")
+            && content.contains("pub struct CreateUser {"),
+        "module contents should be carried over. Got:\n{}",
+        content
+    );
+    assert!(content.starts_with("//! Generated by protoc-gen-seaorm from protobuf definitions."));
+}
+
+// =============================================================================
+// Temporal Tests
+// =============================================================================
+
+/// A Timestamp field with the given `(seaorm.column)` options
+fn timestamp_field(name: &str, number: i32, column: &str) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        type_name: Some(".google.protobuf.Timestamp".to_string()),
+        ..column_field(name, number, Type::Message, Some(column))
+    }
+}
+
+#[test]
+fn test_temporal_selects_chrono_and_column_types() {
+    let event = model_message(
+        "Event",
+        "table_name: \"events\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            timestamp_field("naive_at", 2, "temporal: \"naive\""),
+            timestamp_field("utc_at", 3, "temporal: \"utc\""),
+            timestamp_field("local_at", 4, "temporal: \"local\""),
+            timestamp_field("on_day", 5, "temporal: \"date\""),
+            timestamp_field("at_time", 6, "temporal: \"time\""),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![event]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "event.rs");
+
+    for (name, rust_type, column_type) in [
+        ("naive_at", "DateTime", "DateTime"),
+        ("utc_at", "DateTimeUtc", "TimestampWithTimeZone"),
+        ("local_at", "DateTimeLocal", "TimestampWithTimeZone"),
+        ("on_day", "Date", "Date"),
+        ("at_time", "Time", "Time"),
+    ] {
+        let expected = format!(
+            "#[sea_orm(column_type = \"{}\")]\n    pub {}: {},",
+            column_type, name, rust_type
+        );
+        assert!(
+            content.contains(&expected),
+            "{} should be {} stored as {}. Got:\n{}",
+            name,
+            rust_type,
+            column_type,
+            content
+        );
+    }
+}
+
+#[test]
+fn test_temporal_requires_timestamp_field() {
+    let event = model_message(
+        "Event",
+        "table_name: \"events\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("at", 2, Type::String, Some("temporal: \"naive\"")),
+        ],
+    );
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![event]))
+        .expect_err("temporal on a string field should fail");
+    assert!(err
+        .to_string()
+        .contains("only google.protobuf.Timestamp fields can"));
+}
+
+// ============================================================================
+// Newtype column tests
+// ============================================================================
+
+#[test]
+fn test_newtype_column_generates_value_adapters() {
+    let order = model_message(
+        "Order",
+        "table_name: \"orders\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field(
+                "user_id",
+                2,
+                Type::Int64,
+                Some("rust_type: \"UserId\", newtype_inner: \"i64\""),
+            ),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![order]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "order.rs");
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+
+    assert!(
+        content.contains("pub user_id: UserId,"),
+        "Field should use the newtype. Got:\n{}",
+        content
+    );
+    for expected in [
+        "implFrom<UserId>forsea_orm::Value",
+        "implsea_orm::TryGetableforUserId",
+        "<i64assea_orm::TryGetable>::try_get_by(res,index).map(UserId)",
+        "implsea_orm::sea_query::ValueTypeforUserId",
+        "implsea_orm::sea_query::NullableforUserId",
+        "implsea_orm::IntoActiveValue<UserId>forUserId",
+    ] {
+        assert!(
+            compact.contains(expected),
+            "Missing {}. Got:\n{}",
+            expected,
+            content
+        );
+    }
+}
+
+#[test]
+fn test_newtype_inner_must_match_proto_type() {
+    let order = model_message(
+        "Order",
+        "table_name: \"orders\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field(
+                "user_id",
+                2,
+                Type::Int64,
+                Some("rust_type: \"UserId\", newtype_inner: \"i32\""),
+            ),
+        ],
+    );
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![order]))
+        .expect_err("a mismatched newtype_inner should fail");
+    assert!(err.to_string().contains("but the field maps to i64"));
+}
+
+// ============================================================================
+// Deprecated field tests
+// ============================================================================
+
+#[test]
+fn test_deprecated_note_comes_from_trailing_comment() {
+    let mut name = column_field("name", 2, Type::String, None);
+    name.options = Some(prost_types::FieldOptions {
+        deprecated: Some(true),
+        ..Default::default()
+    });
+    let mut nickname = column_field("nickname", 3, Type::String, None);
+    nickname.options = Some(prost_types::FieldOptions {
+        deprecated: Some(true),
+        ..Default::default()
+    });
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            name,
+            nickname,
+            column_field("full_name", 4, Type::String, None),
+        ],
+    );
+
+    let mut request = single_file_request(vec![user]);
+    request.proto_file[0].source_code_info = Some(prost_types::SourceCodeInfo {
+        location: vec![prost_types::source_code_info::Location {
+            path: vec![4, 0, 2, 1],
+            trailing_comments: Some(" deprecated: use full_name\n".to_string()),
+            ..Default::default()
+        }],
+    });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains("#[deprecated(note = \"use full_name\")]\n    pub name: String,"),
+        "Deprecated field should carry the comment note. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("#[deprecated]\n    pub nickname: String,"),
+        "Deprecated field without a note should get a bare attribute. Got:\n{}",
+        content
+    );
+    assert!(!content.contains("#[deprecated]\n    pub full_name"));
+}
+
+// ============================================================================
+// Domain builder tests
+// ============================================================================
+
+#[test]
+fn test_domain_builder_validates_on_build() {
+    let mut request = create_domain_type_test_request();
+    request.proto_file[0].message_type[0].options = Some(MessageOptions {
+        uninterpreted_option: vec![seaorm_option(
+            "input_message",
+            "domain_type: \"CreateUser\", generate_builder: true",
+        )],
+        ..Default::default()
+    });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "create_user.rs");
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+
+    for expected in [
+        "pubstructCreateUserBuilder{email:Option<String>,name:Option<String>,}",
+        "pubfnbuilder()->CreateUserBuilder",
+        "pubfnemail(mutself,value:implInto<String>)->Self",
+        "pubfnbuild(self)->Result<CreateUser,DomainError>",
+        "email:self.email.ok_or_else(||{DomainError::Conversion(format!(\"{}:isrequired\",\"email\"))})?",
+        "domain.validate()?;Ok(domain)",
+        "pubenumDomainError",
+    ] {
+        assert!(
+            compact.contains(expected),
+            "Missing {}. Got:\n{}",
+            expected,
+            content
+        );
+    }
+    assert!(content.contains("use garde::Validate;"));
+    assert!(!content.contains("impl TryFrom<CreateUserRequest>"));
+}
+
+// ============================================================================
+// Well-known type mapping tests
+// ============================================================================
+
+#[test]
+fn test_timestamp_fields_map_to_date_time_utc() {
+    let created_at = FieldDescriptorProto {
+        type_name: Some(".google.protobuf.Timestamp".to_string()),
+        ..column_field("created_at", 2, Type::Message, None)
+    };
+    let deleted_at = FieldDescriptorProto {
+        type_name: Some(".google.protobuf.Timestamp".to_string()),
+        proto3_optional: Some(true),
+        ..column_field("deleted_at", 3, Type::Message, None)
+    };
+    let event = model_message(
+        "Event",
+        "table_name: \"events\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            created_at,
+            deleted_at,
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![event]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "event.rs");
+
+    assert!(
+        content.contains(
+            "#[sea_orm(column_type = \"TimestampWithTimeZone\")]\n    pub created_at: DateTimeUtc,"
+        ),
+        "Timestamp should map to DateTimeUtc. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains(
+            "#[sea_orm(column_type = \"TimestampWithTimeZone\")]\n    #[sea_orm(nullable)]\n    pub deleted_at: Option<DateTimeUtc>,"
+        ),
+        "Optional Timestamp should be nullable. Got:\n{}",
+        content
+    );
+}
+
+// ============================================================================
+// Generated test module tests
+// ============================================================================
+
+#[test]
+fn test_emit_tests_false_suppresses_generated_tests() {
+    let user = || {
+        model_message(
+            "User",
+            "table_name: \"users\", generate_tests: true",
+            vec![column_field(
+                "id",
+                1,
+                Type::Int64,
+                Some("primary_key: true"),
+            )],
+        )
+    };
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user()]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    assert!(
+        content.contains("#[cfg(test)]\nmod tests {"),
+        "generate_tests should emit a test module. Got:\n{}",
+        content
+    );
+    assert!(content.contains("assert_eq!(Entity.table_name(), \"users\");"));
+    assert!(content.contains("assert_eq!(PrimaryKey::iter().count(), 1usize);"));
+
+    let mut request = single_file_request(vec![user()]);
+    request.parameter = Some("emit_tests=false".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    for file in &response.file {
+        let content = file.content.as_deref().unwrap_or("");
+        assert!(
+            !content.contains("#[cfg(test)]"),
+            "{} should have no test module. Got:\n{}",
+            file.name.as_deref().unwrap_or(""),
+            content
+        );
+    }
+}
+
+// ============================================================================
+// Relation naming tests
+// ============================================================================
+
+/// A post with an `author` column, plus the given relations
+fn post_with_author_column(model: &str) -> DescriptorProto {
+    model_message(
+        "Post",
+        model,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("user_id", 2, Type::Int64, None),
+            column_field("author", 3, Type::String, None),
+        ],
+    )
+}
+
+#[test]
+fn test_relation_colliding_with_column_gets_suffixed_variant() {
+    let post = post_with_author_column(
+        r#"table_name: "posts", relations: [
+            {name: "author", type: RELATION_TYPE_BELONGS_TO, related: "user", foreign_key: "user_id"}
+        ]"#,
+    );
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![post]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "post.rs");
+
+    assert!(
+        content.contains("    pub author: String,"),
+        "the column should keep its name. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains(
+            "#[sea_orm(belongs_to, from = \"user_id\", to = \"id\")]\n    pub author_rel: HasOne<super::user::Entity>,"
+        ),
+        "the relation should get the non-colliding AuthorRel variant. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_relation_enum_prefix_names_variants() {
+    let post = post_with_author_column(
+        r#"table_name: "posts", relation_enum_prefix: "Rel", relations: [
+            {name: "author", type: RELATION_TYPE_BELONGS_TO, related: "user", foreign_key: "user_id"},
+            {name: "comments", type: RELATION_TYPE_HAS_MANY, related: "comment"}
+        ]"#,
+    );
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![post]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "post.rs");
+
+    assert!(
+        content.contains(
+            "#[sea_orm(belongs_to, from = \"user_id\", to = \"id\")]\n    pub rel_author: HasOne<super::user::Entity>,"
+        ),
+        "a relation clashing with a column should take the prefix. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains(
+            "#[sea_orm(has_many, relation_enum = \"RelComments\")]\n    pub comments: HasMany<super::comment::Entity>,"
+        ),
+        "the prefix should name the variant. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_manual_entity_names_relations_like_the_derive() {
+    let post = post_with_author_column(
+        r#"table_name: "posts", relation_enum_prefix: "Rel", related_entity: true, relations: [
+            {name: "author", type: RELATION_TYPE_BELONGS_TO, related: "user", foreign_key: "user_id"},
+            {name: "parent", type: RELATION_TYPE_BELONGS_TO, related: "post", foreign_key: "id"},
+            {name: "children", type: RELATION_TYPE_HAS_MANY, related: "post", foreign_key: "id"}
+        ]"#,
+    );
+    let mut request = single_file_request(vec![post]);
+    request.parameter = Some("manual_entity=true".to_string());
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "post.rs");
+    let compact: String = content
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .replace(",)", ")");
+
+    assert!(
+        compact.contains("pubenumRelation{RelAuthor,RelParent,RelChildren,}"),
+        "variants should carry the prefix. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains("Self::RelChildren=>Relation::RelParent.def().rev(),"),
+        "the self-referential reverse should use the prefixed variant. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains("def=\"Relation::RelParent.def()\""),
+        "RelatedEntity should point at an existing variant. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_relation_colliding_with_relation_is_rejected() {
+    let post = post_with_author_column(
+        r#"table_name: "posts", relations: [
+            {name: "author", type: RELATION_TYPE_BELONGS_TO, related: "user", foreign_key: "user_id"},
+            {name: "author_rel", type: RELATION_TYPE_HAS_MANY, related: "comment"}
+        ]"#,
+    );
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![post]))
+        .expect_err("clashing relations should fail");
+    assert!(err
+        .to_string()
+        .contains("collides with another field or relation"));
+}
+
+// ============================================================================
+// Duration mapping tests
+// ============================================================================
+
+/// Build a `google.protobuf.Duration` field, optionally annotated with `(seaorm.column)`
+fn duration_field(name: &str, number: i32, column: Option<&str>) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        type_name: Some(".google.protobuf.Duration".to_string()),
+        ..column_field(name, number, Type::Message, column)
+    }
+}
+
+#[test]
+fn test_duration_maps_to_nanoseconds_or_interval() {
+    let timeout = FieldDescriptorProto {
+        proto3_optional: Some(true),
+        ..duration_field("timeout", 3, None)
+    };
+    let job = model_message(
+        "Job",
+        "table_name: \"jobs\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            duration_field("elapsed", 2, None),
+            timeout,
+            duration_field("window", 4, Some("duration_as: \"interval\"")),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![job]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "job.rs");
+
+    for expected in [
+        "#[sea_orm(column_type = \"BigInteger\")]\n    pub elapsed: i64,",
+        "#[sea_orm(column_type = \"BigInteger\")]\n    #[sea_orm(nullable)]\n    pub timeout: Option<i64>,",
+        "#[sea_orm(column_type = \"Interval(None, None)\")]\n    pub window: chrono::TimeDelta,",
+    ] {
+        assert!(
+            content.contains(expected),
+            "Missing {}. Got:\n{}",
+            expected,
+            content
+        );
+    }
+}
+
+#[test]
+fn test_duration_as_requires_duration_field() {
+    let job = model_message(
+        "Job",
+        "table_name: \"jobs\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("window", 2, Type::Int64, Some("duration_as: \"interval\"")),
+        ],
+    );
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![job]))
+        .expect_err("duration_as on an int64 field should fail");
+    assert!(err
+        .to_string()
+        .contains("only google.protobuf.Duration fields can"));
+}
+
+// ============================================================================
+// Bytes column tests
+// ============================================================================
+
+#[test]
+fn test_bytes_fields_map_to_binary_columns() {
+    let profile = model_message(
+        "Profile",
+        "table_name: \"profiles\"",
+        vec![
+            column_field("avatar", 1, Type::Bytes, None),
+            column_field("id", 2, Type::Int64, Some("primary_key: true")),
+            column_field("thumbnail", 3, Type::Bytes, Some("column_type: \"Blob\"")),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![profile]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "profile.rs");
+
+    assert!(
+        content.contains(
+            "#[sea_orm(column_type = \"VarBinary(StringLen::None)\")]\n    pub avatar: Vec<u8>,"
+        ),
+        "bytes should be a binary Vec<u8> column. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("#[sea_orm(column_type = \"Blob\")]\n    pub thumbnail: Vec<u8>,"),
+        "an explicit column_type should win. Got:\n{}",
+        content
+    );
+}
+
+// ============================================================================
+// Empty active model tests
+// ============================================================================
+
+#[test]
+fn test_empty_active_model_sets_every_column_not_set() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\", empty_active_model: true",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, None),
+            column_field("nickname", 3, Type::String, Some("nullable: true")),
+        ],
+    );
+
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+
+    assert!(
+        compact.contains(
+            "implActiveModel{///Anactivemodelwitheverycolumn`NotSet`,forbuildingpartialupdates\
+             pubfnempty()->Self{Self{id:sea_orm::ActiveValue::NotSet,\
+             email:sea_orm::ActiveValue::NotSet,nickname:sea_orm::ActiveValue::NotSet,}}}"
+        ),
+        "empty() should leave every column NotSet. Got:\n{}",
+        content
+    );
+}
+
+// ============================================================================
+// Oneof discriminator enum tests
+// ============================================================================
+
+/// A payment with a tagged `method` oneof whose discriminator reuses `PaymentKind`
+fn payment_with_discriminator_enum(kinds: &[&str]) -> CodeGeneratorRequest {
+    let mut card = column_field("card", 2, Type::String, None);
+    card.oneof_index = Some(0);
+    let mut points = column_field("points", 3, Type::Int64, None);
+    points.oneof_index = Some(0);
+    let mut payment = model_message(
+        "Payment",
+        "table_name: \"payments\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            card,
+            points,
+        ],
+    );
+    payment.oneof_decl = vec![OneofDescriptorProto {
+        name: Some("method".to_string()),
+        options: Some(OneofOptions {
+            uninterpreted_option: vec![seaorm_option(
+                "oneof",
+                "strategy: \"tagged\", discriminator_enum: \"PaymentKind\"",
+            )],
+        }),
+    }];
+
+    let mut request = single_file_request(vec![payment]);
+    request.proto_file[0].enum_type = vec![EnumDescriptorProto {
+        name: Some("PaymentKind".to_string()),
+        value: kinds
+            .iter()
+            .enumerate()
+            .map(|(number, name)| EnumValueDescriptorProto {
+                name: Some(name.to_string()),
+                number: Some(number as i32),
+                ..Default::default()
+            })
+            .collect(),
+        options: Some(EnumOptions {
+            uninterpreted_option: vec![seaorm_option("enum_opt", "db_type: \"string\"")],
+            ..Default::default()
+        }),
+        ..Default::default()
+    }];
+    request
+}
+
+#[test]
+fn test_tagged_oneof_reuses_discriminator_enum() {
+    let request = payment_with_discriminator_enum(&["CARD", "POINTS"]);
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "payment.rs");
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+
+    assert!(
+        content.contains("pub method_type: Option<PaymentKind>,"),
+        "the discriminator column should use the enum. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub fn from_db(disc: PaymentKind, value: &str) -> Result<Self, DbErr> {")
+            && compact.contains("PaymentKind::Points=>{value.parse().map(Self::Points)"),
+        "variants should map from the enum. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("pub fn to_db(&self) -> (PaymentKind, String) {")
+            && content.contains("Self::Card(value) => (PaymentKind::Card, value.to_string()),"),
+        "variants should map to the enum. Got:\n{}",
+        content
+    );
+    assert!(
+        !content.contains("other =>"),
+        "an enum covering exactly the fields needs no fallback arm. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_discriminator_enum_must_name_every_field() {
+    let request = payment_with_discriminator_enum(&["CARD", "CASH"]);
+    let err = protoc_gen_seaorm::generate(request)
+        .expect_err("a missing discriminator variant should fail");
+    assert!(
+        err.to_string().contains("without variants Points"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+// ============================================================================
+// RelatedEntity tests
+// ============================================================================
+
+#[test]
+fn test_related_entity_enum_lists_relations() {
+    let post = post_with_author_column(
+        r#"table_name: "posts", related_entity: true, relations: [
+            {name: "author", type: RELATION_TYPE_BELONGS_TO, related: "user", foreign_key: "user_id"},
+            {name: "comments", type: RELATION_TYPE_HAS_MANY, related: "comment"}
+        ]"#,
+    );
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![post]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "post.rs");
+
+    assert!(
+        content.contains("#[derive(Copy, Clone, Debug, EnumIter, DeriveRelatedEntity)]\npub enum RelatedEntity {"),
+        "the RelatedEntity enum should be generated. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("    #[sea_orm(entity = \"super::user::Entity\")]\n    AuthorRel,"),
+        "variants should follow the Relation variants. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("    #[sea_orm(entity = \"super::comment::Entity\")]\n    Comments,"),
+        "every relation should get a variant. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_related_entity_self_reference_names_its_relation() {
+    let category = model_message(
+        "Category",
+        r#"table_name: "categories", related_entity: true, relations: [
+            {name: "parent", type: RELATION_TYPE_BELONGS_TO, related: "category", foreign_key: "parent_id"},
+            {name: "children", type: RELATION_TYPE_HAS_MANY, related: "category"}
+        ]"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("parent_id", 2, Type::Int64, None),
+        ],
+    );
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![category]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "category.rs");
+
+    assert!(
+        content.contains(
+            "#[sea_orm(entity = \"Entity\", def = \"Relation::Parent.def()\")]\n    Parent,"
+        ) && content.contains(
+            "#[sea_orm(entity = \"Entity\", def = \"Relation::Children.def()\")]\n    Children,"
+        ),
+        "self-referential variants should name their relation. Got:\n{}",
+        content
+    );
+}
+
+// ============================================================================
+// Auto timestamp tests
+// ============================================================================
+
+#[test]
+fn test_auto_timestamps_stamp_insert_and_update() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            timestamp_field("created_at", 2, "auto_create_time: true"),
+            timestamp_field("updated_at", 3, "auto_update_time: true"),
+            timestamp_field("seen_at", 4, "temporal: \"naive\", auto_update_time: true"),
+        ],
+    );
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+
+    assert!(
+        compact.contains(
+            "ifinsert{\
+             self.created_at=sea_orm::ActiveValue::Set(chrono::Utc::now().into());\
+             self.updated_at=sea_orm::ActiveValue::Set(chrono::Utc::now().into());\
+             self.seen_at=sea_orm::ActiveValue::Set(chrono::Utc::now().naive_utc());\
+             }else{\
+             self.updated_at=sea_orm::ActiveValue::Set(chrono::Utc::now().into());\
+             self.seen_at=sea_orm::ActiveValue::Set(chrono::Utc::now().naive_utc());\
+             }"
+        ),
+        "created_at should be stamped on insert, updated_at on every save. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_timestamps_option_stamps_conventional_columns() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\", timestamps: true",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            timestamp_field("created_at", 2, "nullable: true"),
+            timestamp_field("updated_at", 3, "nullable: true"),
+        ],
+    );
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+
+    assert!(
+        compact.contains(
+            "ifinsert{self.created_at=sea_orm::ActiveValue::Set(Some(chrono::Utc::now().into()));"
+        ) && compact.contains(
+            "}else{self.updated_at=sea_orm::ActiveValue::Set(Some(chrono::Utc::now().into()));}"
+        ),
+        "timestamps should stamp created_at and updated_at. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_auto_time_requires_timestamp_column() {
+    let user = model_message(
+        "User",
+        "table_name: \"users\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field(
+                "created_at",
+                2,
+                Type::String,
+                Some("auto_create_time: true"),
+            ),
+        ],
+    );
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect_err("a non-timestamp auto_create_time column should fail");
+    assert!(
+        err.to_string()
+            .contains("User.created_at is stamped with the current time"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+// ============================================================================
+// Identifier length tests
+// ============================================================================
+
+#[test]
+fn test_max_identifier_length_shortens_long_names() {
+    let message = |name: &str| {
+        model_message(
+            name,
+            "",
+            vec![
+                column_field("id", 1, Type::Int64, Some("primary_key: true")),
+                column_field(
+                    "externally_provided_reconciliation_reference",
+                    2,
+                    Type::String,
+                    None,
+                ),
+            ],
+        )
+    };
+    let mut request = single_file_request(vec![
+        message("CustomerSubscriptionInvoiceLineItem"),
+        message("CustomerSubscriptionInvoiceLineTotal"),
+    ]);
+    request.parameter = Some("max_identifier_length=30".to_string());
+
+    let response = protoc_gen_seaorm::generate(request.clone()).expect("generation should succeed");
+    let table_name = |content: &str| {
+        let start = content.find("table_name = \"").expect("table name") + 14;
+        content[start..start + content[start..].find('"').unwrap()].to_string()
+    };
+    let items = generated_content(&response, "customer_subscription_invoice_line_item.rs");
+    let totals = generated_content(&response, "customer_subscription_invoice_line_total.rs");
+
+    let items_table = table_name(items);
+    assert_eq!(items_table.len(), 30, "table name should fit the limit");
+    assert!(items_table.starts_with("customer_subscription_"));
+    assert_ne!(
+        items_table,
+        table_name(totals),
+        "shortened table names should stay unique"
+    );
+    assert!(
+        items.contains(&format!(
+            "//! - `customer_subscription_invoice_line_item` is named `{}`",
+            items_table
+        )),
+        "the mapping should be recorded. Got:\n{}",
+        items
+    );
+    assert!(
+        items.contains("#[sea_orm(column_name = \"externally_provided_r_")
+            && items.contains("pub externally_provided_reconciliation_reference: String,"),
+        "long columns should be renamed but keep their field. Got:\n{}",
+        items
+    );
+
+    let again = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    assert_eq!(
+        items,
+        generated_content(&again, "customer_subscription_invoice_line_item.rs"),
+        "shortening should be deterministic"
+    );
+}
+
+// ============================================================================
+// Soft delete tests
+// ============================================================================
+
+#[test]
+fn test_soft_delete_adds_column_and_helpers() {
+    let post = model_message(
+        "Post",
+        r#"table_name: "posts", soft_delete: "deleted_at""#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("title", 2, Type::String, None),
+        ],
+    );
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![post]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "post.rs");
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+
+    assert!(
+        content.contains(
+            "#[sea_orm(column_type = \"TimestampWithTimeZone\")]\n    pub deleted_at: Option<DateTimeUtc>,"
+        ),
+        "the soft-delete column should be a nullable timestamp. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains(
+            "pubfnfind_active()->Select<Entity>{Self::find().filter(Column::DeletedAt.is_null())}"
+        ),
+        "find_active should skip soft-deleted rows. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains("pubasyncfnsoft_delete<C>(db:&C,id:i64)->Result<(),DbErr>")
+            && compact
+                .contains("letdeleted_at:Option<DateTimeUtc>=Some(chrono::Utc::now().into());")
+            && compact.contains("Column::DeletedAt,sea_orm::sea_query::Expr::value(deleted_at)")
+            && compact.contains(".filter(Column::Id.eq(id))"),
+        "soft_delete should set the timestamp by id. Got:\n{}",
+        content
+    );
+    assert!(!compact.contains("delete_by_id"));
+}
+
+#[test]
+fn test_soft_delete_stamps_the_column_type() {
+    let post = model_message(
+        "Post",
+        r#"table_name: "posts", soft_delete: "deleted_at""#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            timestamp_field("deleted_at", 2, "nullable: true, temporal: \"naive\""),
+        ],
+    );
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![post]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "post.rs");
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+
+    assert!(
+        compact.contains("letdeleted_at:Option<DateTime>=Some(chrono::Utc::now().naive_utc());"),
+        "soft_delete should write a naive timestamp. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_soft_delete_rejects_non_nullable_column() {
+    let post = model_message(
+        "Post",
+        r#"table_name: "posts", soft_delete: "deleted_at""#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            timestamp_field("deleted_at", 2, "indexed: true"),
+        ],
+    );
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![post]))
+        .expect_err("a required soft-delete column should fail");
+    assert!(
+        err.to_string()
+            .contains("soft_delete column Post.deleted_at must be a nullable timestamp"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+// ============================================================================
+// Identity hash tests
+// ============================================================================
+
+#[test]
+fn test_identity_hash_compares_primary_key_only() {
+    let membership = model_message(
+        "Membership",
+        r#"table_name: "memberships", identity_hash: true"#,
+        vec![
+            column_field("team_id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("user_id", 2, Type::Int64, Some("primary_key: true")),
+            column_field("role", 3, Type::String, None),
+        ],
+    );
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![membership]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "membership.rs");
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+
+    assert!(
+        content.contains("#[derive(Clone, Debug, DeriveEntityModel)]"),
+        "equality should no longer be derived over every field. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains(
+            "implPartialEqforModel{fneq(&self,other:&Self)->bool{self.team_id==other.team_id&&self.user_id==other.user_id}}"
+        ) && compact.contains("implEqforModel{}"),
+        "models with the same key should be equal whatever their role. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains(
+            "implstd::hash::HashforModel{fnhash<H:std::hash::Hasher>(&self,state:&mutH){std::hash::Hash::hash(&self.team_id,state);std::hash::Hash::hash(&self.user_id,state);}}"
+        ),
+        "models with the same key should hash equally. Got:\n{}",
+        content
+    );
+    assert!(!compact.contains("self.role"));
+}
+
+// ============================================================================
+// Map field tests
+// ============================================================================
+
+#[test]
+fn test_map_field_is_single_json_column() {
+    let mut message = model_message(
+        "Document",
+        r#"table_name: "documents""#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            FieldDescriptorProto {
+                label: Some(prost_types::field_descriptor_proto::Label::Repeated.into()),
+                type_name: Some(".test.Document.CountsEntry".to_string()),
+                ..column_field("counts", 2, Type::Message, None)
+            },
+        ],
+    );
+    message.nested_type.push(DescriptorProto {
+        name: Some("CountsEntry".to_string()),
+        field: vec![
+            FieldDescriptorProto {
+                name: Some("key".to_string()),
+                number: Some(1),
+                r#type: Some(Type::String as i32),
+                ..Default::default()
+            },
+            FieldDescriptorProto {
+                name: Some("value".to_string()),
+                number: Some(2),
+                r#type: Some(Type::Int32 as i32),
+                ..Default::default()
+            },
+        ],
+        options: Some(MessageOptions {
+            map_entry: Some(true),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![message]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "document.rs");
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+
+    assert!(
+        content.contains("#[sea_orm(column_type = \"JsonBinary\")]\n    pub counts: Json,"),
+        "the map should be a single JSON column. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains(
+            "pubfncounts_map(&self,)->Result<std::collections::HashMap<String,i32>,serde_json::Error>{serde_json::from_value(self.counts.clone())}"
+        ),
+        "the column should deserialize to a HashMap. Got:\n{}",
+        content
+    );
+    assert!(!content.contains("CountsEntry"));
+    assert!(
+        response
+            .file
+            .iter()
+            .all(|f| !f.name.as_deref().unwrap_or("").contains("counts_entry")),
+        "the map entry message should not become an entity"
+    );
+}
+
+// ============================================================================
+// Serde tests
+// ============================================================================
+
+#[test]
+fn test_serde_skip_marks_sensitive_columns() {
+    let user = || {
+        model_message(
+            "User",
+            r#"table_name: "users""#,
+            vec![
+                column_field("id", 1, Type::Int64, Some("primary_key: true")),
+                column_field("email", 2, Type::String, Some("unique: true")),
+                column_field("password_hash", 3, Type::String, Some("serde_skip: true")),
+            ],
+        )
+    };
+    let mut request = single_file_request(vec![user()]);
+    request.parameter = Some("derive_serde=true".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content.contains("#[derive(serde::Serialize, serde::Deserialize)]"),
+        "derive_serde should derive serde on the model. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("    #[serde(skip_serializing)]\n    pub password_hash: String,"),
+        "the marked field should be skipped. Got:\n{}",
+        content
+    );
+    assert_eq!(content.matches("skip_serializing").count(), 1);
+
+    // Without serde on the model there's nothing to skip
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user()]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    assert!(!content.contains("serde"), "Got:\n{}", content);
+}
+
+// ============================================================================
+// Proto codec tests
+// ============================================================================
+
+#[test]
+fn test_proto_codec_round_trips_through_message() {
+    let user = model_message(
+        "User",
+        r#"table_name: "users", proto_codec: true"#,
+        vec![column_field(
+            "id",
+            1,
+            Type::Int64,
+            Some("primary_key: true"),
+        )],
+    );
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![user]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+
+    assert!(
+        compact.contains(
+            "pubfnto_proto_bytes(&self)->Vec<u8>{prost::Message::encode_to_vec(&User::from(self.clone()))}"
+        ),
+        "the model should encode through its message. Got:\n{}",
+        content
+    );
+    assert!(
+        compact.contains(
+            "pubfnfrom_proto_bytes(bytes:&[u8])->Result<Model,prost::DecodeError>{<Userasprost::Message>::decode(bytes).map(Model::from)}"
+        ),
+        "the model should decode through its message. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("impl TryFrom<&[u8]> for Model {"),
+        "decoding should be available as TryFrom. Got:\n{}",
+        content
+    );
+}
+
+// ============================================================================
+// Wrapper type tests
+// ============================================================================
+
+#[test]
+fn test_wrapper_types_are_nullable_scalar_columns() {
+    let wrapper = |name: &str, number: i32, wrapper: &str| FieldDescriptorProto {
+        type_name: Some(format!(".google.protobuf.{}", wrapper)),
+        ..column_field(name, number, Type::Message, None)
+    };
+    let profile = model_message(
+        "Profile",
+        r#"table_name: "profiles""#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            wrapper("nickname", 2, "StringValue"),
+            wrapper("score", 3, "Int64Value"),
+            wrapper("verified", 4, "BoolValue"),
+            wrapper("avatar", 5, "BytesValue"),
+        ],
+    );
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![profile]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "profile.rs");
+
+    for expected in [
+        "    pub nickname: Option<String>,",
+        "    pub score: Option<i64>,",
+        "    pub verified: Option<bool>,",
+        "    #[sea_orm(column_type = \"VarBinary(StringLen::None)\")]\n    pub avatar: Option<Vec<u8>>,",
+    ] {
+        assert!(
+            content.contains(expected),
+            "expected `{}`. Got:\n{}",
+            expected,
+            content
+        );
+    }
+    assert!(
+        !content.contains("JsonBinary") && !content.contains("Value>"),
+        "wrappers should not be stored as embedded messages. Got:\n{}",
+        content
+    );
+}
+
+// ============================================================================
+// Column source tests
+// ============================================================================
+
+#[test]
+fn test_column_from_json_name() {
+    let with_json_name = |name: &str, number: i32, json_name: &str| FieldDescriptorProto {
+        json_name: Some(json_name.to_string()),
+        ..column_field(name, number, Type::String, None)
+    };
+    let user = model_message(
+        "User",
+        r#"table_name: "users""#,
+        vec![
+            FieldDescriptorProto {
+                json_name: Some("id".to_string()),
+                ..column_field("id", 1, Type::Int64, Some("primary_key: true"))
+            },
+            with_json_name("display_name", 2, "displayName"),
+            FieldDescriptorProto {
+                json_name: Some("emailAddress".to_string()),
+                ..column_field(
+                    "email_address",
+                    3,
+                    Type::String,
+                    Some("column_name: \"email\""),
+                )
+            },
+            column_field("nickname", 4, Type::String, None),
+        ],
+    );
+    let mut request = single_file_request(vec![user]);
+    request.parameter = Some("column_from=json_name".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    assert!(
+        content
+            .contains("#[sea_orm(column_name = \"displayName\")]\n    pub display_name: String,"),
+        "the column should be named after the JSON name. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("#[sea_orm(column_name = \"email\")]\n    pub email_address: String,"),
+        "an explicit column name should win. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("    pub nickname: String,") && !content.contains("\"nickname\""),
+        "fields without a JSON name keep the field name. Got:\n{}",
+        content
+    );
+}
+
+// ============================================================================
+// Proto3 optional tests
+// ============================================================================
+
+#[test]
+fn test_proto3_optional_scalar_is_nullable() {
+    let mut person = model_message(
+        "Person",
+        r#"table_name: "people""#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("name", 2, Type::String, None),
+            FieldDescriptorProto {
+                proto3_optional: Some(true),
+                oneof_index: Some(0),
+                ..column_field("age", 3, Type::Int32, None)
+            },
+        ],
+    );
+    person.oneof_decl = vec![OneofDescriptorProto {
+        name: Some("_age".to_string()),
+        ..Default::default()
+    }];
+    let response = protoc_gen_seaorm::generate(single_file_request(vec![person]))
+        .expect("generation should succeed");
+    let content = generated_content(&response, "person.rs");
+
+    assert!(
+        content.contains("    #[sea_orm(nullable)]\n    pub age: Option<i32>,"),
+        "an optional scalar should be a nullable column. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("    pub name: String,"),
+        "fields without presence stay required. Got:\n{}",
+        content
+    );
+    assert!(
+        !content.contains("enum Age") && !content.contains("_age"),
+        "the synthetic oneof should not be treated as a oneof. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Generated Column Tests
+// =============================================================================
+
+#[test]
+fn test_generated_column_is_read_only_and_carries_its_expression() {
+    let person = model_message(
+        "Person",
+        "table_name: \"people\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("first_name", 2, Type::String, None),
+            column_field("last_name", 3, Type::String, None),
+            column_field(
+                "full_name",
+                4,
+                Type::String,
+                Some(r#"generated: "first_name || ' ' || last_name", stored: true"#),
+            ),
+        ],
+    );
+
+    let mut request = single_file_request(vec![person]);
+    request.parameter = Some("emit_migrations=true".to_string());
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+
+    let migration = generated_content(&response, "test/m_create_people.rs");
+    let compact: String = migration.chars().filter(|c| !c.is_whitespace()).collect();
+    assert!(
+        compact
+            .contains("ColumnDef::new(super::person::Column::FullName).string().not_null().extra(")
+            && migration
+                .contains("\"GENERATED ALWAYS AS (first_name || ' ' || last_name) STORED\""),
+        "the generated expression should be part of the column definition. Got:\n{}",
+        migration
+    );
+
+    let entity = generated_content(&response, "person.rs");
+    assert!(
+        entity.contains("self.full_name = sea_orm::ActiveValue::NotSet;"),
+        "saves should never write the generated column. Got:\n{}",
+        entity
+    );
+    assert!(
+        !entity.contains("self.first_name = sea_orm::ActiveValue::NotSet;"),
+        "ordinary columns should stay writable. Got:\n{}",
+        entity
+    );
+}
+
+#[test]
+fn test_generated_column_is_stored_on_postgres() {
+    let migration = |parameter: &str| {
+        let person = model_message(
+            "Person",
+            "table_name: \"people\"",
+            vec![
+                column_field("id", 1, Type::Int64, Some("primary_key: true")),
+                column_field("name", 2, Type::String, None),
+                column_field(
+                    "shout",
+                    3,
+                    Type::String,
+                    Some(r#"generated: "upper(name)""#),
+                ),
+            ],
+        );
+        let mut request = single_file_request(vec![person]);
+        request.parameter = Some(parameter.to_string());
+        let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+        generated_content(&response, "test/m_create_people.rs").to_string()
+    };
+
+    let postgres = migration("emit_migrations=true");
+    assert!(
+        postgres.contains("\"GENERATED ALWAYS AS (upper(name)) STORED\""),
+        "Postgres only supports stored generated columns. Got:\n{}",
+        postgres
+    );
+    let sqlite = migration("emit_migrations=true,backend=sqlite");
+    assert!(
+        sqlite.contains("\"GENERATED ALWAYS AS (upper(name)) VIRTUAL\""),
+        "other backends compute unstored columns on read. Got:\n{}",
+        sqlite
+    );
+}
+
+#[test]
+fn test_generated_column_rejects_defaults() {
+    let person = model_message(
+        "Person",
+        "table_name: \"people\"",
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field(
+                "full_name",
+                2,
+                Type::String,
+                Some(r#"generated: "upper(name)", default_value: "''""#),
+            ),
+        ],
+    );
+
+    let err = protoc_gen_seaorm::generate(single_file_request(vec![person]))
+        .expect_err("a generated column with a default should fail");
+    assert!(
+        err.to_string().contains(
+            "Person.full_name is generated by the database and can't also set default_value"
+        ),
+        "unexpected error: {}",
+        err
+    );
+}
+
+// =============================================================================
+// Enum Value Override Tests
+// =============================================================================
+
+#[test]
+fn test_enum_value_overrides_from_extension_data() {
+    use prost::encoding::{encode_key, encode_varint, WireType};
+    use prost::Message;
+    use protoc_gen_seaorm::options::seaorm;
+
+    fn length_delimited(tag: u32, payload: &[u8], buf: &mut Vec<u8>) {
+        encode_key(tag, WireType::LengthDelimited, buf);
+        encode_varint(payload.len() as u64, buf);
+        buf.extend_from_slice(payload);
+    }
+    fn extension_options(tag: u32, extension: &impl Message) -> Vec<u8> {
+        let mut options = Vec::new();
+        length_delimited(tag, &extension.encode_to_vec(), &mut options);
+        options
+    }
+
+    // (value name, number, string_value override)
+    let values = [
+        ("STATUS_UNKNOWN", 0, None),
+        ("STATUS_ACTIVE", 1, Some("active")),
+        ("STATUS_INACTIVE", 2, Some("disabled")),
+    ];
+
+    let mut enum_bytes = EnumDescriptorProto {
+        name: Some("Status".to_string()),
+        ..Default::default()
+    }
+    .encode_to_vec();
+    for (name, number, string_value) in values {
+        let mut value_bytes = EnumValueDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            ..Default::default()
+        }
+        .encode_to_vec();
+        if let Some(string_value) = string_value {
+            let extension = seaorm::EnumValueOptions {
+                string_value: string_value.to_string(),
+                ..Default::default()
+            };
+            length_delimited(3, &extension_options(50003, &extension), &mut value_bytes);
+        }
+        length_delimited(2, &value_bytes, &mut enum_bytes);
+    }
+    let extension = seaorm::EnumOptions {
+        db_type: "string".to_string(),
+        ..Default::default()
+    };
+    length_delimited(3, &extension_options(50002, &extension), &mut enum_bytes);
+
+    let mut file_bytes = FileDescriptorProto {
+        name: Some("test/status.proto".to_string()),
+        package: Some("test".to_string()),
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    }
+    .encode_to_vec();
+    length_delimited(5, &enum_bytes, &mut file_bytes);
+
+    let mut bytes = CodeGeneratorRequest {
+        file_to_generate: vec!["test/status.proto".to_string()],
+        ..Default::default()
+    }
+    .encode_to_vec();
+    length_delimited(15, &file_bytes, &mut bytes);
+
+    let response =
+        protoc_gen_seaorm::generate_from_bytes(&bytes).expect("generation should succeed");
+    let content = generated_content(&response, "status.rs");
+
+    assert!(
+        content.contains("#[sea_orm(string_value = \"active\")]\n    StatusActive,")
+            && content.contains("#[sea_orm(string_value = \"disabled\")]\n    StatusInactive,"),
+        "overridden values should be stored as given. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains("#[sea_orm(string_value = \"status_unknown\")]\n    StatusUnknown,"),
+        "un-annotated values should keep the default. Got:\n{}",
+        content
+    );
+}
+
+// =============================================================================
+// Package Module Tests
+// =============================================================================
+
+#[test]
+fn test_package_modules_maps_versioned_packages() {
+    let request = |package: &str, parameter: &str| {
+        let mut request = single_file_request(vec![model_message(
+            "Invoice",
+            "table_name: \"invoices\"",
+            vec![column_field(
+                "id",
+                1,
+                Type::Int64,
+                Some("primary_key: true"),
+            )],
+        )]);
+        request.proto_file[0].package = Some(package.to_string());
+        request.parameter = Some(parameter.to_string()).filter(|p| !p.is_empty());
+        request
+    };
+    let entity_files = |request| -> Vec<String> {
+        protoc_gen_seaorm::generate(request)
+            .expect("generation should succeed")
+            .file
+            .into_iter()
+            .filter_map(|f| f.name)
+            .collect()
+    };
+
+    assert_eq!(
+        entity_files(request("acme.billing.v1", "")),
+        vec!["acme/billing/v1/invoice.rs"]
+    );
+    assert_eq!(
+        entity_files(request("acme.billing.v1", "package_modules=strip_version")),
+        vec!["acme/billing/invoice.rs"]
+    );
+    assert_eq!(
+        entity_files(request(
+            "acme.billing.v1beta2",
+            "package_modules=strip_version"
+        )),
+        vec!["acme/billing/invoice.rs"]
+    );
+    assert_eq!(
+        entity_files(request("acme.billing.v1", "package_modules=flatten")),
+        vec!["acme_billing_v1/invoice.rs"]
+    );
+
+    let err = protoc_gen_seaorm::generate(request("acme.billing.v1", "package_modules=split"))
+        .expect_err("unknown layouts should fail");
+    assert!(
+        err.to_string()
+            .contains("package_modules expects nested, strip_version or flatten"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn test_package_modules_strip_version_addresses_related_packages() {
+    let account = FileDescriptorProto {
+        name: Some("auth/v1/account.proto".to_string()),
+        package: Some("auth.v1".to_string()),
+        message_type: vec![model_message(
+            "Account",
+            "table_name: \"accounts\"",
+            vec![column_field(
+                "id",
+                1,
+                Type::Int64,
+                Some("primary_key: true"),
+            )],
+        )],
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    };
+    let invoice = FileDescriptorProto {
+        name: Some("billing/v1/invoice.proto".to_string()),
+        package: Some("billing.v1".to_string()),
+        dependency: vec!["auth/v1/account.proto".to_string()],
+        message_type: vec![model_message(
+            "Invoice",
+            r#"table_name: "invoices", relations: [
+                {name: "account", type: RELATION_TYPE_BELONGS_TO, related: "auth.v1.Account", foreign_key: "account_id"}
+            ]"#,
+            vec![
+                column_field("id", 1, Type::Int64, Some("primary_key: true")),
+                column_field("account_id", 2, Type::Int64, None),
+            ],
+        )],
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    };
+    let request = CodeGeneratorRequest {
+        file_to_generate: vec![
+            "auth/v1/account.proto".to_string(),
+            "billing/v1/invoice.proto".to_string(),
+        ],
+        parameter: Some("package_modules=strip_version,module_root=crate::entities".to_string()),
+        proto_file: vec![account, invoice],
+        ..Default::default()
+    };
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "billing/invoice.rs");
+    assert!(
+        content.contains("crate::entities::auth::account::Entity"),
+        "relations should use the stripped module path. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_package_modules_rejects_colliding_packages() {
+    let file = |version: &str| FileDescriptorProto {
+        name: Some(format!("billing/{}/invoice.proto", version)),
+        package: Some(format!("billing.{}", version)),
+        message_type: vec![model_message(
+            "Invoice",
+            &format!("table_name: \"invoices_{}\"", version),
+            vec![column_field(
+                "id",
+                1,
+                Type::Int64,
+                Some("primary_key: true"),
+            )],
+        )],
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    };
+    let request = CodeGeneratorRequest {
+        file_to_generate: vec![
+            "billing/v1/invoice.proto".to_string(),
+            "billing/v2/invoice.proto".to_string(),
+        ],
+        parameter: Some("package_modules=strip_version".to_string()),
+        proto_file: vec![file("v1"), file("v2")],
+        ..Default::default()
+    };
+
+    let errors = protoc_gen_seaorm::validate(&request).expect_err("validate should see it");
+    assert!(
+        errors.iter().any(|e| e
+            .to_string()
+            .contains("packages billing.v1 and billing.v2 both map to module billing")),
+        "unexpected errors: {:?}",
+        errors
+    );
+
+    let err = protoc_gen_seaorm::generate(request).expect_err("colliding modules should fail");
+    assert!(
+        err.to_string()
+            .contains("packages billing.v1 and billing.v2 both map to module billing"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn test_package_modules_place_enums_with_their_package() {
+    let mut request = create_enum_test_request();
+    request.proto_file[0].name = Some("acme/billing/v1/status.proto".to_string());
+    request.proto_file[0].package = Some("acme.billing.v1".to_string());
+    request.file_to_generate = vec!["acme/billing/v1/status.proto".to_string()];
+    request.parameter = Some("package_modules=strip_version".to_string());
+
+    let names: Vec<String> = protoc_gen_seaorm::generate(request)
+        .expect("generation should succeed")
+        .file
+        .into_iter()
+        .filter_map(|f| f.name)
+        .collect();
+    assert_eq!(names, vec!["acme/billing/status.rs"]);
+}
+
+// =============================================================================
+// Enum Default Tests
+// =============================================================================
+
+#[test]
+fn test_enum_derives_default_on_zero_value() {
+    let response =
+        protoc_gen_seaorm::generate(create_enum_test_request()).expect("generation should succeed");
+    let content = generated_content(&response, "status.rs");
+
+    assert!(
+        content.contains(
+            "#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, EnumIter, DeriveActiveEnum)]"
+        ),
+        "enum should derive Default. Got:\n{}",
+        content
+    );
+    assert!(
+        content.contains(
+            "#[default]\n    #[sea_orm(string_value = \"status_unknown\")]\n    StatusUnknown,"
+        ),
+        "the zero value should be the default variant. Got:\n{}",
+        content
+    );
+    assert_eq!(
+        content.matches("#[default]").count(),
+        1,
+        "only one variant should be the default. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_enum_derive_default_opt_out() {
+    let mut request = create_enum_test_request();
+    request.proto_file[0].enum_type[0].options = Some(EnumOptions {
+        uninterpreted_option: vec![seaorm_option(
+            "enum_opt",
+            "db_type: \"string\", derive_default: false",
+        )],
+        ..Default::default()
+    });
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "status.rs");
+
+    assert!(
+        !content.contains("Default") && !content.contains("#[default]"),
+        "derive_default: false should leave Default out. Got:\n{}",
+        content
+    );
+}
+
+#[test]
+fn test_no_prelude_qualifies_every_sea_orm_name() {
+    let created_at = timestamp_field("created_at", 3, "temporal: \"local\"");
+    let user = model_message(
+        "User",
+        r#"table_name: "users", soft_delete: "deleted_at", filter_struct: true"#,
+        vec![
+            column_field("id", 1, Type::Int64, Some("primary_key: true")),
+            column_field("email", 2, Type::String, None),
+            created_at,
+        ],
+    );
+    let mut request = single_file_request(vec![user]);
+    request.parameter = Some("no_prelude,manual_entity".to_string());
+
+    let response = protoc_gen_seaorm::generate(request).expect("generation should succeed");
+    let content = generated_content(&response, "user.rs");
+
+    for path in [
+        "pub fn find_active() -> sea_orm::entity::prelude::Select<Entity>",
+        "mut query: sea_orm::entity::prelude::Select<Entity>,",
+        "pub created_at: sea_orm::entity::prelude::DateTimeLocal,",
+        "sea_orm::entity::prelude::DeriveEntity",
+        "sea_orm::entity::prelude::DeriveModel",
+        "sea_orm::entity::prelude::DeriveActiveModel",
+        "sea_orm::entity::prelude::DeriveColumn",
+        "sea_orm::entity::prelude::DerivePrimaryKey",
+        "impl sea_orm::entity::prelude::EntityName for Entity",
+        "impl sea_orm::entity::prelude::PrimaryKeyTrait for PrimaryKey",
+        "fn def(&self) -> sea_orm::entity::prelude::ColumnDef",
+        "type EntityName = Entity;",
+        "use sea_orm::entity::prelude::ColumnTypeTrait as _;",
+    ] {
+        assert!(
+            content.contains(path),
+            "should contain `{}`. Got:\n{}",
+            path,
+            content
+        );
+    }
+    for bare in [
+        " Select<",
+        ": DateTimeLocal",
+        "(ColumnType::",
+        " ColumnDef {",
+        "impl EntityName",
+        "impl RelationTrait",
+        "-> RelationDef",
+    ] {
+        assert!(
+            !content.contains(bare),
+            "`{}` should be qualified. Got:\n{}",
+            bare,
+            content
+        );
+    }
+}